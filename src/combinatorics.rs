@@ -0,0 +1,251 @@
+#[cfg(feature = "big-integer")]
+use num_bigint::BigUint;
+
+// Purpose:
+//     To count arrangements without silently overflowing, where each count is either checked within a fixed-size integer or, with the "big-integer" feature, kept at arbitrary precision
+// Notes:
+//     Each intermediate value is a count of its own, so a count only fails to be returned when the count itself does not fit
+pub trait Count: Sized + Clone + Ord {
+    fn from_u64(value: u64) -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    // multiplies by the numerator and then divides by the denominator, where the denominator is expected to evenly divide the product
+    fn checked_mul_div(&self, numerator: u64, denominator: u64) -> Option<Self>;
+}
+
+impl Count for u64 {
+    fn from_u64(value: u64) -> Self {
+        return value;
+    }
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        return u64::checked_add(*self, *other);
+    }
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        return u64::checked_sub(*self, *other);
+    }
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        return u64::checked_mul(*self, *other);
+    }
+    fn checked_mul_div(&self, numerator: u64, denominator: u64) -> Option<Self> {
+        // the product of two u64 values always fits within a u128
+        let quotient = (*self as u128 * numerator as u128) / denominator as u128;
+        return u64::try_from(quotient).ok();
+    }
+}
+
+impl Count for u128 {
+    fn from_u64(value: u64) -> Self {
+        return value as u128;
+    }
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        return u128::checked_add(*self, *other);
+    }
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        return u128::checked_sub(*self, *other);
+    }
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        return u128::checked_mul(*self, *other);
+    }
+    fn checked_mul_div(&self, numerator: u64, denominator: u64) -> Option<Self> {
+        // divide out the common factor first so that the product only overflows if the quotient would
+        let common_factor = get_greatest_common_divisor(*self, denominator as u128);
+        let reduced_denominator = denominator as u128 / common_factor;
+        if !(numerator as u128).is_multiple_of(reduced_denominator) {
+            panic!("Unexpected denominator not evenly dividing the product.");
+        }
+        return (*self / common_factor).checked_mul(numerator as u128 / reduced_denominator);
+    }
+}
+
+#[cfg(feature = "big-integer")]
+impl Count for BigUint {
+    fn from_u64(value: u64) -> Self {
+        return BigUint::from(value);
+    }
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        return Some(self + other);
+    }
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if other > self {
+            return None;
+        }
+        return Some(self - other);
+    }
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        return Some(self * other);
+    }
+    fn checked_mul_div(&self, numerator: u64, denominator: u64) -> Option<Self> {
+        return Some(self * numerator / denominator);
+    }
+}
+
+fn get_greatest_common_divisor(mut first: u128, mut second: u128) -> u128 {
+    while second != 0 {
+        (first, second) = (second, first % second);
+    }
+    return first;
+}
+
+/// Returns the number of ways to choose k items from n items, or None if the count does not fit.
+pub fn get_binomial<T: Count>(n: u64, k: u64) -> Option<T> {
+    if k > n {
+        return Some(T::from_u64(0));
+    }
+    // the running value is the number of ways to choose f_k items from n items, which is always a whole number
+    let mut binomial = T::from_u64(1);
+    for f_k in 1..=k.min(n - k) {
+        binomial = binomial.checked_mul_div(n - f_k + 1, f_k)?;
+    }
+    return Some(binomial);
+}
+
+/// Returns the number of orders of n distinct items, or None if the count does not fit.
+pub fn get_factorial<T: Count>(n: u64) -> Option<T> {
+    return get_permutations(n, n);
+}
+
+/// Returns the number of orders of k distinct items chosen from n items, or None if the count does not fit.
+pub fn get_permutations<T: Count>(n: u64, k: u64) -> Option<T> {
+    if k > n {
+        return Some(T::from_u64(0));
+    }
+    let mut permutations = T::from_u64(1);
+    for f_k in (n - k + 1)..=n {
+        permutations = permutations.checked_mul(&T::from_u64(f_k))?;
+    }
+    return Some(permutations);
+}
+
+/// Returns the number of ways to split the items into groups of the provided sizes, where the items within a group are unordered, or None if the count does not fit.
+pub fn get_multinomial<T: Count>(group_sizes: &[u64]) -> Option<T> {
+    let mut multinomial = T::from_u64(1);
+    let mut items_total: u64 = 0;
+    for group_size in group_sizes.iter() {
+        items_total = items_total.checked_add(*group_size)?;
+        multinomial = multinomial.checked_mul(&get_binomial(items_total, *group_size)?)?;
+    }
+    return Some(multinomial);
+}
+
+/// Returns the number of ways to place the segments within the bounding length such that consecutive segments are separated by at least the padding, or None if the count does not fit.
+/// When swapping is permitted, each order of the segments is counted separately, even if segments share the same length.
+pub fn get_padded_permutations<T: Count>(segment_lengths: &[usize], bounding_length: usize, padding: usize, is_swapping_permitted: bool) -> Option<T> {
+    let mut minimum_bounding_length: usize = segment_lengths.iter().sum();
+    minimum_bounding_length += padding * segment_lengths.len().saturating_sub(1);
+    if minimum_bounding_length > bounding_length {
+        return Some(T::from_u64(0));
+    }
+    // each unit of slack is either placed before one of the segments or after the last segment
+    let slack = (bounding_length - minimum_bounding_length) as u64;
+    let segments_total = segment_lengths.len() as u64;
+    let position_offsets_total: T = get_binomial(slack + segments_total, segments_total)?;
+    if !is_swapping_permitted {
+        return Some(position_offsets_total);
+    }
+    return position_offsets_total.checked_mul(&get_factorial(segments_total)?);
+}
+
+#[cfg(test)]
+mod combinatorics_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    // returns the binomial from Pascal's triangle, which only ever adds
+    fn get_pascal_binomial(n: u64, k: u64) -> u128 {
+        let mut row: Vec<u128> = vec![1];
+        for _ in 0..n {
+            let mut next_row: Vec<u128> = vec![1];
+            for pair in row.windows(2) {
+                next_row.push(pair[0] + pair[1]);
+            }
+            next_row.push(1);
+            row = next_row;
+        }
+        return row.get(k as usize).cloned().unwrap_or(0);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(5, 2)]
+    #[case(5, 3)]
+    #[case(10, 0)]
+    #[case(10, 11)]
+    #[case(30, 7)]
+    #[case(70, 35)]
+    #[case(120, 60)]
+    fn binomial_matches_pascal_triangle(#[case] n: u64, #[case] k: u64) {
+        init();
+
+        let expected = get_pascal_binomial(n, k);
+        assert_eq!(Some(expected), get_binomial::<u128>(n, k));
+        assert_eq!(u64::try_from(expected).ok(), get_binomial::<u64>(n, k));
+    }
+
+    #[rstest]
+    fn binomial_overflow_is_reported() {
+        init();
+
+        assert_eq!(Some(112186277816662845432), get_binomial::<u128>(70, 35));
+        assert_eq!(None, get_binomial::<u64>(70, 35));
+        assert_eq!(None, get_binomial::<u128>(200, 100));
+        // the largest central binomial that fits within a u64
+        assert_eq!(Some(14226520737620288370), get_binomial::<u64>(67, 33));
+        assert_eq!(None, get_binomial::<u64>(68, 34));
+    }
+
+    #[rstest]
+    fn factorial_and_permutations() {
+        init();
+
+        assert_eq!(Some(1), get_factorial::<u64>(0));
+        assert_eq!(Some(120), get_factorial::<u64>(5));
+        assert_eq!(Some(2432902008176640000), get_factorial::<u64>(20));
+        assert_eq!(None, get_factorial::<u64>(21));
+        assert_eq!(Some(51090942171709440000), get_factorial::<u128>(21));
+        assert_eq!(Some(60), get_permutations::<u64>(5, 3));
+        assert_eq!(Some(0), get_permutations::<u64>(3, 5));
+    }
+
+    #[rstest]
+    #[case(vec![], 1)]
+    #[case(vec![3], 1)]
+    #[case(vec![2, 1], 3)]
+    #[case(vec![2, 2, 2], 90)]
+    #[case(vec![1, 1, 1, 1], 24)]
+    #[case(vec![0, 4, 1], 5)]
+    fn multinomial_counts_groupings(#[case] group_sizes: Vec<u64>, #[case] expected: u64) {
+        init();
+
+        assert_eq!(Some(expected), get_multinomial::<u64>(&group_sizes));
+        assert_eq!(Some(expected as u128), get_multinomial::<u128>(&group_sizes));
+    }
+
+    #[rstest]
+    #[case(vec![1, 2, 1], 8, 1, false, 10)]
+    #[case(vec![1, 2, 1], 8, 1, true, 60)]
+    #[case(vec![2, 3], 8, 1, false, 6)]
+    #[case(vec![2, 3], 5, 1, true, 0)]
+    #[case(vec![1], 5, 3, true, 5)]
+    #[case(vec![], 5, 1, true, 1)]
+    fn padded_permutations_counts_arrangements(#[case] segment_lengths: Vec<usize>, #[case] bounding_length: usize, #[case] padding: usize, #[case] is_swapping_permitted: bool, #[case] expected: u64) {
+        init();
+
+        assert_eq!(Some(expected), get_padded_permutations::<u64>(&segment_lengths, bounding_length, padding, is_swapping_permitted));
+    }
+
+    #[cfg(feature = "big-integer")]
+    #[rstest]
+    fn big_integer_binomial_does_not_overflow() {
+        init();
+
+        let binomial: BigUint = get_binomial(200, 100).unwrap();
+        assert_eq!("90548514656103281165404177077484163874504589675413336841320", binomial.to_string());
+        assert_eq!(Some(BigUint::from(get_pascal_binomial(120, 60))), get_binomial::<BigUint>(120, 60));
+    }
+}
@@ -1,10 +1,134 @@
 use std::rc::Rc;
 use bitvec::vec::BitVec;
 use crate::IndexedElement;
-use super::Incrementer;
+use super::{Incrementer};
+
+// Purpose:
+//     To hold the bits of a binary density incrementer, where the bits are kept within a single word whenever the length permits
+// Notes:
+//     The word stores the bit at index 0 as its most significant bit, so each next arrangement of the same density is found in a few word operations instead of a scan per bit
+pub(crate) enum DensityBits {
+    Word(u128, usize),
+    BitVec(BitVec)
+}
+
+impl DensityBits {
+    pub(crate) fn new_with_leading_ones(ones_total: usize, length: usize) -> Self {
+        if length <= u128::BITS as usize {
+            return DensityBits::Word(get_leading_ones_word(ones_total, length), length);
+        }
+        let mut bits: BitVec = BitVec::repeat(true, ones_total);
+        let mut remaining_bits: BitVec = BitVec::repeat(false, length - ones_total);
+        bits.append(&mut remaining_bits);
+        return DensityBits::BitVec(bits);
+    }
+    pub(crate) fn set_leading_ones(&mut self, ones_total: usize) {
+        match self {
+            DensityBits::Word(word, length) => {
+                *word = get_leading_ones_word(ones_total, *length);
+            },
+            DensityBits::BitVec(bits) => {
+                for index in 0..bits.len() {
+                    bits.set(index, index < ones_total);
+                }
+            }
+        }
+    }
+    pub(crate) fn get(&self, index: usize) -> bool {
+        match self {
+            DensityBits::Word(word, length) => {
+                return (word >> (*length - 1 - index)) & 1 == 1;
+            },
+            DensityBits::BitVec(bits) => {
+                return bits[index];
+            }
+        }
+    }
+    // returns the word where the bit at index 0 is the least significant bit, if the bits fit within a word
+    pub(crate) fn get_bits(&self) -> Option<u128> {
+        match self {
+            DensityBits::Word(word, length) => {
+                if *length == 0 {
+                    return Some(0);
+                }
+                return Some(word.reverse_bits() >> (u128::BITS as usize - *length));
+            },
+            DensityBits::BitVec(_) => {
+                return None;
+            }
+        }
+    }
+    // moves to the next arrangement with the same number of ones, returning false if the ones have all built up on the end
+    pub(crate) fn try_shift(&mut self) -> bool {
+        match self {
+            DensityBits::Word(word, length) => {
+                // the zeros only ever move toward the most significant bit, so the next arrangement is the next larger word of the same density for the complement
+                let mask = get_leading_ones_word(*length, *length);
+                let zeros = !*word & mask;
+                if zeros == 0 {
+                    return false;
+                }
+                let lowest_zero = zeros & zeros.wrapping_neg();
+                let Some(carried_zeros) = zeros.checked_add(lowest_zero) else {
+                    return false;
+                };
+                if carried_zeros > mask {
+                    return false;
+                }
+                let next_zeros = carried_zeros | (((carried_zeros ^ zeros) >> 2) / lowest_zero);
+                *word = !next_zeros & mask;
+                return true;
+            },
+            DensityBits::BitVec(bits) => {
+                let length = bits.len();
+                let mut is_zero_found = false;
+                let mut rightmost_zero_before_one_index_option: Option<usize> = None;
+                for index in (0..length).rev() {
+                    if bits[index] {
+                        if is_zero_found && rightmost_zero_before_one_index_option.is_none() {
+                            rightmost_zero_before_one_index_option = Some(index + 1);
+                            break;
+                        }
+                    }
+                    else {
+                        is_zero_found = true;
+                    }
+                }
+                if let Some(index) = rightmost_zero_before_one_index_option {
+                    // move the rightmost one to the right since we've guaranteed that it was empty
+                    bits.set(index - 1, false);
+                    bits.set(index, true);
+
+                    // move all of the ones built up on the right side of the structure back to the point where we just moved a bit over
+                    let mut zero_index = index + 1;
+                    let mut one_index = index + 1;
+                    while one_index < length && !bits[one_index] {
+                        one_index += 1;
+                    }
+                    while one_index < length && !bits[zero_index] {
+                        bits.set(zero_index, true);
+                        bits.set(one_index, false);
+                        zero_index += 1;
+                        one_index += 1;
+                    }
+                    return true;
+                }
+                return false;
+            }
+        }
+    }
+}
+
+fn get_leading_ones_word(ones_total: usize, length: usize) -> u128 {
+    if ones_total == 0 {
+        return 0;
+    }
+    return (u128::MAX >> (u128::BITS as usize - ones_total)) << (length - ones_total);
+}
+
 pub struct BinaryDensityIncrementer {
     length: usize,
-    current_state: BitVec,
+    current_state: DensityBits,
     current_ones_total: usize,
     is_started: bool
 }
@@ -13,11 +137,15 @@ impl BinaryDensityIncrementer {
     pub fn new(length: usize) -> Self {
         BinaryDensityIncrementer {
             length,
-            current_state: BitVec::repeat(false, length),
+            current_state: DensityBits::new_with_leading_ones(0, length),
             current_ones_total: 0,
             is_started: false
         }
     }
+    /// Returns the current bits as a word where the bit at index 0 is the least significant bit, or None if the length is beyond 128 bits.
+    pub fn get_bits(&self) -> Option<u128> {
+        return self.current_state.get_bits();
+    }
 }
 
 impl Incrementer for BinaryDensityIncrementer {
@@ -32,61 +160,27 @@ impl Incrementer for BinaryDensityIncrementer {
             return true;
         }
         if self.is_started && self.current_ones_total == 0 {
-            self.current_state.set(0, true);
             self.current_ones_total = 1;
+            self.current_state.set_leading_ones(self.current_ones_total);
             return true;
         }
-        let mut is_zero_found = false;
-        let mut rightmost_zero_before_one_index_option: Option<usize> = None;
-        for index in (0..self.length).rev() {
-            if self.current_state[index] {
-                if is_zero_found && rightmost_zero_before_one_index_option.is_none() {
-                    rightmost_zero_before_one_index_option = Some(index + 1);
-                    break;
-                }
-            }
-            else {
-                is_zero_found = true;
-            }
-        }
-        if let Some(index) = rightmost_zero_before_one_index_option {
-            // move the rightmost one to the right since we've guaranteed that it was empty
-            self.current_state.set(index - 1, false);
-            self.current_state.set(index, true);
-            
-            // move all of the ones built up on the right side of the structure back to the point where we just moved a bit over
-            let mut zero_index = index + 1;
-            let mut one_index = index + 1;
-            while one_index < self.length && !self.current_state[one_index] {
-                one_index += 1;
-            }
-            while one_index < self.length && !self.current_state[zero_index] {
-                self.current_state.set(zero_index, true);
-                self.current_state.set(one_index, false);
-                zero_index += 1;
-                one_index += 1;
-            }
-        }
-        else {
+        if !self.current_state.try_shift() {
             // if all of the ones have built up on the end of the state, we need to restart them on the left and add one
             self.current_ones_total += 1;
-            for index in 0..self.length {
-                let state = index < self.current_ones_total;
-                self.current_state.set(index, state);
-            }
+            self.current_state.set_leading_ones(self.current_ones_total);
         }
         return true;
     }
     fn get(&self) -> Vec<IndexedElement<Self::T>> {
         let mut indexed_elements = Vec::new();
         for index in 0..self.length {
-            let indexed_element: IndexedElement<Self::T> = IndexedElement::new(Rc::new(self.current_state[index]), index);
+            let indexed_element: IndexedElement<Self::T> = IndexedElement::new(Rc::new(self.current_state.get(index)), index);
             indexed_elements.push(indexed_element);
         }
         return indexed_elements;
     }
     fn reset(&mut self) {
-        self.current_state = BitVec::repeat(false, self.length);
+        self.current_state.set_leading_ones(0);
         self.current_ones_total = 0;
         self.is_started = false;
     }
@@ -95,17 +189,6 @@ impl Incrementer for BinaryDensityIncrementer {
     }
 }
 
-impl Iterator for BinaryDensityIncrementer {
-    type Item = Vec<IndexedElement<bool>>;
-
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.try_increment() {
-            return Some(self.get());
-        }
-        return None;
-    }
-}
-
 #[cfg(test)]
 mod binary_density_incrementer {
     use std::{time::{Duration, Instant}, cell::RefCell, collections::BTreeSet};
@@ -510,4 +593,56 @@ mod binary_density_incrementer {
             incrementer.reset();
         }
     }
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(5)]
+    #[case(8)]
+    #[case(12)]
+    fn word_matches_bit_vector(#[case] length: usize) {
+        init();
+
+        let mut word_incrementer = BinaryDensityIncrementer::new(length);
+        let mut bit_vector_incrementer = BinaryDensityIncrementer::new(length);
+        bit_vector_incrementer.current_state = DensityBits::BitVec(BitVec::repeat(false, length));
+        for _ in 0..2 {
+            let mut states_total: usize = 0;
+            while word_incrementer.try_increment() {
+                assert!(bit_vector_incrementer.try_increment());
+                assert_eq!(bit_vector_incrementer.get(), word_incrementer.get());
+                assert!(bit_vector_incrementer.get_bits().is_none());
+                let bits = word_incrementer.get_bits().unwrap();
+                for indexed_element in word_incrementer.get() {
+                    assert_eq!(*indexed_element.element, (bits >> indexed_element.index) & 1 == 1);
+                }
+                states_total += 1;
+            }
+            assert!(!bit_vector_incrementer.try_increment());
+            assert_eq!(1 << length, states_total);
+            word_incrementer.reset();
+            bit_vector_incrementer.reset();
+        }
+    }
+
+    #[rstest]
+    #[case(128, 4000)]
+    #[case(129, 4000)]
+    fn long_lengths_start_with_lowest_densities(#[case] length: usize, #[case] states_total: usize) {
+        init();
+
+        let mut incrementer = BinaryDensityIncrementer::new(length);
+        assert_eq!(length <= 128, incrementer.get_bits().is_some());
+        let mut previous_ones_total: usize = 0;
+        for _ in 0..states_total {
+            assert!(incrementer.try_increment());
+            let ones_total = incrementer.get().into_iter().filter(|indexed_element| *indexed_element.element).count();
+            assert!(ones_total == previous_ones_total || ones_total == previous_ones_total + 1);
+            if let Some(bits) = incrementer.get_bits() {
+                assert_eq!(ones_total, bits.count_ones() as usize);
+            }
+            previous_ones_total = ones_total;
+        }
+        // the zero and single densities are exhausted before the third density begins
+        assert_eq!(2, previous_ones_total);
+    }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use std::rc::Rc;
 use bitvec::vec::BitVec;
 use crate::IndexedElement;
-use super::Incrementer;
+use super::{Incrementer};
 pub struct BinaryValueIncrementer {
     length: usize,
     current_state: BitVec,
@@ -57,17 +57,6 @@ impl Incrementer for BinaryValueIncrementer {
     }
 }
 
-impl Iterator for BinaryValueIncrementer {
-    type Item = Vec<IndexedElement<bool>>;
-
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.try_increment() {
-            return Some(self.get());
-        }
-        return None;
-    }
-}
-
 #[cfg(test)]
 mod binary_value_incrementer {
     use std::{time::{Duration, Instant}, cell::RefCell, collections::BTreeSet};
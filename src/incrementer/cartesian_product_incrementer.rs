@@ -0,0 +1,145 @@
+use crate::IndexedElement;
+use super::{Incrementer};
+
+// Purpose:
+//     To increment over every combination of the increments of each incrementer, where the last incrementer changes most often like the digits of an odometer
+//     Each incrementer is reset and incremented again once it is unable to increment, so each incrementer must return the same increments after being reset
+pub struct CartesianProductIncrementer<T> {
+    incrementers: Vec<Box<dyn Incrementer<T = T>>>,
+    is_started: bool,
+    is_completed: bool
+}
+
+impl<T> CartesianProductIncrementer<T> {
+    pub fn new(incrementers: Vec<Box<dyn Incrementer<T = T>>>) -> Self {
+        let is_completed = incrementers.len() == 0;
+        CartesianProductIncrementer {
+            incrementers: incrementers,
+            is_started: false,
+            is_completed: is_completed
+        }
+    }
+}
+
+impl<T> Incrementer for CartesianProductIncrementer<T> {
+    type T = T;
+
+    fn try_increment(&mut self) -> bool {
+        if self.is_completed {
+            return false;
+        }
+        if !self.is_started {
+            self.is_started = true;
+            for incrementer in self.incrementers.iter_mut() {
+                if !incrementer.try_increment() {
+                    // an incrementer without any increments leaves no combinations
+                    self.is_completed = true;
+                    return false;
+                }
+            }
+            return true;
+        }
+        let mut incrementer_index = self.incrementers.len();
+        while incrementer_index != 0 {
+            incrementer_index -= 1;
+            if self.incrementers[incrementer_index].try_increment() {
+                return true;
+            }
+            // start this incrementer over and carry over to the previous incrementer
+            self.incrementers[incrementer_index].reset();
+            if !self.incrementers[incrementer_index].try_increment() {
+                panic!("Unexpectedly failed to increment after reset when the incrementer previously incremented.");
+            }
+        }
+        self.is_completed = true;
+        return false;
+    }
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
+        let mut indexed_elements: Vec<IndexedElement<T>> = Vec::new();
+        for incrementer in self.incrementers.iter() {
+            indexed_elements.append(&mut incrementer.get());
+        }
+        return indexed_elements;
+    }
+    fn reset(&mut self) {
+        for incrementer in self.incrementers.iter_mut() {
+            incrementer.reset();
+        }
+        self.is_started = false;
+        self.is_completed = self.incrementers.len() == 0;
+    }
+    fn randomize(&mut self) {
+        for incrementer in self.incrementers.iter_mut() {
+            incrementer.randomize();
+        }
+    }
+}
+
+#[cfg(test)]
+mod cartesian_product_incrementer_tests {
+    use std::rc::Rc;
+
+    use crate::{incrementer::{limited_incrementer::LimitedIncrementer, shifter_incrementer::ShifterIncrementer}, shifter::index_shifter::IndexShifter};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    fn get_incrementer(states: Vec<u8>, index: usize) -> Box<dyn Incrementer<T = u8>> {
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![states.into_iter().map(Rc::new).collect()];
+        return Box::new(ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![index]));
+    }
+
+    #[rstest]
+    fn no_incrementers() {
+        init();
+
+        let mut cartesian_product_incrementer: CartesianProductIncrementer<u8> = CartesianProductIncrementer::new(Vec::new());
+        for _ in 0..10 {
+            assert!(!cartesian_product_incrementer.try_increment());
+        }
+    }
+
+    #[rstest]
+    fn empty_incrementer_leaves_no_combinations() {
+        init();
+
+        let mut cartesian_product_incrementer = CartesianProductIncrementer::new(vec![
+            get_incrementer(vec![1, 2], 0),
+            Box::new(LimitedIncrementer::new(get_incrementer(vec![0], 1), 0))
+        ]);
+        for _ in 0..10 {
+            assert!(!cartesian_product_incrementer.try_increment());
+        }
+    }
+
+    #[rstest]
+    fn every_combination() {
+        init();
+
+        let mut cartesian_product_incrementer = CartesianProductIncrementer::new(vec![
+            get_incrementer(vec![1, 2], 0),
+            get_incrementer(vec![3], 1),
+            get_incrementer(vec![4, 5, 6], 2)
+        ]);
+        for _ in 0..3 {
+            let states_per_increment: Vec<Vec<(usize, u8)>> = cartesian_product_incrementer.iter()
+                .map(|indexed_elements| indexed_elements.iter().map(|indexed_element| (indexed_element.index, *indexed_element.element)).collect())
+                .collect();
+            assert_eq!(vec![
+                vec![(0, 1), (1, 3), (2, 4)],
+                vec![(0, 1), (1, 3), (2, 5)],
+                vec![(0, 1), (1, 3), (2, 6)],
+                vec![(0, 2), (1, 3), (2, 4)],
+                vec![(0, 2), (1, 3), (2, 5)],
+                vec![(0, 2), (1, 3), (2, 6)]
+            ], states_per_increment);
+            assert!(!cartesian_product_incrementer.try_increment());
+            cartesian_product_incrementer.reset();
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::IndexedElement;
+use super::{Incrementer};
+
+// Purpose:
+//     To increment over each incrementer to completion, one after the other, in the order that they were provided
+pub struct ChainIncrementer<T> {
+    incrementers: Vec<Box<dyn Incrementer<T = T>>>,
+    current_incrementer_index: usize
+}
+
+impl<T> ChainIncrementer<T> {
+    pub fn new(incrementers: Vec<Box<dyn Incrementer<T = T>>>) -> Self {
+        ChainIncrementer {
+            incrementers: incrementers,
+            current_incrementer_index: 0
+        }
+    }
+}
+
+impl<T> Incrementer for ChainIncrementer<T> {
+    type T = T;
+
+    fn try_increment(&mut self) -> bool {
+        while self.current_incrementer_index != self.incrementers.len() {
+            if self.incrementers[self.current_incrementer_index].try_increment() {
+                return true;
+            }
+            self.current_incrementer_index += 1;
+        }
+        return false;
+    }
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
+        return self.incrementers[self.current_incrementer_index].get();
+    }
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        self.incrementers[self.current_incrementer_index].visit(visitor);
+    }
+    fn reset(&mut self) {
+        for incrementer in self.incrementers.iter_mut() {
+            incrementer.reset();
+        }
+        self.current_incrementer_index = 0;
+    }
+    fn randomize(&mut self) {
+        // the order of the incrementers is kept since it is the purpose of the chain
+        for incrementer in self.incrementers.iter_mut() {
+            incrementer.randomize();
+        }
+    }
+}
+
+#[cfg(test)]
+mod chain_incrementer_tests {
+    use std::rc::Rc;
+
+    use crate::{incrementer::{limited_incrementer::LimitedIncrementer, shifter_incrementer::ShifterIncrementer}, shifter::index_shifter::IndexShifter};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    fn get_incrementer(states: Vec<u8>) -> Box<dyn Incrementer<T = u8>> {
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![states.into_iter().map(Rc::new).collect()];
+        return Box::new(ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![0]));
+    }
+
+    #[rstest]
+    fn no_incrementers() {
+        init();
+
+        let mut chain_incrementer: ChainIncrementer<u8> = ChainIncrementer::new(Vec::new());
+        for _ in 0..10 {
+            assert!(!chain_incrementer.try_increment());
+        }
+    }
+
+    #[rstest]
+    fn each_incrementer_in_order() {
+        init();
+
+        let mut chain_incrementer = ChainIncrementer::new(vec![
+            get_incrementer(vec![1, 2]),
+            Box::new(LimitedIncrementer::new(get_incrementer(vec![0]), 0)),
+            get_incrementer(vec![3])
+        ]);
+        for _ in 0..3 {
+            let states: Vec<u8> = chain_incrementer.iter()
+                .map(|indexed_elements| *indexed_elements[0].element)
+                .collect();
+            assert_eq!(vec![1, 2, 3], states);
+            assert!(!chain_incrementer.try_increment());
+            chain_incrementer.reset();
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{shifter::Shifter, IndexedElement};
+use super::{Incrementer};
+
+// Purpose:
+//     To determine if the latest element of a partial assignment is consistent with the elements before it
+//     The last element of indexed_elements is the one that was just assigned, so each earlier element has already been found to be consistent
+pub trait Constraint<T> {
+    fn is_satisfied(&self, indexed_elements: &Vec<IndexedElement<T>>) -> bool;
+}
+
+// Purpose:
+//     A constraint that checks the latest element against each earlier element one pair at a time
+//     The first element provided to the function is always the earlier element
+pub struct PairwiseConstraint<T> {
+    is_valid_pair: Box<dyn Fn(&IndexedElement<T>, &IndexedElement<T>) -> bool>
+}
+
+impl<T> PairwiseConstraint<T> {
+    pub fn new(is_valid_pair: Box<dyn Fn(&IndexedElement<T>, &IndexedElement<T>) -> bool>) -> Self {
+        PairwiseConstraint {
+            is_valid_pair: is_valid_pair
+        }
+    }
+}
+
+impl<T> Constraint<T> for PairwiseConstraint<T> {
+    fn is_satisfied(&self, indexed_elements: &Vec<IndexedElement<T>>) -> bool {
+        if let Some((latest_indexed_element, earlier_indexed_elements)) = indexed_elements.split_last() {
+            for earlier_indexed_element in earlier_indexed_elements.iter() {
+                if !(self.is_valid_pair)(earlier_indexed_element, latest_indexed_element) {
+                    return false;
+                }
+            }
+        }
+        return true;
+    }
+}
+
+// Purpose:
+//     To search over any shifter as a constraint satisfaction problem, returning each full assignment that satisfies every constraint
+//     As soon as a partial assignment fails a constraint, the remaining states of that shift are tried before backing off, so no full assignment is ever built upon a failing prefix
+// Example:
+//     The shifter could contain the possible time slots per meeting and the constraints could disallow meetings that share attendees from being in the same time slot
+pub struct ConstraintSatisfactionIncrementer<T> {
+    shifter: Box<dyn Shifter<T = T>>,
+    constraints: Vec<Box<dyn Constraint<T>>>,
+    is_started: bool,
+    is_completed: bool,
+    current_indexed_elements: Vec<IndexedElement<T>>,
+    shifter_length: usize
+}
+
+impl<T> ConstraintSatisfactionIncrementer<T> {
+    pub fn new(shifter: Box<dyn Shifter<T = T>>, constraints: Vec<Box<dyn Constraint<T>>>) -> Self {
+        let shifter_length = shifter.get_length();
+        ConstraintSatisfactionIncrementer {
+            shifter: shifter,
+            constraints: constraints,
+            is_started: false,
+            is_completed: shifter_length == 0,
+            current_indexed_elements: Vec::new(),
+            shifter_length: shifter_length
+        }
+    }
+    fn is_current_indexed_elements_satisfied(&self) -> bool {
+        for constraint in self.constraints.iter() {
+            if !constraint.is_satisfied(&self.current_indexed_elements) {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+impl<T> Incrementer for ConstraintSatisfactionIncrementer<T> {
+    type T = T;
+
+    fn try_increment(&mut self) -> bool {
+        if self.is_completed {
+            return false;
+        }
+        if !self.is_started {
+            self.is_started = true;
+            if !self.shifter.try_forward() {
+                panic!("Unexpectedly failed to move forward when not at the end.");
+            }
+        }
+        else {
+            // try the next state of the last shift
+            self.current_indexed_elements.pop();
+        }
+        loop {
+            if self.shifter.try_increment() {
+                self.current_indexed_elements.push(self.shifter.get_indexed_element());
+                if !self.is_current_indexed_elements_satisfied() {
+                    // prune every assignment built upon this prefix
+                    self.current_indexed_elements.pop();
+                }
+                else if self.current_indexed_elements.len() == self.shifter_length {
+                    return true;
+                }
+                else if !self.shifter.try_forward() {
+                    panic!("Unexpectedly failed to move forward when not at the end.");
+                }
+            }
+            else {
+                if self.current_indexed_elements.len() == 0 {
+                    self.is_completed = true;
+                    return false;
+                }
+                self.current_indexed_elements.pop();
+                if !self.shifter.try_backward() {
+                    panic!("Unexpectedly failed to move backward when not at the beginning.");
+                }
+            }
+        }
+    }
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
+        return self.current_indexed_elements
+            .iter()
+            .map(|indexed_element| { IndexedElement::new(indexed_element.element.clone(), indexed_element.index) })
+            .collect();
+    }
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        visitor(&self.current_indexed_elements);
+    }
+    fn reset(&mut self) {
+        self.shifter.reset();
+        self.is_started = false;
+        self.is_completed = self.shifter_length == 0;
+        self.current_indexed_elements.clear();
+    }
+    fn randomize(&mut self) {
+        self.shifter.randomize();
+    }
+}
+
+#[cfg(test)]
+mod constraint_satisfaction_incrementer_tests {
+    use std::{rc::Rc, cell::Cell, collections::BTreeSet};
+
+    use crate::shifter::index_shifter::IndexShifter;
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    #[rstest]
+    fn no_shifts() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = Vec::new();
+        let mut incrementer = ConstraintSatisfactionIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), Vec::new());
+        for _ in 0..10 {
+            assert!(!incrementer.try_increment());
+        }
+    }
+
+    #[rstest]
+    fn no_constraints_returns_every_assignment() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![
+            vec![Rc::new(0), Rc::new(1)],
+            vec![Rc::new(0), Rc::new(1), Rc::new(2)]
+        ];
+        let mut incrementer = ConstraintSatisfactionIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), Vec::new());
+        for _ in 0..10 {
+            let mut assignments: Vec<Vec<u8>> = Vec::new();
+            while incrementer.try_increment() {
+                assignments.push(incrementer.get().iter().map(|indexed_element| *indexed_element.element).collect());
+            }
+            assert_eq!(vec![
+                vec![0, 0], vec![0, 1], vec![0, 2],
+                vec![1, 0], vec![1, 1], vec![1, 2]
+            ], assignments);
+            incrementer.reset();
+        }
+    }
+
+    #[rstest]
+    #[case(4, 2)]
+    #[case(5, 10)]
+    #[case(6, 4)]
+    #[case(8, 92)]
+    fn n_queens(#[case] queens_total: u8, #[case] expected_solutions_total: usize) {
+        init();
+
+        // each shift index is a column and each state is the row of the queen in that column
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = (0..queens_total)
+            .map(|_| (0..queens_total).map(|row| Rc::new(row)).collect())
+            .collect();
+        let constraints: Vec<Box<dyn Constraint<u8>>> = vec![
+            Box::new(PairwiseConstraint::new(Box::new(|earlier: &IndexedElement<u8>, latest: &IndexedElement<u8>| -> bool {
+                let column_difference = latest.index.abs_diff(earlier.index);
+                let row_difference = (*latest.element as usize).abs_diff(*earlier.element as usize);
+                return row_difference != 0 && row_difference != column_difference;
+            })))
+        ];
+        let incrementer = ConstraintSatisfactionIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), constraints);
+        let solutions: BTreeSet<Vec<u8>> = incrementer.into_incrementer_iter()
+            .map(|indexed_elements| indexed_elements.iter().map(|indexed_element| *indexed_element.element).collect())
+            .collect();
+        assert_eq!(expected_solutions_total, solutions.len());
+    }
+
+    struct CountingConstraint {
+        checks_total: Rc<Cell<usize>>
+    }
+
+    impl Constraint<u8> for CountingConstraint {
+        fn is_satisfied(&self, indexed_elements: &Vec<IndexedElement<u8>>) -> bool {
+            self.checks_total.set(self.checks_total.get() + 1);
+            // the first shift may never be zero
+            return *indexed_elements[0].element != 0;
+        }
+    }
+
+    #[rstest]
+    fn failing_prefix_is_pruned() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = (0..6)
+            .map(|_| vec![Rc::new(0), Rc::new(1)])
+            .collect();
+        let checks_total = Rc::new(Cell::new(0));
+        let constraints: Vec<Box<dyn Constraint<u8>>> = vec![
+            Box::new(CountingConstraint {
+                checks_total: checks_total.clone()
+            })
+        ];
+        let mut incrementer = ConstraintSatisfactionIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), constraints);
+        let mut assignments_total = 0;
+        while incrementer.try_increment() {
+            assert_eq!(1, *incrementer.get()[0].element);
+            assignments_total += 1;
+        }
+        assert_eq!(32, assignments_total);
+        // both first states are checked, but only prefixes under the passing first state are checked further, rather than the 126 checks without pruning
+        assert_eq!(2 + 2 + 4 + 8 + 16 + 32, checks_total.get());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::IndexedElement;
+use super::{Incrementer};
+
+// Purpose:
+//     To increment only to the increments of the incrementer that satisfy the predicate, skipping past all others
+pub struct FilterIncrementer<T> {
+    incrementer: Box<dyn Incrementer<T = T>>,
+    predicate: Box<dyn Fn(&Vec<IndexedElement<T>>) -> bool>
+}
+
+impl<T> FilterIncrementer<T> {
+    pub fn new(incrementer: Box<dyn Incrementer<T = T>>, predicate: Box<dyn Fn(&Vec<IndexedElement<T>>) -> bool>) -> Self {
+        FilterIncrementer {
+            incrementer: incrementer,
+            predicate: predicate
+        }
+    }
+}
+
+impl<T> Incrementer for FilterIncrementer<T> {
+    type T = T;
+
+    fn try_increment(&mut self) -> bool {
+        while self.incrementer.try_increment() {
+            if (self.predicate)(&self.incrementer.get()) {
+                return true;
+            }
+        }
+        return false;
+    }
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
+        return self.incrementer.get();
+    }
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        self.incrementer.visit(visitor);
+    }
+    fn get_element_index_and_state_index_pairs(&self) -> Option<&[(usize, usize)]> {
+        return self.incrementer.get_element_index_and_state_index_pairs();
+    }
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return self.incrementer.get_state_table();
+    }
+    fn get_state(&self, state_index: usize) -> Option<&Self::T> {
+        return self.incrementer.get_state(state_index);
+    }
+    fn reset(&mut self) {
+        self.incrementer.reset();
+    }
+    fn randomize(&mut self) {
+        self.incrementer.randomize();
+    }
+}
+
+#[cfg(test)]
+mod filter_incrementer_tests {
+    use crate::incrementer::binary_value_incrementer::BinaryValueIncrementer;
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    fn only_increments_with_exact_number_of_true_values(#[case] true_values_total: usize) {
+        init();
+
+        let mut filter_incrementer = FilterIncrementer::new(
+            Box::new(BinaryValueIncrementer::new(3)),
+            Box::new(move |indexed_elements: &Vec<IndexedElement<bool>>| {
+                indexed_elements.iter().filter(|indexed_element| *indexed_element.element).count() == true_values_total
+            })
+        );
+        let expected_total = match true_values_total {
+            0 | 3 => 1,
+            _ => 3
+        };
+        for _ in 0..2 {
+            let mut increments_total = 0;
+            for indexed_elements in filter_incrementer.iter() {
+                assert_eq!(true_values_total, indexed_elements.iter().filter(|indexed_element| *indexed_element.element).count());
+                increments_total += 1;
+            }
+            assert_eq!(expected_total, increments_total);
+            filter_incrementer.reset();
+        }
+    }
+}
@@ -1,24 +1,18 @@
 use std::rc::Rc;
-use bitvec::vec::BitVec;
 use crate::IndexedElement;
-use super::Incrementer;
+use super::{Incrementer, binary_density_incrementer::DensityBits};
 pub struct FixedBinaryDensityIncrementer {
     length: usize,
     density: usize,
-    current_state: BitVec,
+    current_state: DensityBits,
     current_ones_total: usize,
     is_started: bool
 }
 
 impl FixedBinaryDensityIncrementer {
     pub fn new(density: usize, remaining_length: usize) -> Self {
-        let current_state = {
-            // start with the density on the left and the remaining are false
-            let mut left = BitVec::repeat(true, density);
-            let mut right: BitVec = BitVec::repeat(false, remaining_length);
-            left.append(&mut right);
-            left
-        };
+        // start with the density on the left and the remaining are false
+        let current_state = DensityBits::new_with_leading_ones(density, density + remaining_length);
         FixedBinaryDensityIncrementer {
             length: density + remaining_length,
             density,
@@ -27,6 +21,10 @@ impl FixedBinaryDensityIncrementer {
             is_started: false
         }
     }
+    /// Returns the current bits as a word where the bit at index 0 is the least significant bit, or None if the length is beyond 128 bits.
+    pub fn get_bits(&self) -> Option<u128> {
+        return self.current_state.get_bits();
+    }
 }
 
 impl Incrementer for FixedBinaryDensityIncrementer {
@@ -45,38 +43,7 @@ impl Incrementer for FixedBinaryDensityIncrementer {
             self.current_ones_total += 1;
             return false;
         }
-        let mut is_zero_found = false;
-        let mut rightmost_zero_before_one_index_option: Option<usize> = None;
-        for index in (0..self.length).rev() {
-            if self.current_state[index] {
-                if is_zero_found && rightmost_zero_before_one_index_option.is_none() {
-                    rightmost_zero_before_one_index_option = Some(index + 1);
-                    break;
-                }
-            }
-            else {
-                is_zero_found = true;
-            }
-        }
-        if let Some(index) = rightmost_zero_before_one_index_option {
-            // move the rightmost one to the right since we've guaranteed that it was empty
-            self.current_state.set(index - 1, false);
-            self.current_state.set(index, true);
-
-            // move all of the ones built up on the right side of the structure back to the point where we just moved a bit over
-            let mut zero_index = index + 1;
-            let mut one_index = index + 1;
-            while one_index < self.length && !self.current_state[one_index] {
-                one_index += 1;
-            }
-            while one_index < self.length && !self.current_state[zero_index] {
-                self.current_state.set(zero_index, true);
-                self.current_state.set(one_index, false);
-                zero_index += 1;
-                one_index += 1;
-            }
-        }
-        else {
+        if !self.current_state.try_shift() {
             // we would need to change the density, so the current value is invalid
             self.current_ones_total += 1;
             return false;
@@ -86,19 +53,14 @@ impl Incrementer for FixedBinaryDensityIncrementer {
     fn get(&self) -> Vec<IndexedElement<Self::T>> {
         let mut indexed_elements = Vec::new();
         for index in 0..self.length {
-            let indexed_element: IndexedElement<Self::T> = IndexedElement::new(Rc::new(self.current_state[index]), index);
+            let indexed_element: IndexedElement<Self::T> = IndexedElement::new(Rc::new(self.current_state.get(index)), index);
             indexed_elements.push(indexed_element);
         }
         return indexed_elements;
     }
     fn reset(&mut self) {
-        self.current_state = {
-            // start with the density on the left and the remaining are false
-            let mut left = BitVec::repeat(true, self.density);
-            let mut right: BitVec = BitVec::repeat(false, self.length - self.density);
-            left.append(&mut right);
-            left
-        };
+        // start with the density on the left and the remaining are false
+        self.current_state.set_leading_ones(self.density);
         self.current_ones_total = self.density;
         self.is_started = false;
     }
@@ -107,21 +69,12 @@ impl Incrementer for FixedBinaryDensityIncrementer {
     }
 }
 
-impl Iterator for FixedBinaryDensityIncrementer {
-    type Item = Vec<IndexedElement<bool>>;
-
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.try_increment() {
-            return Some(self.get());
-        }
-        return None;
-    }
-}
-
 #[cfg(test)]
 mod fixed_binary_density_incrementer {
     use super::*;
+    use bitvec::vec::BitVec;
     use rstest::rstest;
+    use crate::combinatorics::get_binomial;
 
     fn init() {
         std::env::set_var("RUST_LOG", "trace");
@@ -360,4 +313,38 @@ mod fixed_binary_density_incrementer {
             incrementer.reset();
         }
     }
+    #[rstest]
+    #[case(0, 5)]
+    #[case(1, 7)]
+    #[case(3, 4)]
+    #[case(6, 6)]
+    #[case(2, 126)]
+    #[case(126, 2)]
+    #[case(64, 0)]
+    fn word_matches_bit_vector(#[case] density: usize, #[case] remaining_length: usize) {
+        init();
+
+        let mut word_incrementer = FixedBinaryDensityIncrementer::new(density, remaining_length);
+        let mut bit_vector_incrementer = FixedBinaryDensityIncrementer::new(density, remaining_length);
+        bit_vector_incrementer.current_state = {
+            let mut bits: BitVec = BitVec::repeat(true, density);
+            let mut remaining_bits: BitVec = BitVec::repeat(false, remaining_length);
+            bits.append(&mut remaining_bits);
+            DensityBits::BitVec(bits)
+        };
+        let expected_states_total: u64 = get_binomial((density + remaining_length) as u64, density as u64).unwrap();
+        for _ in 0..2 {
+            let mut states_total: u64 = 0;
+            while word_incrementer.try_increment() {
+                assert!(bit_vector_incrementer.try_increment());
+                assert_eq!(bit_vector_incrementer.get(), word_incrementer.get());
+                assert_eq!(density, word_incrementer.get_bits().unwrap().count_ones() as usize);
+                states_total += 1;
+            }
+            assert!(!bit_vector_incrementer.try_increment());
+            assert_eq!(expected_states_total, states_total);
+            word_incrementer.reset();
+            bit_vector_incrementer.reset();
+        }
+    }
 }
\ No newline at end of file
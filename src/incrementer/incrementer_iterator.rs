@@ -0,0 +1,152 @@
+use crate::IndexedElement;
+use super::Incrementer;
+
+// Purpose:
+//     To iterate over any incrementer, borrowed or owned, so that it can be used alongside the standard iterator adapters
+//     Each item is the result of get() after a successful try_increment(), so the iterator ends once the incrementer is unable to increment
+pub struct IncrementerIterator<'a, TIncrementer: Incrementer + ?Sized> {
+    incrementer: &'a mut TIncrementer
+}
+
+impl<'a, TIncrementer: Incrementer + ?Sized> IncrementerIterator<'a, TIncrementer> {
+    pub fn new(incrementer: &'a mut TIncrementer) -> Self {
+        IncrementerIterator {
+            incrementer: incrementer
+        }
+    }
+}
+
+impl<'a, TIncrementer: Incrementer + ?Sized> Iterator for IncrementerIterator<'a, TIncrementer> {
+    type Item = Vec<IndexedElement<TIncrementer::T>>;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.incrementer.try_increment() {
+            return Some(self.incrementer.get());
+        }
+        return None;
+    }
+}
+
+pub struct IncrementerIntoIterator<TIncrementer: Incrementer> {
+    incrementer: TIncrementer
+}
+
+impl<TIncrementer: Incrementer> IncrementerIntoIterator<TIncrementer> {
+    pub fn new(incrementer: TIncrementer) -> Self {
+        IncrementerIntoIterator {
+            incrementer: incrementer
+        }
+    }
+    pub fn into_inner(self) -> TIncrementer {
+        return self.incrementer;
+    }
+}
+
+impl<TIncrementer: Incrementer> Iterator for IncrementerIntoIterator<TIncrementer> {
+    type Item = Vec<IndexedElement<TIncrementer::T>>;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.incrementer.try_increment() {
+            return Some(self.incrementer.get());
+        }
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod incrementer_iterator_tests {
+    use std::rc::Rc;
+
+    use itertools::Itertools;
+
+    use crate::{incrementer::{binary_value_incrementer::BinaryValueIncrementer, shifter_incrementer::ShifterIncrementer}, shifter::index_shifter::IndexShifter};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    fn get_states(indexed_elements: Vec<IndexedElement<u8>>) -> Vec<u8> {
+        return indexed_elements.iter().map(|indexed_element| *indexed_element.element).collect();
+    }
+
+    #[rstest]
+    fn borrowed_iterator_matches_try_increment() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![
+            vec![Rc::new(0), Rc::new(1)],
+            vec![Rc::new(2), Rc::new(3), Rc::new(4)]
+        ];
+        let mut incrementer = ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![0, 1]);
+        let mut expected_states_per_increment: Vec<Vec<u8>> = Vec::new();
+        while incrementer.try_increment() {
+            expected_states_per_increment.push(get_states(incrementer.get()));
+        }
+        assert_eq!(6, expected_states_per_increment.len());
+        for _ in 0..2 {
+            incrementer.reset();
+            let states_per_increment: Vec<Vec<u8>> = incrementer.iter()
+                .map(get_states)
+                .collect();
+            assert_eq!(expected_states_per_increment, states_per_increment);
+        }
+    }
+
+    #[rstest]
+    fn boxed_incrementer_composes_with_itertools() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![
+            vec![Rc::new(0), Rc::new(1)],
+            vec![Rc::new(2), Rc::new(3)]
+        ];
+        let mut incrementer: Box<dyn Incrementer<T = u8>> = Box::new(ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![0, 1]));
+        let states_per_chunk: Vec<Vec<Vec<u8>>> = IncrementerIterator::new(incrementer.as_mut())
+            .map(get_states)
+            .chunks(3)
+            .into_iter()
+            .map(|chunk| chunk.collect_vec())
+            .collect();
+        assert_eq!(vec![
+            vec![vec![0, 2], vec![0, 3], vec![1, 2]],
+            vec![vec![1, 3]]
+        ], states_per_chunk);
+    }
+
+    #[rstest]
+    fn owned_iterator_returns_incrementer() {
+        init();
+
+        let incrementer = BinaryValueIncrementer::new(2);
+        let mut iterator = incrementer.into_incrementer_iter();
+        assert_eq!(4, iterator.by_ref().count());
+        let mut incrementer = iterator.into_inner();
+        assert!(!incrementer.try_increment());
+        incrementer.reset();
+        assert_eq!(4, incrementer.into_incrementer_iter().count());
+    }
+
+    #[rstest]
+    fn boxed_and_borrowed_incrementers_are_iterable() {
+        init();
+
+        let mut incrementer: Box<dyn Incrementer<T = bool>> = Box::new(BinaryValueIncrementer::new(2));
+        let mut increments_total = 0;
+        for indexed_elements in incrementer.as_mut() {
+            assert_eq!(2, indexed_elements.len());
+            increments_total += 1;
+        }
+        assert_eq!(4, increments_total);
+        incrementer.reset();
+        increments_total = 0;
+        for indexed_elements in incrementer.into_incrementer_iter() {
+            assert_eq!(2, indexed_elements.len());
+            increments_total += 1;
+        }
+        assert_eq!(4, increments_total);
+    }
+}
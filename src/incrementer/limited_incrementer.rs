@@ -1,5 +1,5 @@
 use crate::IndexedElement;
-use super::Incrementer;
+use super::{Incrementer};
 
 pub struct LimitedIncrementer<T> {
     incrementer: Box<dyn Incrementer<T = T>>,
@@ -42,6 +42,18 @@ impl<T> Incrementer for LimitedIncrementer<T> {
     fn get(&self) -> Vec<IndexedElement<Self::T>> {
         return self.incrementer.get();
     }
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        self.incrementer.visit(visitor);
+    }
+    fn get_element_index_and_state_index_pairs(&self) -> Option<&[(usize, usize)]> {
+        return self.incrementer.get_element_index_and_state_index_pairs();
+    }
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return self.incrementer.get_state_table();
+    }
+    fn get_state(&self, state_index: usize) -> Option<&Self::T> {
+        return self.incrementer.get_state(state_index);
+    }
     fn reset(&mut self) {
         self.incrementer.reset();
         self.is_completed = self.length == 0;
@@ -52,17 +64,6 @@ impl<T> Incrementer for LimitedIncrementer<T> {
     }
 }
 
-impl<T> Iterator for LimitedIncrementer<T> {
-    type Item = Vec<IndexedElement<T>>;
-
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.try_increment() {
-            return Some(self.get());
-        }
-        return None;
-    }
-}
-
 #[cfg(test)]
 mod limited_incrementer_tests {
     use rstest::rstest;
@@ -0,0 +1,94 @@
+use crate::IndexedElement;
+use super::{Incrementer};
+
+// Purpose:
+//     To change each increment of the incrementer into a collection of indexed elements of another type
+//     The map is applied each time that get() is called, so it should not depend on anything other than the increment
+pub struct MapIncrementer<TFrom, TTo> {
+    incrementer: Box<dyn Incrementer<T = TFrom>>,
+    map: Box<dyn Fn(Vec<IndexedElement<TFrom>>) -> Vec<IndexedElement<TTo>>>
+}
+
+impl<TFrom, TTo> MapIncrementer<TFrom, TTo> {
+    pub fn new(incrementer: Box<dyn Incrementer<T = TFrom>>, map: Box<dyn Fn(Vec<IndexedElement<TFrom>>) -> Vec<IndexedElement<TTo>>>) -> Self {
+        MapIncrementer {
+            incrementer: incrementer,
+            map: map
+        }
+    }
+}
+
+impl<TFrom, TTo> Incrementer for MapIncrementer<TFrom, TTo> {
+    type T = TTo;
+
+    fn try_increment(&mut self) -> bool {
+        return self.incrementer.try_increment();
+    }
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
+        return (self.map)(self.incrementer.get());
+    }
+    fn reset(&mut self) {
+        self.incrementer.reset();
+    }
+    fn randomize(&mut self) {
+        self.incrementer.randomize();
+    }
+}
+
+#[cfg(test)]
+mod map_incrementer_tests {
+    use std::rc::Rc;
+
+    use crate::incrementer::{binary_value_incrementer::BinaryValueIncrementer, filter_incrementer::FilterIncrementer};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    fn get_map_incrementer() -> MapIncrementer<bool, u8> {
+        return MapIncrementer::new(
+            Box::new(BinaryValueIncrementer::new(2)),
+            Box::new(|indexed_elements: Vec<IndexedElement<bool>>| {
+                indexed_elements.into_iter()
+                    .map(|indexed_element| IndexedElement::new(Rc::new(if *indexed_element.element { 1 } else { 0 }), indexed_element.index))
+                    .collect()
+            })
+        );
+    }
+
+    #[rstest]
+    fn changes_type_of_each_increment() {
+        init();
+
+        let mut map_incrementer = get_map_incrementer();
+        let mut expected_increments: Vec<Vec<(usize, u8)>> = BinaryValueIncrementer::new(2).into_incrementer_iter()
+            .map(|indexed_elements| indexed_elements.iter().map(|indexed_element| (indexed_element.index, if *indexed_element.element { 1 } else { 0 })).collect())
+            .collect();
+        expected_increments.sort();
+        for _ in 0..2 {
+            let mut increments: Vec<Vec<(usize, u8)>> = map_incrementer.iter()
+                .map(|indexed_elements| indexed_elements.iter().map(|indexed_element| (indexed_element.index, *indexed_element.element)).collect())
+                .collect();
+            increments.sort();
+            assert_eq!(expected_increments, increments);
+            map_incrementer.reset();
+        }
+    }
+
+    #[rstest]
+    fn composes_with_filter() {
+        init();
+
+        let mut filter_incrementer = FilterIncrementer::new(
+            Box::new(get_map_incrementer()),
+            Box::new(|indexed_elements: &Vec<IndexedElement<u8>>| {
+                indexed_elements.iter().map(|indexed_element| *indexed_element.element).sum::<u8>() == 1
+            })
+        );
+        assert_eq!(2, filter_incrementer.iter().count());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::IndexedElement;
+use self::incrementer_iterator::{IncrementerIterator, IncrementerIntoIterator};
 
 pub mod shifting_cell_group_dependency_incrementer;
 pub mod round_robin_incrementer;
@@ -7,6 +8,16 @@ pub mod binary_density_incrementer;
 pub mod binary_value_incrementer;
 pub mod limited_incrementer;
 pub mod fixed_binary_density_incrementer;
+pub mod constraint_satisfaction_incrementer;
+pub mod nogood_cache;
+pub mod incrementer_iterator;
+pub mod chain_incrementer;
+pub mod zip_incrementer;
+pub mod cartesian_product_incrementer;
+pub mod filter_incrementer;
+pub mod map_incrementer;
+pub mod skip_incrementer;
+pub mod optional_segment_permutation_incrementer;
 
 pub trait Incrementer {
     type T;
@@ -15,15 +26,71 @@ pub trait Incrementer {
     fn get(&self) -> Vec<IndexedElement<Self::T>>;
     fn reset(&mut self);
     fn randomize(&mut self);
+    // provides the same indexed elements as get() to the visitor, borrowing them where the incrementer already keeps them so that nothing is allocated per increment
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        visitor(&self.get());
+    }
+    // returns the element index, the same as the IndexedElement.index from get(), and the state index of each current element, to be used against get_state(), if the incrementer keeps its current elements as state indexes
+    fn get_element_index_and_state_index_pairs(&self) -> Option<&[(usize, usize)]> {
+        return None;
+    }
+    // returns the distinct states as a borrowed table of plain states, indexed by the state indexes from get_element_index_and_state_index_pairs(), if the incrementer keeps its states without an Rc
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return None;
+    }
+    // returns a reference to the state at the state index from get_element_index_and_state_index_pairs(), so that the current elements can be read without allocating, or None if the incrementer does not keep state indexes
+    fn get_state(&self, state_index: usize) -> Option<&Self::T> {
+        return self.get_state_table()
+            .map(|state_table| &state_table[state_index]);
+    }
+
+    // returns an iterator over each remaining increment while borrowing the incrementer, so that the incrementer can be reset and iterated over again afterwards
+    // requires Self: Sized, so it cannot be called on a dyn Incrementer itself, only on a sized incrementer such as a Box<dyn Incrementer>, while a &mut dyn Incrementer can be iterated over in a for loop instead
+    fn iter(&mut self) -> IncrementerIterator<'_, Self> where Self: Sized {
+        return IncrementerIterator::new(self);
+    }
+    // returns an iterator over each remaining increment that takes ownership of the incrementer, which can be taken back out via into_inner()
+    // requires Self: Sized for the same reason as iter()
+    fn into_incrementer_iter(self) -> IncrementerIntoIterator<Self> where Self: Sized {
+        return IncrementerIntoIterator::new(self);
+    }
 }
 
-impl<T> Iterator for dyn Incrementer<T = T> {
+// Notes: boxed incrementers forward to the incrementer within the box, so a Box<dyn Incrementer> can be iterated over via iter() or into_incrementer_iter()
+impl<TIncrementer: Incrementer + ?Sized> Incrementer for Box<TIncrementer> {
+    type T = TIncrementer::T;
+
+    fn try_increment(&mut self) -> bool {
+        return self.as_mut().try_increment();
+    }
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
+        return self.as_ref().get();
+    }
+    fn reset(&mut self) {
+        self.as_mut().reset();
+    }
+    fn randomize(&mut self) {
+        self.as_mut().randomize();
+    }
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        self.as_ref().visit(visitor);
+    }
+    fn get_element_index_and_state_index_pairs(&self) -> Option<&[(usize, usize)]> {
+        return self.as_ref().get_element_index_and_state_index_pairs();
+    }
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return self.as_ref().get_state_table();
+    }
+    fn get_state(&self, state_index: usize) -> Option<&Self::T> {
+        return self.as_ref().get_state(state_index);
+    }
+}
+
+impl<'a, 'b, T> IntoIterator for &'a mut (dyn Incrementer<T = T> + 'b) {
     type Item = Vec<IndexedElement<T>>;
+    type IntoIter = IncrementerIterator<'a, dyn Incrementer<T = T> + 'b>;
 
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.try_increment() {
-            return Some(self.get());
-        }
-        return None;
+    fn into_iter(self) -> Self::IntoIter {
+        return IncrementerIterator::new(self);
     }
 }
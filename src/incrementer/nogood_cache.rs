@@ -0,0 +1,203 @@
+use std::{rc::Rc, collections::{HashMap, VecDeque}, hash::Hash};
+
+// Purpose:
+//     To remember which sets of cell group locations were found to be invalid together so that later searches over the same cell groups can skip them without checking again
+//     Each nogood is a sorted collection of (cell group index, location) tuples, where the pairs are found by comparing cell groups and the larger nogoods are found while searching
+//     Once the capacity is reached, the oldest nogood is forgotten to make room for the newest nogood
+// Notes:
+//     The larger nogoods depend on the possible locations of each cell group, so a cache should only be shared between searches where each cell group has the same possible locations
+//     The location type defaults to the locations of a pixel board, but any location type of the cell groups can be used, such as the locations of a voxel board
+pub struct NogoodCache<TLocation = (u8, u8)> {
+    capacity: usize,
+    nogood_index_per_nogood: HashMap<Rc<Vec<(usize, TLocation)>>, usize>,
+    nogood_index_per_pair: HashMap<((usize, TLocation), (usize, TLocation)), usize>,
+    nogood_per_nogood_index: HashMap<usize, Rc<Vec<(usize, TLocation)>>>,
+    nogood_indexes_per_cell_group_index_and_location_tuple: HashMap<(usize, TLocation), Vec<usize>>,
+    nogood_indexes: VecDeque<usize>,
+    next_nogood_index: usize,
+    non_pair_nogoods_total: usize
+}
+
+impl<TLocation: Copy + Ord + Hash> NogoodCache<TLocation> {
+    pub fn new(capacity: usize) -> Self {
+        NogoodCache {
+            capacity: capacity,
+            nogood_index_per_nogood: HashMap::new(),
+            nogood_index_per_pair: HashMap::new(),
+            nogood_per_nogood_index: HashMap::new(),
+            nogood_indexes_per_cell_group_index_and_location_tuple: HashMap::new(),
+            nogood_indexes: VecDeque::new(),
+            next_nogood_index: 0,
+            non_pair_nogoods_total: 0
+        }
+    }
+    pub fn get_capacity(&self) -> usize {
+        return self.capacity;
+    }
+    pub fn len(&self) -> usize {
+        return self.nogood_indexes.len();
+    }
+    pub fn is_empty(&self) -> bool {
+        return self.nogood_indexes.is_empty();
+    }
+    pub fn clear(&mut self) {
+        self.nogood_index_per_nogood.clear();
+        self.nogood_index_per_pair.clear();
+        self.nogood_per_nogood_index.clear();
+        self.nogood_indexes_per_cell_group_index_and_location_tuple.clear();
+        self.nogood_indexes.clear();
+        self.non_pair_nogoods_total = 0;
+    }
+    // determines if any nogood other than a pair of cell group locations is stored, since the pairs can be checked directly
+    pub fn has_non_pair_nogoods(&self) -> bool {
+        return self.non_pair_nogoods_total != 0;
+    }
+    // stores the cell group locations as invalid together, forgetting the oldest nogood if the capacity has been reached
+    pub fn add(&mut self, mut cell_group_index_and_location_tuples: Vec<(usize, TLocation)>) {
+        if self.capacity == 0 || cell_group_index_and_location_tuples.is_empty() {
+            return;
+        }
+        cell_group_index_and_location_tuples.sort();
+        cell_group_index_and_location_tuples.dedup();
+        let nogood = Rc::new(cell_group_index_and_location_tuples);
+        if self.nogood_index_per_nogood.contains_key(&nogood) {
+            return;
+        }
+        while self.nogood_indexes.len() >= self.capacity {
+            self.remove_oldest();
+        }
+        let nogood_index = self.next_nogood_index;
+        self.next_nogood_index += 1;
+        for cell_group_index_and_location_tuple in nogood.iter() {
+            self.nogood_indexes_per_cell_group_index_and_location_tuple
+                .entry(*cell_group_index_and_location_tuple)
+                .or_insert_with(Vec::new)
+                .push(nogood_index);
+        }
+        if nogood.len() == 2 {
+            self.nogood_index_per_pair.insert((nogood[0], nogood[1]), nogood_index);
+        }
+        else {
+            self.non_pair_nogoods_total += 1;
+        }
+        self.nogood_index_per_nogood.insert(nogood.clone(), nogood_index);
+        self.nogood_per_nogood_index.insert(nogood_index, nogood);
+        self.nogood_indexes.push_back(nogood_index);
+    }
+    fn remove_oldest(&mut self) {
+        if let Some(nogood_index) = self.nogood_indexes.pop_front() {
+            let nogood = self.nogood_per_nogood_index.remove(&nogood_index).unwrap();
+            self.nogood_index_per_nogood.remove(&nogood);
+            if nogood.len() == 2 {
+                self.nogood_index_per_pair.remove(&(nogood[0], nogood[1]));
+            }
+            else {
+                self.non_pair_nogoods_total -= 1;
+            }
+            for cell_group_index_and_location_tuple in nogood.iter() {
+                let nogood_indexes = self.nogood_indexes_per_cell_group_index_and_location_tuple.get_mut(cell_group_index_and_location_tuple).unwrap();
+                nogood_indexes.retain(|other_nogood_index| *other_nogood_index != nogood_index);
+                if nogood_indexes.is_empty() {
+                    self.nogood_indexes_per_cell_group_index_and_location_tuple.remove(cell_group_index_and_location_tuple);
+                }
+            }
+        }
+    }
+    // determines if exactly these cell group locations were stored as invalid together
+    pub fn contains(&self, cell_group_index_and_location_tuples: &Vec<(usize, TLocation)>) -> bool {
+        let mut sorted_cell_group_index_and_location_tuples = cell_group_index_and_location_tuples.clone();
+        sorted_cell_group_index_and_location_tuples.sort();
+        sorted_cell_group_index_and_location_tuples.dedup();
+        return self.nogood_index_per_nogood.contains_key(&sorted_cell_group_index_and_location_tuples);
+    }
+    // determines if the pair of cell group locations was stored as invalid together, in either order
+    pub fn contains_pair(&self, cell_group_index_and_location_tuple: &(usize, TLocation), other_cell_group_index_and_location_tuple: &(usize, TLocation)) -> bool {
+        if cell_group_index_and_location_tuple < other_cell_group_index_and_location_tuple {
+            return self.nogood_index_per_pair.contains_key(&(*cell_group_index_and_location_tuple, *other_cell_group_index_and_location_tuple));
+        }
+        return self.nogood_index_per_pair.contains_key(&(*other_cell_group_index_and_location_tuple, *cell_group_index_and_location_tuple));
+    }
+    // returns a stored nogood that contains the latest cell group location such that every other cell group location of the nogood is within the earlier cell group locations
+    pub fn get_nogood_within(&self, latest_cell_group_index_and_location_tuple: &(usize, TLocation), earlier_cell_group_index_and_location_tuples: &Vec<(usize, TLocation)>) -> Option<Rc<Vec<(usize, TLocation)>>> {
+        if let Some(nogood_indexes) = self.nogood_indexes_per_cell_group_index_and_location_tuple.get(latest_cell_group_index_and_location_tuple) {
+            for nogood_index in nogood_indexes.iter() {
+                let nogood = &self.nogood_per_nogood_index[nogood_index];
+                let is_nogood_within = nogood.iter()
+                    .all(|cell_group_index_and_location_tuple| cell_group_index_and_location_tuple == latest_cell_group_index_and_location_tuple || earlier_cell_group_index_and_location_tuples.contains(cell_group_index_and_location_tuple));
+                if is_nogood_within {
+                    return Some(nogood.clone());
+                }
+            }
+        }
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod nogood_cache_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    #[rstest]
+    fn zero_capacity_stores_nothing() {
+        init();
+
+        let mut nogood_cache = NogoodCache::new(0);
+        nogood_cache.add(vec![(0, (1, 1)), (1, (2, 2))]);
+        assert!(nogood_cache.is_empty());
+        assert!(!nogood_cache.contains(&vec![(0, (1, 1)), (1, (2, 2))]));
+    }
+
+    #[rstest]
+    fn contains_regardless_of_order() {
+        init();
+
+        let mut nogood_cache = NogoodCache::new(10);
+        nogood_cache.add(vec![(1, (2, 2)), (0, (1, 1))]);
+        nogood_cache.add(vec![(0, (1, 1)), (1, (2, 2))]);
+        assert_eq!(1, nogood_cache.len());
+        assert!(nogood_cache.contains(&vec![(0, (1, 1)), (1, (2, 2))]));
+        assert!(nogood_cache.contains(&vec![(1, (2, 2)), (0, (1, 1))]));
+        assert!(!nogood_cache.contains(&vec![(0, (1, 1)), (1, (2, 3))]));
+        assert!(nogood_cache.contains_pair(&(0, (1, 1)), &(1, (2, 2))));
+        assert!(nogood_cache.contains_pair(&(1, (2, 2)), &(0, (1, 1))));
+        assert!(!nogood_cache.has_non_pair_nogoods());
+    }
+
+    #[rstest]
+    fn oldest_nogood_is_forgotten() {
+        init();
+
+        let mut nogood_cache = NogoodCache::new(2);
+        nogood_cache.add(vec![(0, (0, 0)), (1, (0, 1))]);
+        nogood_cache.add(vec![(0, (0, 0)), (1, (0, 2))]);
+        nogood_cache.add(vec![(0, (0, 0)), (1, (0, 3))]);
+        assert_eq!(2, nogood_cache.len());
+        assert!(!nogood_cache.contains(&vec![(0, (0, 0)), (1, (0, 1))]));
+        assert!(!nogood_cache.contains_pair(&(0, (0, 0)), &(1, (0, 1))));
+        assert!(nogood_cache.contains(&vec![(0, (0, 0)), (1, (0, 2))]));
+        assert!(nogood_cache.contains(&vec![(0, (0, 0)), (1, (0, 3))]));
+        assert!(nogood_cache.get_nogood_within(&(1, (0, 1)), &vec![(0, (0, 0))]).is_none());
+        nogood_cache.clear();
+        assert!(nogood_cache.is_empty());
+        assert!(nogood_cache.get_nogood_within(&(1, (0, 2)), &vec![(0, (0, 0))]).is_none());
+    }
+
+    #[rstest]
+    fn nogood_within_requires_every_other_location() {
+        init();
+
+        let mut nogood_cache = NogoodCache::new(10);
+        nogood_cache.add(vec![(0, (0, 0)), (1, (1, 1)), (2, (2, 2))]);
+        assert!(nogood_cache.has_non_pair_nogoods());
+        assert!(nogood_cache.get_nogood_within(&(2, (2, 2)), &vec![(0, (0, 0))]).is_none());
+        assert!(nogood_cache.get_nogood_within(&(2, (2, 2)), &vec![(0, (0, 0)), (1, (1, 2))]).is_none());
+        assert_eq!(Some(Rc::new(vec![(0, (0, 0)), (1, (1, 1)), (2, (2, 2))])), nogood_cache.get_nogood_within(&(2, (2, 2)), &vec![(1, (1, 1)), (3, (3, 3)), (0, (0, 0))]));
+        assert_eq!(Some(Rc::new(vec![(0, (0, 0)), (1, (1, 1)), (2, (2, 2))])), nogood_cache.get_nogood_within(&(0, (0, 0)), &vec![(1, (1, 1)), (2, (2, 2))]));
+    }
+}
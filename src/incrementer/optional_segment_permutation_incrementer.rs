@@ -0,0 +1,360 @@
+use std::{rc::Rc, ops::RangeInclusive};
+use crate::{IndexedElement, shifter::{Shifter, segment_permutation_shifter::{Segment, Spacing, SegmentPermutationShifter}}};
+use super::{Incrementer, shifter_incrementer::ShifterIncrementer, fixed_binary_density_incrementer::FixedBinaryDensityIncrementer};
+
+// Purpose:
+//     To increment over every arrangement of each selection of segments, where the required segments are always selected and the total of selected optional segments is within the provided range
+//     The selections of each total of optional segments are found by a FixedBinaryDensityIncrementer over the optional segments, starting with the selections that have the fewest optional segments
+//     Each selection is iterated over by its own SegmentPermutationShifter, which is only constructed once the previous selection is exhausted
+//     Omitted segments are not returned, so the element index of each returned located segment is the segment index from the original collection of segments
+pub struct OptionalSegmentPermutationIncrementer {
+    placed_segments: Vec<Rc<Segment>>,
+    required_segment_indexes: Vec<usize>,
+    optional_segment_indexes: Vec<usize>,
+    optional_segments_totals: Vec<usize>,
+    origin: (u8, u8),
+    bounding_length: usize,
+    is_horizontal: bool,
+    spacing_per_segment_index: Vec<Spacing>,
+    is_swapping_permitted: bool,
+    precedence_pairs: Vec<(usize, usize)>,
+    is_randomized: bool,
+    current_optional_segments_total_index: usize,
+    current_fixed_binary_density_incrementer: Option<FixedBinaryDensityIncrementer>,
+    current_shifter_incrementer: Option<ShifterIncrementer<(u8, u8)>>
+}
+
+impl OptionalSegmentPermutationIncrementer {
+    /// Selections of segments that cannot fit within the bounding length are skipped, as are empty selections since they have no segments to arrange.
+    pub fn new(segments: Vec<Rc<Segment>>, optional_segments_totals: RangeInclusive<usize>, origin: (u8, u8), bounding_length: usize, is_horizontal: bool, padding: usize, is_swapping_permitted: bool) -> Self {
+        let spacing_per_segment_index: Vec<Spacing> = vec![Spacing::new(padding, None); segments.len()];
+        return Self::new_with_spacing(segments, optional_segments_totals, origin, bounding_length, is_horizontal, spacing_per_segment_index, is_swapping_permitted);
+    }
+    /// The spacing is per segment index of the original collection of segments, the same as the SegmentPermutationShifter, so the spacing of an omitted segment is unused.
+    pub fn new_with_spacing(segments: Vec<Rc<Segment>>, optional_segments_totals: RangeInclusive<usize>, origin: (u8, u8), bounding_length: usize, is_horizontal: bool, spacing_per_segment_index: Vec<Spacing>, is_swapping_permitted: bool) -> Self {
+        if optional_segments_totals.is_empty() {
+            panic!("Unexpected minimum optional segments total greater than the maximum optional segments total.");
+        }
+        if spacing_per_segment_index.len() != segments.len() {
+            panic!("Unexpected mismatch between the number of segments and the number of spacings.");
+        }
+        let required_segment_indexes: Vec<usize> = (0..segments.len())
+            .filter(|segment_index| !segments[*segment_index].is_optional())
+            .collect();
+        let optional_segment_indexes: Vec<usize> = (0..segments.len())
+            .filter(|segment_index| segments[*segment_index].is_optional())
+            .collect();
+        if *optional_segments_totals.start() > optional_segment_indexes.len() {
+            panic!("Unexpected minimum optional segments total greater than the number of optional segments.");
+        }
+        let optional_segments_totals: Vec<usize> = (*optional_segments_totals.start()..=(*optional_segments_totals.end()).min(optional_segment_indexes.len())).collect();
+        // the SegmentPermutationShifter places every segment it is provided, so each selected segment is provided as a required segment
+        let placed_segments: Vec<Rc<Segment>> = segments.iter()
+            .map(|segment| Rc::new(Segment::new(segment.get_length())))
+            .collect();
+        OptionalSegmentPermutationIncrementer {
+            placed_segments: placed_segments,
+            required_segment_indexes: required_segment_indexes,
+            optional_segment_indexes: optional_segment_indexes,
+            optional_segments_totals: optional_segments_totals,
+            origin: origin,
+            bounding_length: bounding_length,
+            is_horizontal: is_horizontal,
+            spacing_per_segment_index: spacing_per_segment_index,
+            is_swapping_permitted: is_swapping_permitted,
+            precedence_pairs: Vec::new(),
+            is_randomized: false,
+            current_optional_segments_total_index: 0,
+            current_fixed_binary_density_incrementer: None,
+            current_shifter_incrementer: None
+        }
+    }
+    /// Each precedence pair is kept whenever both of its segment indexes are selected and is ignored when either segment is omitted.
+    pub fn new_with_precedence(segments: Vec<Rc<Segment>>, optional_segments_totals: RangeInclusive<usize>, origin: (u8, u8), bounding_length: usize, is_horizontal: bool, spacing_per_segment_index: Vec<Spacing>, precedence_pairs: Vec<(usize, usize)>) -> Self {
+        for (preceding_segment_index, following_segment_index) in precedence_pairs.iter() {
+            if *preceding_segment_index >= segments.len() || *following_segment_index >= segments.len() {
+                panic!("Unexpected segment index outside of the segments in precedence pair.");
+            }
+        }
+        let mut optional_segment_permutation_incrementer = Self::new_with_spacing(segments, optional_segments_totals, origin, bounding_length, is_horizontal, spacing_per_segment_index, true);
+        optional_segment_permutation_incrementer.precedence_pairs = precedence_pairs;
+        return optional_segment_permutation_incrementer;
+    }
+    // moves to the next selection of optional segments, returning the selected optional segment indexes or None once every optional segments total is exhausted
+    fn try_get_next_selected_optional_segment_indexes(&mut self) -> Option<Vec<usize>> {
+        while self.current_optional_segments_total_index != self.optional_segments_totals.len() {
+            match self.current_fixed_binary_density_incrementer.as_mut() {
+                Some(fixed_binary_density_incrementer) => {
+                    if fixed_binary_density_incrementer.try_increment() {
+                        let selected_optional_segment_indexes: Vec<usize> = fixed_binary_density_incrementer.get().into_iter()
+                            .filter(|indexed_element| *indexed_element.element)
+                            .map(|indexed_element| self.optional_segment_indexes[indexed_element.index])
+                            .collect();
+                        return Some(selected_optional_segment_indexes);
+                    }
+                    self.current_fixed_binary_density_incrementer = None;
+                    self.current_optional_segments_total_index += 1;
+                },
+                None => {
+                    let optional_segments_total = self.optional_segments_totals[self.current_optional_segments_total_index];
+                    self.current_fixed_binary_density_incrementer = Some(FixedBinaryDensityIncrementer::new(optional_segments_total, self.optional_segment_indexes.len() - optional_segments_total));
+                    if self.optional_segment_indexes.is_empty() {
+                        // the incrementer over zero optional segments has no states, but selecting none of them is still the one selection
+                        return Some(Vec::new());
+                    }
+                }
+            }
+        }
+        return None;
+    }
+    // returns the smallest length that the selected segments fit within, where only a segment that no other selected segment must follow may be placed last without its spacing after it
+    fn get_minimum_bounding_length(&self, selected_segment_indexes: &Vec<usize>) -> usize {
+        let mut minimum_bounding_length = 0;
+        let mut largest_last_minimum_spacing = 0;
+        for segment_index in selected_segment_indexes.iter() {
+            let minimum_spacing = self.spacing_per_segment_index[*segment_index].get_minimum();
+            minimum_bounding_length += self.placed_segments[*segment_index].get_length() + minimum_spacing;
+            let is_possibly_last = if self.is_swapping_permitted {
+                !self.precedence_pairs.iter().any(|(preceding_segment_index, following_segment_index)| preceding_segment_index == segment_index && selected_segment_indexes.contains(following_segment_index))
+            }
+            else {
+                segment_index == selected_segment_indexes.last().unwrap()
+            };
+            if is_possibly_last {
+                largest_last_minimum_spacing = largest_last_minimum_spacing.max(minimum_spacing);
+            }
+        }
+        return minimum_bounding_length - largest_last_minimum_spacing;
+    }
+    // constructs the shifter incrementer over the next selection of segments that fits within the bounding length, returning false once every selection is exhausted
+    fn try_set_next_shifter_incrementer(&mut self) -> bool {
+        while let Some(selected_optional_segment_indexes) = self.try_get_next_selected_optional_segment_indexes() {
+            let mut selected_segment_indexes: Vec<usize> = self.required_segment_indexes.iter().cloned().chain(selected_optional_segment_indexes).collect();
+            selected_segment_indexes.sort();
+            if selected_segment_indexes.is_empty() || self.get_minimum_bounding_length(&selected_segment_indexes) > self.bounding_length {
+                continue;
+            }
+            let selected_segments: Vec<Rc<Segment>> = selected_segment_indexes.iter()
+                .map(|segment_index| self.placed_segments[*segment_index].clone())
+                .collect();
+            let selected_spacing_per_segment_index: Vec<Spacing> = selected_segment_indexes.iter()
+                .map(|segment_index| self.spacing_per_segment_index[*segment_index].clone())
+                .collect();
+            let mut segment_permutation_shifter = if self.precedence_pairs.is_empty() {
+                SegmentPermutationShifter::new_with_spacing(selected_segments, self.origin, self.bounding_length, self.is_horizontal, selected_spacing_per_segment_index, self.is_swapping_permitted)
+            }
+            else {
+                // the precedence pairs refer to the position of each segment within the selection
+                let selected_precedence_pairs: Vec<(usize, usize)> = self.precedence_pairs.iter()
+                    .filter_map(|(preceding_segment_index, following_segment_index)| {
+                        let selected_preceding_segment_index = selected_segment_indexes.iter().position(|segment_index| segment_index == preceding_segment_index)?;
+                        let selected_following_segment_index = selected_segment_indexes.iter().position(|segment_index| segment_index == following_segment_index)?;
+                        Some((selected_preceding_segment_index, selected_following_segment_index))
+                    })
+                    .collect();
+                SegmentPermutationShifter::new_with_precedence(selected_segments, self.origin, self.bounding_length, self.is_horizontal, selected_spacing_per_segment_index, selected_precedence_pairs)
+            };
+            if self.is_randomized {
+                segment_permutation_shifter.randomize();
+            }
+            self.current_shifter_incrementer = Some(ShifterIncrementer::new(Box::new(segment_permutation_shifter), selected_segment_indexes));
+            return true;
+        }
+        return false;
+    }
+}
+
+impl Incrementer for OptionalSegmentPermutationIncrementer {
+    type T = (u8, u8);
+
+    fn try_increment(&mut self) -> bool {
+        loop {
+            if let Some(shifter_incrementer) = self.current_shifter_incrementer.as_mut() {
+                if shifter_incrementer.try_increment() {
+                    return true;
+                }
+                self.current_shifter_incrementer = None;
+            }
+            if !self.try_set_next_shifter_incrementer() {
+                return false;
+            }
+        }
+    }
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
+        return self.current_shifter_incrementer.as_ref().unwrap().get();
+    }
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        self.current_shifter_incrementer.as_ref().unwrap().visit(visitor);
+    }
+    fn reset(&mut self) {
+        self.is_randomized = false;
+        self.current_optional_segments_total_index = 0;
+        self.current_fixed_binary_density_incrementer = None;
+        self.current_shifter_incrementer = None;
+    }
+    fn randomize(&mut self) {
+        // the selections are visited in a random order by shuffling which optional segment each bit selects and the order of the optional segments totals, each selection starting from a random arrangement
+        fastrand::shuffle(&mut self.optional_segment_indexes);
+        fastrand::shuffle(&mut self.optional_segments_totals);
+        self.is_randomized = true;
+        self.current_optional_segments_total_index = 0;
+        self.current_fixed_binary_density_incrementer = None;
+        self.current_shifter_incrementer = None;
+    }
+}
+
+#[cfg(test)]
+mod optional_segment_permutation_incrementer_tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    fn get_segments(segment_lengths: &Vec<usize>, is_optional_per_segment_index: &Vec<bool>) -> Vec<Rc<Segment>> {
+        return segment_lengths.iter()
+            .zip(is_optional_per_segment_index.iter())
+            .map(|(segment_length, is_optional)| {
+                if *is_optional {
+                    Rc::new(Segment::new_optional(*segment_length))
+                }
+                else {
+                    Rc::new(Segment::new(*segment_length))
+                }
+            })
+            .collect();
+    }
+
+    #[rstest]
+    fn two_to_four_of_six_optional_segments() {
+        init();
+
+        let segment_lengths: Vec<usize> = vec![1, 2, 1, 1, 2, 1];
+        let bounding_length = 8;
+        let mut expected_arrangements_option: Option<BTreeSet<Vec<(usize, u8)>>> = None;
+        for is_randomized in [false, true] {
+            let mut optional_segment_permutation_incrementer = OptionalSegmentPermutationIncrementer::new(get_segments(&segment_lengths, &vec![true; 6]), 2..=4, (0, 0), bounding_length, true, 1, true);
+            if is_randomized {
+                optional_segment_permutation_incrementer.randomize();
+            }
+            let mut arrangements: BTreeSet<Vec<(usize, u8)>> = BTreeSet::new();
+            let mut segments_totals: BTreeSet<usize> = BTreeSet::new();
+            for indexed_elements in optional_segment_permutation_incrementer.iter() {
+                assert!(indexed_elements.len() >= 2);
+                assert!(indexed_elements.len() <= 4);
+                segments_totals.insert(indexed_elements.len());
+                let mut previous_end_option: Option<u8> = None;
+                for indexed_element in indexed_elements.iter() {
+                    if let Some(previous_end) = previous_end_option {
+                        assert!(indexed_element.element.0 > previous_end);
+                    }
+                    previous_end_option = Some(indexed_element.element.0 + segment_lengths[indexed_element.index] as u8);
+                    assert!(previous_end_option.unwrap() as usize <= bounding_length);
+                }
+                let arrangement: Vec<(usize, u8)> = indexed_elements.iter()
+                    .map(|indexed_element| (indexed_element.index, indexed_element.element.0))
+                    .collect();
+                assert!(arrangements.insert(arrangement));
+            }
+            // four segments with padding need at least 7 positions, so each total of optional segments is reached
+            assert_eq!(BTreeSet::from([2, 3, 4]), segments_totals);
+
+            // two segments of length one at both ends with one of the length one segments between them
+            assert!(arrangements.contains(&vec![(0, 0), (2, 3), (3, 7)]));
+            assert!(arrangements.contains(&vec![(5, 0), (1, 2)]));
+
+            // randomizing only changes the order of the arrangements
+            match &expected_arrangements_option {
+                Some(expected_arrangements) => assert_eq!(expected_arrangements, &arrangements),
+                None => expected_arrangements_option = Some(arrangements)
+            }
+        }
+    }
+
+    #[rstest]
+    fn required_segments_are_always_placed() {
+        init();
+
+        let segment_lengths: Vec<usize> = vec![1, 1, 1];
+        let mut optional_segment_permutation_incrementer = OptionalSegmentPermutationIncrementer::new(get_segments(&segment_lengths, &vec![false, true, true]), 0..=1, (0, 0), 3, true, 0, false);
+        let mut arrangements: Vec<Vec<(usize, u8)>> = Vec::new();
+        for _ in 0..2 {
+            arrangements = optional_segment_permutation_incrementer.iter()
+                .map(|indexed_elements| {
+                    indexed_elements.iter()
+                        .map(|indexed_element| (indexed_element.index, indexed_element.element.0))
+                        .collect()
+                })
+                .collect();
+            optional_segment_permutation_incrementer.reset();
+        }
+        assert_eq!(vec![
+            vec![(0, 0)],
+            vec![(0, 1)],
+            vec![(0, 2)],
+            vec![(0, 0), (1, 1)],
+            vec![(0, 0), (1, 2)],
+            vec![(0, 1), (1, 2)],
+            vec![(0, 0), (2, 1)],
+            vec![(0, 0), (2, 2)],
+            vec![(0, 1), (2, 2)]
+        ], arrangements);
+    }
+
+    #[rstest]
+    fn spacing_and_precedence_follow_the_selected_segments() {
+        init();
+
+        let segment_lengths: Vec<usize> = vec![1, 1, 1];
+        let spacing_per_segment_index: Vec<Spacing> = vec![Spacing::new(1, None), Spacing::new(0, None), Spacing::new(2, None)];
+        let optional_segment_permutation_incrementer = OptionalSegmentPermutationIncrementer::new_with_precedence(get_segments(&segment_lengths, &vec![false, true, true]), 0..=2, (0, 0), 5, true, spacing_per_segment_index.clone(), vec![(2, 0)]);
+        let mut arrangements: BTreeSet<Vec<(usize, u8)>> = BTreeSet::new();
+        for indexed_elements in optional_segment_permutation_incrementer.into_incrementer_iter() {
+            assert!(indexed_elements.iter().any(|indexed_element| indexed_element.index == 0));
+            for (previous_indexed_element, indexed_element) in indexed_elements.iter().zip(indexed_elements.iter().skip(1)) {
+                let previous_end = previous_indexed_element.element.0 as usize + segment_lengths[previous_indexed_element.index];
+                assert!(indexed_element.element.0 as usize >= previous_end + spacing_per_segment_index[previous_indexed_element.index].get_minimum());
+            }
+            if let Some(following_shift_index) = indexed_elements.iter().position(|indexed_element| indexed_element.index == 2) {
+                assert!(following_shift_index < indexed_elements.iter().position(|indexed_element| indexed_element.index == 0).unwrap());
+            }
+            let arrangement: Vec<(usize, u8)> = indexed_elements.iter()
+                .map(|indexed_element| (indexed_element.index, indexed_element.element.0))
+                .collect();
+            assert!(arrangements.insert(arrangement));
+        }
+        assert!(arrangements.contains(&vec![(0, 2)]));
+        assert!(arrangements.contains(&vec![(0, 0), (1, 2)]));
+        assert!(arrangements.contains(&vec![(2, 0), (0, 3)]));
+        assert!(arrangements.contains(&vec![(1, 0), (2, 1), (0, 4)]));
+        assert!(!arrangements.contains(&vec![(0, 0), (1, 1)]));
+        assert!(!arrangements.contains(&vec![(2, 0), (0, 2)]));
+        assert!(!arrangements.contains(&vec![(0, 0), (2, 2)]));
+    }
+
+    #[rstest]
+    fn many_optional_segments_are_selected_lazily() {
+        init();
+
+        // every one of the selections of forty optional segments could never be constructed up front
+        let segment_lengths: Vec<usize> = vec![1; 40];
+        let mut optional_segment_permutation_incrementer = OptionalSegmentPermutationIncrementer::new(get_segments(&segment_lengths, &vec![true; 40]), 1..=40, (0, 0), 40, true, 0, false);
+        let arrangements: Vec<Vec<(usize, u8)>> = optional_segment_permutation_incrementer.iter()
+            .take(3)
+            .map(|indexed_elements| {
+                indexed_elements.iter()
+                    .map(|indexed_element| (indexed_element.index, indexed_element.element.0))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(vec![
+            vec![(0, 0)],
+            vec![(0, 1)],
+            vec![(0, 2)]
+        ], arrangements);
+    }
+}
@@ -1,29 +1,132 @@
-use super::Incrementer;
+use super::{Incrementer};
 use crate::IndexedElement;
 
+// Purpose:
+//     To decide which of the remaining incrementers of a RoundRobinIncrementer should be incremented next
+//     RoundRobin: each incrementer is incremented once per turn
+//     Weighted: each incrementer is incremented up to its weight (in the order that the incrementers were provided) consecutively per turn
+//     YieldRate: the incrementer with the highest rate of yields, as recorded by record_yield(), is incremented next, where incrementers not yet incremented are tried first
+//     Priority: the incrementer with the highest priority, as set by set_priority(), is incremented next
+//     Ties are broken by continuing around from the latest incrementer so that equally ranked incrementers still take turns
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchedulingPolicy {
+    RoundRobin,
+    Weighted(Vec<usize>),
+    YieldRate,
+    Priority
+}
+
 pub struct RoundRobinIncrementer<T> {
     incrementers: Vec<Box<dyn Incrementer<T = T>>>,
+    scheduling_policy: SchedulingPolicy,
+    incrementer_indexes: Vec<usize>,
     current_available_indexes: Vec<usize>,
     current_available_indexes_index: Option<usize>,
+    current_consecutive_increments_total: usize,
+    increments_total_per_incrementer_index: Vec<usize>,
+    yields_total_per_incrementer_index: Vec<usize>,
+    priority_per_incrementer_index: Vec<usize>,
     is_completed: bool
 }
 
 impl<T> RoundRobinIncrementer<T> {
     pub fn new(incrementers: Vec<Box<dyn Incrementer<T = T>>>) -> Self {
-        let mut current_available_indexes: Vec<usize> = Vec::new();
+        return Self::new_with_scheduling_policy(incrementers, SchedulingPolicy::RoundRobin);
+    }
+    pub fn new_with_scheduling_policy(incrementers: Vec<Box<dyn Incrementer<T = T>>>, scheduling_policy: SchedulingPolicy) -> Self {
+        if let SchedulingPolicy::Weighted(weights) = &scheduling_policy {
+            if weights.len() != incrementers.len() {
+                panic!("Each incrementer must have exactly one weight.");
+            }
+            if weights.contains(&0) {
+                panic!("Each weight must be positive so that every incrementer is eventually incremented.");
+            }
+        }
+        let incrementer_indexes: Vec<usize> = (0..incrementers.len()).collect();
         let is_completed = incrementers.len() == 0;
+        let mut current_available_indexes: Vec<usize> = Vec::new();
         if !is_completed {
-            for index in 0..incrementers.len() {
-                current_available_indexes.push(index);
-            }
+            current_available_indexes = incrementer_indexes.clone();
         }
+        let incrementers_length = incrementers.len();
         RoundRobinIncrementer {
             incrementers: incrementers,
+            scheduling_policy: scheduling_policy,
+            incrementer_indexes: incrementer_indexes,
             current_available_indexes: current_available_indexes,
             current_available_indexes_index: None,
+            current_consecutive_increments_total: 0,
+            increments_total_per_incrementer_index: vec![0; incrementers_length],
+            yields_total_per_incrementer_index: vec![0; incrementers_length],
+            priority_per_incrementer_index: vec![0; incrementers_length],
             is_completed: is_completed
         }
     }
+    /// Records that the latest increment was productive, raising the yield rate of the incrementer that produced it for the YieldRate scheduling policy.
+    pub fn record_yield(&mut self, yields_total: usize) {
+        if let Some(current_available_indexes_index) = self.current_available_indexes_index {
+            if !self.is_completed {
+                let incrementer_index = self.current_available_indexes[current_available_indexes_index];
+                self.yields_total_per_incrementer_index[incrementer_index] += yields_total;
+                return;
+            }
+        }
+        panic!("Unexpected attempt to record a yield without a current increment.");
+    }
+    /// Sets the priority of the incrementer at the index that it was provided for the Priority scheduling policy, where higher priorities are incremented first.
+    pub fn set_priority(&mut self, incrementer_index: usize, priority: usize) {
+        self.priority_per_incrementer_index[incrementer_index] = priority;
+    }
+    // returns the incrementer with the higher yield rate, preferring the former when equal
+    fn is_yield_rate_higher(&self, incrementer_index: usize, other_incrementer_index: usize) -> bool {
+        // each rate starts as if one increment yielded once so that every incrementer is tried before it is passed over
+        let numerator = (self.yields_total_per_incrementer_index[incrementer_index] + 1) * (self.increments_total_per_incrementer_index[other_incrementer_index] + 1);
+        let other_numerator = (self.yields_total_per_incrementer_index[other_incrementer_index] + 1) * (self.increments_total_per_incrementer_index[incrementer_index] + 1);
+        return numerator > other_numerator;
+    }
+    // determines which of the available incrementers should be tried next, where is_current_removed indicates that the current available index now refers to the next incrementer
+    fn get_next_available_indexes_index(&self, is_current_removed: bool) -> usize {
+        let available_indexes_length = self.current_available_indexes.len();
+        let starting_available_indexes_index: usize;
+        if let Some(current_available_indexes_index) = self.current_available_indexes_index {
+            if is_current_removed {
+                starting_available_indexes_index = current_available_indexes_index % available_indexes_length;
+            }
+            else {
+                if let SchedulingPolicy::Weighted(weights) = &self.scheduling_policy {
+                    let incrementer_index = self.current_available_indexes[current_available_indexes_index];
+                    if self.current_consecutive_increments_total < weights[incrementer_index] {
+                        return current_available_indexes_index;
+                    }
+                }
+                starting_available_indexes_index = (current_available_indexes_index + 1) % available_indexes_length;
+            }
+        }
+        else {
+            starting_available_indexes_index = 0;
+        }
+        match self.scheduling_policy {
+            SchedulingPolicy::RoundRobin | SchedulingPolicy::Weighted(_) => {
+                return starting_available_indexes_index;
+            },
+            SchedulingPolicy::YieldRate | SchedulingPolicy::Priority => {
+                let mut best_available_indexes_index = starting_available_indexes_index;
+                for offset in 1..available_indexes_length {
+                    let available_indexes_index = (starting_available_indexes_index + offset) % available_indexes_length;
+                    let incrementer_index = self.current_available_indexes[available_indexes_index];
+                    let best_incrementer_index = self.current_available_indexes[best_available_indexes_index];
+                    let is_better = match self.scheduling_policy {
+                        SchedulingPolicy::YieldRate => self.is_yield_rate_higher(incrementer_index, best_incrementer_index),
+                        _ => self.priority_per_incrementer_index[incrementer_index] > self.priority_per_incrementer_index[best_incrementer_index]
+                    };
+                    if is_better {
+                        best_available_indexes_index = available_indexes_index;
+                    }
+                }
+                return best_available_indexes_index;
+            }
+        }
+    }
 }
 
 impl<T> Incrementer for RoundRobinIncrementer<T> {
@@ -33,32 +136,29 @@ impl<T> Incrementer for RoundRobinIncrementer<T> {
         if self.is_completed {
             return false;
         }
-        if let Some(mut current_available_indexes_index) = self.current_available_indexes_index {
-            current_available_indexes_index += 1;
-            if current_available_indexes_index == self.current_available_indexes.len() {
-                self.current_available_indexes_index = Some(0);
-            }
-            else {
-                self.current_available_indexes_index = Some(current_available_indexes_index);
-            }
-        }
-        else {
-            self.current_available_indexes_index = Some(0);
-        }
-        let mut incrementer_index: usize = self.current_available_indexes[self.current_available_indexes_index.unwrap()];
+        let previous_incrementer_index_option = self.current_available_indexes_index.map(|current_available_indexes_index| self.current_available_indexes[current_available_indexes_index]);
+        let mut current_available_indexes_index = self.get_next_available_indexes_index(false);
+        let mut incrementer_index: usize = self.current_available_indexes[current_available_indexes_index];
         while !self.incrementers[incrementer_index].try_increment() {
             debug!("removing incrementer {incrementer_index}");
-            self.current_available_indexes.remove(self.current_available_indexes_index.unwrap());
+            self.current_available_indexes.remove(current_available_indexes_index);
             if self.current_available_indexes.len() == 0 {
                 debug!("removed all incrementers");
                 self.is_completed = true;
                 return false;
             }
-            if self.current_available_indexes_index.unwrap() == self.current_available_indexes.len() {
-                self.current_available_indexes_index = Some(0);
-            }
-            incrementer_index = self.current_available_indexes[self.current_available_indexes_index.unwrap()];
+            self.current_available_indexes_index = Some(current_available_indexes_index);
+            current_available_indexes_index = self.get_next_available_indexes_index(true);
+            incrementer_index = self.current_available_indexes[current_available_indexes_index];
+        }
+        self.current_available_indexes_index = Some(current_available_indexes_index);
+        if previous_incrementer_index_option == Some(incrementer_index) {
+            self.current_consecutive_increments_total += 1;
         }
+        else {
+            self.current_consecutive_increments_total = 1;
+        }
+        self.increments_total_per_incrementer_index[incrementer_index] += 1;
         return true;
     }
     fn get(&self) -> Vec<IndexedElement<Self::T>> {
@@ -66,14 +166,20 @@ impl<T> Incrementer for RoundRobinIncrementer<T> {
         let indexed_elements = self.incrementers[incrementer_index].get();
         return indexed_elements;
     }
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        let incrementer_index: usize = self.current_available_indexes[self.current_available_indexes_index.unwrap()];
+        self.incrementers[incrementer_index].visit(visitor);
+    }
     fn reset(&mut self) {
         self.is_completed = self.incrementers.len() == 0;
         if !self.is_completed {
-            self.current_available_indexes.clear();
+            self.current_available_indexes = self.incrementer_indexes.clone();
             self.current_available_indexes_index = None;
-            for index in 0..self.incrementers.len() {
-                self.current_available_indexes.push(index);
-                self.incrementers[index].reset();
+            self.current_consecutive_increments_total = 0;
+            for incrementer_index in 0..self.incrementers.len() {
+                self.incrementers[incrementer_index].reset();
+                self.increments_total_per_incrementer_index[incrementer_index] = 0;
+                self.yields_total_per_incrementer_index[incrementer_index] = 0;
             }
         }
     }
@@ -81,18 +187,18 @@ impl<T> Incrementer for RoundRobinIncrementer<T> {
         for incrementer in self.incrementers.iter_mut() {
             incrementer.randomize();
         }
-        fastrand::shuffle(&mut self.incrementers);
-    }
-}
-
-impl<T> Iterator for RoundRobinIncrementer<T> {
-    type Item = Vec<IndexedElement<T>>;
-
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.try_increment() {
-            return Some(self.get());
+        // the order of the incrementers is shuffled instead of the incrementers themselves so that the weights and priorities remain with their incrementers
+        fastrand::shuffle(&mut self.incrementer_indexes);
+        if !self.is_completed {
+            let current_incrementer_index_option = self.current_available_indexes_index.map(|current_available_indexes_index| self.current_available_indexes[current_available_indexes_index]);
+            self.current_available_indexes = self.incrementer_indexes.iter()
+                .filter(|incrementer_index| self.current_available_indexes.contains(incrementer_index))
+                .cloned()
+                .collect();
+            if let Some(current_incrementer_index) = current_incrementer_index_option {
+                self.current_available_indexes_index = self.current_available_indexes.iter().position(|incrementer_index| *incrementer_index == current_incrementer_index);
+            }
         }
-        return None;
     }
 }
 
@@ -100,7 +206,7 @@ impl<T> Iterator for RoundRobinIncrementer<T> {
 mod round_robin_incrementer_tests {
     use std::{time::{Duration, Instant}, cell::RefCell, collections::BTreeSet, rc::Rc};
 
-    use crate::{incrementer::shifter_incrementer::ShifterIncrementer, shifter::segment_permutation_shifter::{SegmentPermutationShifter, Segment}};
+    use crate::{incrementer::shifter_incrementer::ShifterIncrementer, shifter::{index_shifter::IndexShifter, segment_permutation_shifter::{SegmentPermutationShifter, Segment}}};
 
     use super::*;
     use bitvec::{bits, vec::BitVec};
@@ -201,4 +307,84 @@ mod round_robin_incrementer_tests {
         }
         assert!(!round_robin_incrementer.try_increment());
     }
+
+    fn get_incrementers(states_totals: Vec<u8>) -> Vec<Box<dyn Incrementer<T = u8>>> {
+        let mut incrementers: Vec<Box<dyn Incrementer<T = u8>>> = Vec::new();
+        for (index, states_total) in states_totals.into_iter().enumerate() {
+            let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![(0..states_total).map(Rc::new).collect()];
+            incrementers.push(Box::new(ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![index])));
+        }
+        return incrementers;
+    }
+
+    fn get_incrementer_indexes(round_robin_incrementer: &mut RoundRobinIncrementer<u8>) -> Vec<usize> {
+        return round_robin_incrementer.iter()
+            .map(|indexed_elements| indexed_elements[0].index)
+            .collect();
+    }
+
+    #[rstest]
+    fn weighted_increments_consecutively() {
+        init();
+
+        let mut round_robin_incrementer = RoundRobinIncrementer::new_with_scheduling_policy(get_incrementers(vec![5, 2, 3]), SchedulingPolicy::Weighted(vec![2, 1, 3]));
+        for _ in 0..2 {
+            assert_eq!(vec![0, 0, 1, 2, 2, 2, 0, 0, 1, 0], get_incrementer_indexes(&mut round_robin_incrementer));
+            round_robin_incrementer.reset();
+        }
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn weighted_requires_weight_per_incrementer() {
+        RoundRobinIncrementer::new_with_scheduling_policy(get_incrementers(vec![1, 1]), SchedulingPolicy::Weighted(vec![1]));
+    }
+
+    #[rstest]
+    fn yield_rate_prefers_productive_incrementer() {
+        init();
+
+        let mut round_robin_incrementer = RoundRobinIncrementer::new_with_scheduling_policy(get_incrementers(vec![4, 4, 4]), SchedulingPolicy::YieldRate);
+        let mut incrementer_indexes: Vec<usize> = Vec::new();
+        while round_robin_incrementer.try_increment() {
+            let incrementer_index = round_robin_incrementer.get()[0].index;
+            if incrementer_index == 1 {
+                round_robin_incrementer.record_yield(1);
+            }
+            incrementer_indexes.push(incrementer_index);
+        }
+        // each incrementer is tried once before the productive incrementer is incremented to completion, after which the others continue to take turns
+        assert_eq!(vec![0, 1, 2, 1, 1, 1, 2, 0, 2, 0, 2, 0], incrementer_indexes);
+    }
+
+    #[rstest]
+    fn priority_prefers_highest_priority() {
+        init();
+
+        let mut round_robin_incrementer = RoundRobinIncrementer::new_with_scheduling_policy(get_incrementers(vec![2, 2, 2]), SchedulingPolicy::Priority);
+        assert_eq!(vec![0, 1, 2, 0, 1, 2], get_incrementer_indexes(&mut round_robin_incrementer));
+        round_robin_incrementer.reset();
+        round_robin_incrementer.set_priority(2, 2);
+        round_robin_incrementer.set_priority(1, 1);
+        assert_eq!(vec![2, 2, 1, 1, 0, 0], get_incrementer_indexes(&mut round_robin_incrementer));
+    }
+
+    #[rstest]
+    fn visit_provides_same_indexed_elements_as_get() {
+        init();
+
+        let mut round_robin_incrementer = RoundRobinIncrementer::new(get_incrementers(vec![3, 1, 2]));
+        let mut increments_total = 0;
+        while round_robin_incrementer.try_increment() {
+            increments_total += 1;
+            let expected_indexed_elements = round_robin_incrementer.get();
+            let mut visits_total = 0;
+            round_robin_incrementer.visit(&mut |indexed_elements| {
+                visits_total += 1;
+                assert_eq!(expected_indexed_elements.as_slice(), indexed_elements);
+            });
+            assert_eq!(1, visits_total);
+        }
+        assert_eq!(6, increments_total);
+    }
 }
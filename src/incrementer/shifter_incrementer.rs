@@ -1,31 +1,66 @@
+use std::{rc::Rc, marker::PhantomData, ops::DerefMut};
 use crate::{shifter::Shifter, IndexedElement};
-use super::Incrementer;
+use super::{Incrementer};
 
 // Purpose: with each iteration, evaluates a complete shifted state of the underlying shifter
-pub struct ShifterIncrementer<T> {
-    shifter: Box<dyn Shifter<T = T>>,
+// Notes:
+//     the current elements are kept as element indexes and state indexes so that incrementing never clones a state
+//     the element indexes are mapped as they are kept when the index mapping covers every element index of the shifter, otherwise they are mapped when read by get(), such that a shorter index mapping may still be incremented over
+//     the shifter may be held by any pointer, such as a mutable reference, so that a borrowed shifter can be searched the same way as an owned one
+pub struct ShifterIncrementer<T, TShifter = Box<dyn Shifter<T = T>>> where TShifter: DerefMut, TShifter::Target: Shifter<T = T> {
+    shifter: TShifter,
     index_mapping: Vec<usize>,
     is_started: bool,
     is_completed: bool,
-    current_indexed_elements: Vec<IndexedElement<T>>,
-    shifter_length: usize
+    current_element_index_and_state_index_pairs: Vec<(usize, usize)>,
+    shifter_length: usize,
+    is_index_mapping_complete: bool,
+    phantom_t: PhantomData<T>
 }
 
 impl<T> ShifterIncrementer<T> {
     pub fn new(shifter: Box<dyn Shifter<T = T>>, index_mapping: Vec<usize>) -> Self {
+        return Self::new_with_shifter_pointer(shifter, index_mapping);
+    }
+}
+
+impl<T, TShifter> ShifterIncrementer<T, TShifter> where TShifter: DerefMut, TShifter::Target: Shifter<T = T> {
+    /// Accepts any pointer to the shifter, such as a mutable reference, so that a shifter can be searched without giving up ownership of it.
+    pub fn new_with_shifter_pointer(shifter: TShifter, index_mapping: Vec<usize>) -> Self {
         let shifter_length = shifter.get_length();
+        let is_index_mapping_complete = index_mapping.len() >= shifter_length;
         ShifterIncrementer {
             shifter: shifter,
             index_mapping: index_mapping,
             is_started: shifter_length == 0,
             is_completed: shifter_length == 0,
-            current_indexed_elements: Vec::new(),
-            shifter_length: shifter_length
+            current_element_index_and_state_index_pairs: Vec::new(),
+            shifter_length: shifter_length,
+            is_index_mapping_complete: is_index_mapping_complete,
+            phantom_t: PhantomData
+        }
+    }
+    /// Returns the index that each element index of the underlying shifter is mapped to.
+    pub fn get_index_mapping(&self) -> &[usize] {
+        return &self.index_mapping;
+    }
+    /// Returns the distinct states of the underlying shifter.
+    pub fn get_states_ref(&self) -> &[Rc<T>] {
+        return self.shifter.get_states_ref();
+    }
+    // keeps the current element index mapped only if every element index can be mapped, leaving get() to map it otherwise
+    fn push_current_element_index_and_state_index(&mut self) {
+        let (element_index, state_index) = self.shifter.get_element_index_and_state_index();
+        if self.is_index_mapping_complete {
+            self.current_element_index_and_state_index_pairs.push((self.index_mapping[element_index], state_index));
+        }
+        else {
+            self.current_element_index_and_state_index_pairs.push((element_index, state_index));
         }
     }
 }
 
-impl<T> Incrementer for ShifterIncrementer<T> {
+impl<T, TShifter> Incrementer for ShifterIncrementer<T, TShifter> where TShifter: DerefMut, TShifter::Target: Shifter<T = T> {
     type T = T;
 
     fn try_increment(&mut self) -> bool {
@@ -35,19 +70,18 @@ impl<T> Incrementer for ShifterIncrementer<T> {
         if !self.is_started {
             self.is_started = true;
             let mut is_forward_required = true;
-            while self.current_indexed_elements.len() != self.shifter_length {
+            while self.current_element_index_and_state_index_pairs.len() != self.shifter_length {
                 if is_forward_required && !self.shifter.try_forward() {
                     panic!("Unexpectedly failed to move forward when not at the end.");
                     //self.is_completed = true;
                     //return false;
                 }
                 if self.shifter.try_increment() {
-                    let indexed_element = self.shifter.get_indexed_element();
-                    self.current_indexed_elements.push(indexed_element);
+                    self.push_current_element_index_and_state_index();
                     is_forward_required = true;
                 }
                 else {
-                    self.current_indexed_elements.pop();
+                    self.current_element_index_and_state_index_pairs.pop();
                     if !self.shifter.try_backward() {
                         // failed to find any valid initial set of states
                         self.is_completed = true;
@@ -56,25 +90,24 @@ impl<T> Incrementer for ShifterIncrementer<T> {
                     is_forward_required = false;
                 }
             }
-            return self.current_indexed_elements.len() != 0;
+            return self.current_element_index_and_state_index_pairs.len() != 0;
         }
-        self.current_indexed_elements.pop();
-        while self.current_indexed_elements.len() != self.shifter_length {
+        self.current_element_index_and_state_index_pairs.pop();
+        while self.current_element_index_and_state_index_pairs.len() != self.shifter_length {
             if self.shifter.try_increment() {
-                let indexed_element = self.shifter.get_indexed_element();
-                self.current_indexed_elements.push(indexed_element);
-                if self.current_indexed_elements.len() != self.shifter_length {
+                self.push_current_element_index_and_state_index();
+                if self.current_element_index_and_state_index_pairs.len() != self.shifter_length {
                     if !self.shifter.try_forward() {
                         panic!("Unexpectedly failed to move forward when not at the end.");
                     }
                 }
             }
             else {
-                if self.current_indexed_elements.len() == 0 {
+                if self.current_element_index_and_state_index_pairs.len() == 0 {
                     self.is_completed = true;
                     return false;
                 }
-                self.current_indexed_elements.pop();
+                self.current_element_index_and_state_index_pairs.pop();
                 if !self.shifter.try_backward() {
                     panic!("Unexpectedly failed to move backward when not at the beginning.");
                 }
@@ -83,31 +116,39 @@ impl<T> Incrementer for ShifterIncrementer<T> {
         return true;
     }
     fn get(&self) -> Vec<IndexedElement<Self::T>> {
-        return self.current_indexed_elements
+        let states = self.shifter.get_states_ref();
+        return self.current_element_index_and_state_index_pairs
             .iter()
-            .map(|indexed_element| { IndexedElement::new(indexed_element.element.clone(), self.index_mapping[indexed_element.index]) })
+            .map(|(element_index, state_index)| {
+                if self.is_index_mapping_complete {
+                    return IndexedElement::new(states[*state_index].clone(), *element_index);
+                }
+                return IndexedElement::new(states[*state_index].clone(), self.index_mapping[*element_index]);
+            })
             .collect();
     }
     fn reset(&mut self) {
         self.shifter.reset();
         self.is_started = false;
         self.is_completed = false;
-        self.current_indexed_elements.clear();
+        self.current_element_index_and_state_index_pairs.clear();
 
     }
     fn randomize(&mut self) {
         self.shifter.randomize();
     }
-}
-
-impl<T> Iterator for ShifterIncrementer<T> {
-    type Item = Vec<IndexedElement<T>>;
-
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.try_increment() {
-            return Some(self.get());
+    fn get_element_index_and_state_index_pairs(&self) -> Option<&[(usize, usize)]> {
+        // the element indexes are only kept mapped when the index mapping covers every element index
+        if !self.is_index_mapping_complete {
+            return None;
         }
-        return None;
+        return Some(&self.current_element_index_and_state_index_pairs);
+    }
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return self.shifter.get_state_table();
+    }
+    fn get_state(&self, state_index: usize) -> Option<&Self::T> {
+        return Some(self.shifter.get_state(state_index));
     }
 }
 
@@ -115,7 +156,7 @@ impl<T> Iterator for ShifterIncrementer<T> {
 mod shifter_incrementer_tests {
     use std::{time::{Duration, Instant}, cell::RefCell, collections::BTreeSet, rc::Rc};
 
-    use crate::shifter::{segment_permutation_shifter::{SegmentPermutationShifter, Segment}, hyper_graph_cliche_shifter::{HyperGraphClicheShifter, StatefulHyperGraphNode}};
+    use crate::{incrementer::{chain_incrementer::ChainIncrementer, limited_incrementer::LimitedIncrementer}, shifter::{segment_permutation_shifter::{SegmentPermutationShifter, Segment}, hyper_graph_cliche_shifter::{HyperGraphClicheShifter, StatefulHyperGraphNode}, index_shifter::IndexShifter, combined_shifter::CombinedShifter}};
 
     use super::*;
     use bitvec::{bits, vec::BitVec};
@@ -274,4 +315,112 @@ mod shifter_incrementer_tests {
         }
         assert!(!incrementer.try_increment());
     }
-}
\ No newline at end of file
+
+    #[rstest]
+    fn state_indexes_match_indexed_elements() {
+        init();
+
+        let mut shifter_incrementer = ShifterIncrementer::new(
+            Box::new(SegmentPermutationShifter::new(
+                vec![
+                    Rc::new(Segment::new(1)),
+                    Rc::new(Segment::new(2))
+                ],
+                (10, 100),
+                6,
+                true,
+                1,
+                true
+            )),
+            vec![5, 7]
+        );
+        let mut increments_total = 0;
+        while shifter_incrementer.try_increment() {
+            increments_total += 1;
+            let located_states: Vec<(usize, (u8, u8))> = shifter_incrementer.get_element_index_and_state_index_pairs().unwrap().iter()
+                .map(|(element_index, state_index)| (*element_index, *shifter_incrementer.get_state(*state_index).unwrap()))
+                .collect();
+            let expected_located_states: Vec<(usize, (u8, u8))> = shifter_incrementer.get().iter()
+                .map(|indexed_element| (indexed_element.index, *indexed_element.element))
+                .collect();
+            assert_eq!(expected_located_states, located_states);
+        }
+        assert_eq!(12, increments_total);
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn state_indexes_read_without_state_table(#[case] is_combined: bool) {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<(u8, u8)>>> = vec![
+            vec![Rc::new((1, 2)), Rc::new((3, 4))],
+            vec![Rc::new((5, 6)), Rc::new((1, 2))]
+        ];
+        let shifter: Box<dyn Shifter<T = (u8, u8)>> = if is_combined {
+            let shifters: Vec<Rc<RefCell<dyn Shifter<T = (u8, u8)>>>> = vec![Rc::new(RefCell::new(IndexShifter::new(&states_per_shift_index)))];
+            Box::new(CombinedShifter::new(&shifters, true))
+        }
+        else {
+            Box::new(IndexShifter::new(&states_per_shift_index))
+        };
+        assert!(shifter.get_state_table().is_none());
+        let mut incrementer: Box<dyn Incrementer<T = (u8, u8)>> = Box::new(ShifterIncrementer::new(shifter, vec![3, 1]));
+        let mut increments_total = 0;
+        while incrementer.try_increment() {
+            increments_total += 1;
+            let located_states: Vec<(usize, (u8, u8))> = incrementer.get_element_index_and_state_index_pairs().unwrap().iter()
+                .map(|(element_index, state_index)| (*element_index, *incrementer.get_state(*state_index).unwrap()))
+                .collect();
+            let expected_located_states: Vec<(usize, (u8, u8))> = incrementer.get().iter()
+                .map(|indexed_element| (indexed_element.index, *indexed_element.element))
+                .collect();
+            assert_eq!(expected_located_states, located_states);
+        }
+        assert_eq!(4, increments_total);
+    }
+
+    #[rstest]
+    fn shorter_index_mapping_is_mapped_when_read() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<(u8, u8)>>> = vec![
+            vec![Rc::new((1, 2)), Rc::new((3, 4))],
+            vec![Rc::new((5, 6))]
+        ];
+        let mut shifter_incrementer = ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![7]);
+        let mut increments_total = 0;
+        while shifter_incrementer.try_increment() {
+            increments_total += 1;
+            assert!(shifter_incrementer.get_element_index_and_state_index_pairs().is_none());
+        }
+        assert_eq!(2, increments_total);
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn shorter_index_mapping_panics_when_read() {
+        let states_per_shift_index: Vec<Vec<Rc<(u8, u8)>>> = vec![
+            vec![Rc::new((1, 2))],
+            vec![Rc::new((5, 6))]
+        ];
+        let mut shifter_incrementer = ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![7]);
+        assert!(shifter_incrementer.try_increment());
+        shifter_incrementer.get();
+    }
+
+    #[rstest]
+    fn incrementer_without_state_indexes_has_no_state() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<(u8, u8)>>> = vec![vec![Rc::new((1, 2))]];
+        let mut limited_incrementer = LimitedIncrementer::new(Box::new(ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![0])), 1);
+        assert!(limited_incrementer.try_increment());
+        assert_eq!(Some(&(1, 2)), limited_incrementer.get_state(0));
+        let mut chain_incrementer = ChainIncrementer::new(vec![Box::new(ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![0]))]);
+        assert!(chain_incrementer.try_increment());
+        assert!(chain_incrementer.get_element_index_and_state_index_pairs().is_none());
+        assert_eq!(None, chain_incrementer.get_state(0));
+    }
+}
@@ -6,48 +6,150 @@
 //                  It can be set back to None as each index is incremented across (from shift index 0 to n as each shift index state is found to be valid) since there's no need to look back
 //                  It is filled from a master collection per shift index and state key of vectors of BTreeSets, filled as new bad pairs are discovered.
 
-use std::{collections::{VecDeque, BTreeSet}, rc::Rc, cell::RefCell};
+use std::{collections::{VecDeque, BTreeSet}, rc::Rc, cell::RefCell, hash::Hash};
 use bitvec::vec::BitVec;
 use crate::{shifter::{Shifter}, IndexedElement, CellGroup};
-use super::Incrementer;
+use super::{Incrementer, nogood_cache::NogoodCache};
 
 /// This struct specifies that "this" cell group location has "these" cell group location collections as dependencies such that if being at that location makes all of them invalid, then that location must be invalid
-pub struct CellGroupDependency {
+pub struct CellGroupDependency<TLocation = (u8, u8)> {
     cell_group_index_mapping: Vec<usize>,
-    shifter: Box<dyn Shifter<T = (u8, u8)>>
+    shifter: Box<dyn Shifter<T = TLocation>>
 }
 
-impl CellGroupDependency {
-    pub fn new(cell_group_index_mapping: Vec<usize>, shifter: Box<dyn Shifter<T = (u8, u8)>>) -> Self {
+impl<TLocation> CellGroupDependency<TLocation> {
+    /// The shifter must not rotate its elements, such as a RectanglePackingShifter permitting rotation, since each cell group is compared by its original cells at every location.
+    pub fn new(cell_group_index_mapping: Vec<usize>, shifter: Box<dyn Shifter<T = TLocation>>) -> Self {
+        if shifter.is_rotation_possible() {
+            panic!("Unexpected shifter that may rotate its elements since the cells of each cell group are compared without rotation.");
+        }
         CellGroupDependency {
             cell_group_index_mapping: cell_group_index_mapping,
             shifter: shifter
         }
     }
+    pub fn get_cell_group_index_mapping(&self) -> &[usize] {
+        return &self.cell_group_index_mapping;
+    }
+}
+
+/// This trait is implemented by the locations of cell groups, where each cell of a cell group is also a location relative to the location of the cell group, so that cell groups can be shifted across any number of dimensions
+pub trait CellLocation: Copy + Ord + Hash {
+    /// the offset from a location where another cell group is detected as invalid, which may be negative
+    type Offset;
+
+    /// Returns the location of the cell when its cell group is at this location.
+    fn get_cell_location(&self, cell: &Self) -> Self;
+    /// Returns the location at the offset from this location, or None if the location would be out of range.
+    fn try_get_offset_location(&self, offset: &Self::Offset) -> Option<Self>;
+}
+
+impl CellLocation for (u8, u8) {
+    type Offset = (i16, i16);
+
+    fn get_cell_location(&self, cell: &Self) -> Self {
+        return (cell.0 + self.0, cell.1 + self.1);
+    }
+    fn try_get_offset_location(&self, offset: &Self::Offset) -> Option<Self> {
+        return Some((u8::try_from(offset.0 + self.0 as i16).ok()?, u8::try_from(offset.1 + self.1 as i16).ok()?));
+    }
 }
 
-pub struct ShiftingCellGroupDependencyIncrementer {
-    cell_groups: Rc<Vec<CellGroup>>,
-    cell_group_dependencies: Vec<CellGroupDependency>,
-    detection_offsets_per_cell_group_index_per_cell_group_index: Option<Rc<Vec<Vec<Vec<(i16, i16)>>>>>,
+impl CellLocation for (u8, u8, u8) {
+    type Offset = (i16, i16, i16);
+
+    fn get_cell_location(&self, cell: &Self) -> Self {
+        return (cell.0 + self.0, cell.1 + self.1, cell.2 + self.2);
+    }
+    fn try_get_offset_location(&self, offset: &Self::Offset) -> Option<Self> {
+        return Some((u8::try_from(offset.0 + self.0 as i16).ok()?, u8::try_from(offset.1 + self.1 as i16).ok()?, u8::try_from(offset.2 + self.2 as i16).ok()?));
+    }
+}
+
+/// This trait specifies which neighboring cells are considered adjacent when checking cell groups for adjacency
+pub trait Neighborhood<TLocation> {
+    fn is_adjacent(&self, from_cell: &TLocation, to_cell: &TLocation) -> bool;
+}
+
+/// This enum specifies which neighboring cells are considered adjacent when checking cell groups for adjacency
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellNeighborhood {
+    /// cells are adjacent when they share an edge on a square grid (the 4-neighborhood)
+    Square,
+    /// cells are adjacent when they share an edge on a hex grid of axial coordinates (the 6-neighborhood)
+    Hexagonal
+}
+
+impl Neighborhood<(u8, u8)> for CellNeighborhood {
+    fn is_adjacent(&self, from_cell: &(u8, u8), to_cell: &(u8, u8)) -> bool {
+        let x_diff = to_cell.0 as i16 - from_cell.0 as i16;
+        let y_diff = to_cell.1 as i16 - from_cell.1 as i16;
+        match self {
+            CellNeighborhood::Square => {
+                return (x_diff.abs() == 1 && y_diff == 0) || (x_diff == 0 && y_diff.abs() == 1);
+            },
+            CellNeighborhood::Hexagonal => {
+                // in axial coordinates the six neighbors are the four square neighbors plus the two along the (1, -1) diagonal
+                return (x_diff.abs() == 1 && y_diff == 0) || (x_diff == 0 && y_diff.abs() == 1) || (x_diff == 1 && y_diff == -1) || (x_diff == -1 && y_diff == 1);
+            }
+        }
+    }
+}
+
+/// This enum specifies which neighboring voxels are considered adjacent when checking cell groups of voxels for adjacency
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoxelNeighborhood {
+    /// voxels are adjacent when they share a face (the 6-neighborhood)
+    Face
+}
+
+impl Neighborhood<(u8, u8, u8)> for VoxelNeighborhood {
+    fn is_adjacent(&self, from_cell: &(u8, u8, u8), to_cell: &(u8, u8, u8)) -> bool {
+        match self {
+            VoxelNeighborhood::Face => {
+                return from_cell.0.abs_diff(to_cell.0) as u16 + from_cell.1.abs_diff(to_cell.1) as u16 + from_cell.2.abs_diff(to_cell.2) as u16 == 1;
+            }
+        }
+    }
+}
+
+pub struct ShiftingCellGroupDependencyIncrementer<TLocation = (u8, u8), TNeighborhood = CellNeighborhood> where TLocation: CellLocation {
+    cell_groups: Rc<Vec<CellGroup<TLocation>>>,
+    cell_group_dependencies: Vec<CellGroupDependency<TLocation>>,
+    detection_offsets_per_cell_group_index_per_cell_group_index: Option<Rc<Vec<Vec<Vec<TLocation::Offset>>>>>,
     is_adjacent_cell_group_index_per_cell_group_index: Option<Rc<Vec<BitVec>>>,
+    cell_neighborhood: TNeighborhood,
     current_cell_group_dependency_index: Option<usize>,
-    current_locations: Vec<IndexedElement<(u8, u8)>>,
+    current_locations: Vec<IndexedElement<TLocation>>,
     current_element_index_and_adjusted_element_index_and_state_index_tuples: Vec<(usize, usize, usize)>,
     current_elements_total: usize,
     current_states_total: usize,
     current_is_checked: BitVec,
     current_is_valid: BitVec,
-    current_states: Vec<Rc<(u8, u8)>>
+    current_states: Vec<Rc<TLocation>>,
+    is_forward_checking_enabled: bool,
+    is_arc_consistency_enabled: bool,
+    current_is_state_index_remaining_per_element_index: Option<Vec<BitVec>>,
+    current_removed_element_index_and_state_index_tuples_per_location_index: Vec<Vec<(usize, usize)>>,
+    is_conflict_directed_backjumping_enabled: bool,
+    current_is_conflicting_location_index_per_location_index: Option<Vec<BitVec>>,
+    nogood_cache_option: Option<Rc<RefCell<NogoodCache<TLocation>>>>
 }
 
 impl ShiftingCellGroupDependencyIncrementer {
     pub fn new(cell_groups: Rc<Vec<CellGroup>>, cell_group_dependencies: Vec<CellGroupDependency>, detection_offsets_per_cell_group_index_per_cell_group_index: Option<Rc<Vec<Vec<Vec<(i16, i16)>>>>>, is_adjacent_cell_group_index_per_cell_group_index: Option<Rc<Vec<BitVec>>>) -> Self {
+        return Self::new_with_neighborhood(cell_groups, cell_group_dependencies, detection_offsets_per_cell_group_index_per_cell_group_index, is_adjacent_cell_group_index_per_cell_group_index, CellNeighborhood::Square);
+    }
+}
+
+impl<TLocation: CellLocation, TNeighborhood: Neighborhood<TLocation>> ShiftingCellGroupDependencyIncrementer<TLocation, TNeighborhood> {
+    pub fn new_with_neighborhood(cell_groups: Rc<Vec<CellGroup<TLocation>>>, cell_group_dependencies: Vec<CellGroupDependency<TLocation>>, detection_offsets_per_cell_group_index_per_cell_group_index: Option<Rc<Vec<Vec<Vec<TLocation::Offset>>>>>, is_adjacent_cell_group_index_per_cell_group_index: Option<Rc<Vec<BitVec>>>, cell_neighborhood: TNeighborhood) -> Self {
         ShiftingCellGroupDependencyIncrementer {
             cell_groups: cell_groups,
             cell_group_dependencies: cell_group_dependencies,
             detection_offsets_per_cell_group_index_per_cell_group_index: detection_offsets_per_cell_group_index_per_cell_group_index,
             is_adjacent_cell_group_index_per_cell_group_index: is_adjacent_cell_group_index_per_cell_group_index,
+            cell_neighborhood: cell_neighborhood,
             current_cell_group_dependency_index: None,
             current_locations: Vec::new(),
             current_element_index_and_adjusted_element_index_and_state_index_tuples: Vec::new(),
@@ -55,15 +157,388 @@ impl ShiftingCellGroupDependencyIncrementer {
             current_states_total: 0,
             current_is_checked: BitVec::default(),
             current_is_valid: BitVec::default(),
-            current_states: Vec::default()
+            current_states: Vec::default(),
+            is_forward_checking_enabled: false,
+            is_arc_consistency_enabled: false,
+            current_is_state_index_remaining_per_element_index: None,
+            current_removed_element_index_and_state_index_tuples_per_location_index: Vec::new(),
+            is_conflict_directed_backjumping_enabled: false,
+            current_is_conflicting_location_index_per_location_index: None,
+            nogood_cache_option: None
+        }
+    }
+    /// After each location is found to be valid, removes the states of later elements that are invalid with that location, backing up early if any later element has no remaining states.
+    /// This only applies to dependencies whose shifter provides the states per element index, such as an IndexShifter.
+    pub fn enable_forward_checking(&mut self) {
+        self.is_forward_checking_enabled = true;
+    }
+    /// Before searching each dependency, removes the states of each element that are invalid with every remaining state of some other element, repeating until no more states can be removed.
+    /// When combined with forward checking, this is also repeated across the later elements after each location is found to be valid.
+    /// This only applies to dependencies whose shifter provides the states per element index, such as an IndexShifter.
+    pub fn enable_arc_consistency(&mut self) {
+        self.is_arc_consistency_enabled = true;
+    }
+    /// When every state of some shift is invalid, moves backward directly to the latest earlier shift that caused one of those states to be invalid, skipping the shifts in between that could not have resolved the conflict.
+    /// This only applies to dependencies whose shifter increments each shift independently of the earlier shifts, such as an IndexShifter, since otherwise the states of a shift may depend on the skipped shifts.
+    pub fn enable_conflict_directed_backjumping(&mut self) {
+        self.is_conflict_directed_backjumping_enabled = true;
+    }
+    /// Skips the pairs of cell group locations and the larger collections of cell group locations that the nogood cache contains, adding each newly found nogood to the nogood cache.
+    /// Since the nogood cache outlives this incrementer, the nogoods found here can prune the searches of later incrementers over the same cell groups.
+    pub fn set_nogood_cache(&mut self, nogood_cache: Rc<RefCell<NogoodCache<TLocation>>>) {
+        self.nogood_cache_option = Some(nogood_cache);
+    }
+    fn add_nogood(&self, cell_group_index_and_location_tuples: Vec<(usize, TLocation)>) {
+        if let Some(nogood_cache) = &self.nogood_cache_option {
+            nogood_cache.borrow_mut().add(cell_group_index_and_location_tuples);
+        }
+    }
+    fn get_cell_group_index_and_location_tuple(&self, element_index_and_adjusted_element_index_and_state_index_tuple: (usize, usize, usize)) -> (usize, TLocation) {
+        return (element_index_and_adjusted_element_index_and_state_index_tuple.1, *self.current_states[element_index_and_adjusted_element_index_and_state_index_tuple.2]);
+    }
+    fn initialize_current_is_conflicting_location_index_per_location_index(&mut self) {
+        self.current_is_conflicting_location_index_per_location_index = None;
+        if !self.is_conflict_directed_backjumping_enabled {
+            return;
+        }
+        if self.cell_group_dependencies[self.current_cell_group_dependency_index.unwrap()].shifter.is_each_shift_independent() {
+            self.current_is_conflicting_location_index_per_location_index = Some((0..self.current_elements_total).map(|_| BitVec::repeat(false, self.current_elements_total)).collect());
+        }
+    }
+    // stores that the state being tried at the location index conflicted with the locations at the provided earlier location indexes
+    fn add_conflicting_location_indexes(&mut self, location_index: usize, conflicting_location_indexes: std::ops::Range<usize>) {
+        if let Some(is_conflicting_location_index_per_location_index) = &mut self.current_is_conflicting_location_index_per_location_index {
+            for conflicting_location_index in conflicting_location_indexes {
+                is_conflicting_location_index_per_location_index[location_index].set(conflicting_location_index, true);
+            }
+        }
+    }
+    // stores the earlier location indexes responsible for the state of the element no longer remaining, if it was removed while filtering after an earlier location
+    fn add_conflicting_location_indexes_for_removed_state_index(&mut self, location_index: usize, element_index: usize, state_index: usize) {
+        if self.current_is_conflicting_location_index_per_location_index.is_some() {
+            let removed_location_index_option = self.current_removed_element_index_and_state_index_tuples_per_location_index.iter()
+                .position(|removed_element_index_and_state_index_tuples| removed_element_index_and_state_index_tuples.contains(&(element_index, state_index)));
+            if let Some(removed_location_index) = removed_location_index_option {
+                if self.is_arc_consistency_enabled {
+                    // arc consistency may have removed the state due to any of the locations up to this point
+                    self.add_conflicting_location_indexes(location_index, 0..(removed_location_index + 1));
+                }
+                else {
+                    self.add_conflicting_location_indexes(location_index, removed_location_index..(removed_location_index + 1));
+                }
+            }
+        }
+    }
+    // every earlier location is a conflict for each location of a collection of valid locations, so that the search continues chronologically from any shift that led to a valid collection
+    // each location is also marked as conflicting with itself so that these conflicts are never mistaken for a nogood
+    fn set_all_earlier_location_indexes_conflicting(&mut self) {
+        if let Some(is_conflicting_location_index_per_location_index) = &mut self.current_is_conflicting_location_index_per_location_index {
+            for (location_index, is_conflicting_location_index) in is_conflicting_location_index_per_location_index.iter_mut().enumerate() {
+                is_conflicting_location_index[..=location_index].fill(true);
+            }
+        }
+    }
+    // returns how many times to move backward after every state of the location index has been tried, merging the conflicts of the location index into the location index being moved back to
+    // moving backward beyond the first location index means that no earlier location could resolve the conflict
+    fn get_backward_total(&mut self, location_index: usize) -> usize {
+        if let Some(is_conflicting_location_index_per_location_index) = &mut self.current_is_conflicting_location_index_per_location_index {
+            let mut is_conflicting_location_index = is_conflicting_location_index_per_location_index[location_index].clone();
+            is_conflicting_location_index_per_location_index[location_index].fill(false);
+            let is_valid_collection_found = is_conflicting_location_index[location_index];
+            is_conflicting_location_index.set(location_index, false);
+            if let Some(backjump_location_index) = is_conflicting_location_index.last_one() {
+                for conflicting_location_index in is_conflicting_location_index.iter_ones() {
+                    if conflicting_location_index != backjump_location_index {
+                        is_conflicting_location_index_per_location_index[backjump_location_index].set(conflicting_location_index, true);
+                    }
+                }
+                if is_valid_collection_found {
+                    is_conflicting_location_index_per_location_index[backjump_location_index].set(backjump_location_index, true);
+                }
+                else if let Some(nogood_cache) = &self.nogood_cache_option {
+                    // the conflicting locations leave no possible location for this location index
+                    let cell_group_index_and_location_tuples: Vec<(usize, TLocation)> = is_conflicting_location_index.iter_ones()
+                        .map(|conflicting_location_index| (self.current_locations[conflicting_location_index].index, *self.current_locations[conflicting_location_index].element))
+                        .collect();
+                    nogood_cache.borrow_mut().add(cell_group_index_and_location_tuples);
+                }
+                for skipped_location_index in (backjump_location_index + 1)..location_index {
+                    is_conflicting_location_index_per_location_index[skipped_location_index].fill(false);
+                }
+                if location_index - backjump_location_index > 1 {
+                    debug!("backjumping from location {} to location {}", location_index, backjump_location_index);
+                }
+                return location_index - backjump_location_index;
+            }
+            return location_index + 1;
+        }
+        return 1;
+    }
+    fn initialize_current_is_state_index_remaining_per_element_index(&mut self) {
+        self.current_is_state_index_remaining_per_element_index = None;
+        self.current_removed_element_index_and_state_index_tuples_per_location_index.clear();
+        if !self.is_forward_checking_enabled && !self.is_arc_consistency_enabled {
+            return;
+        }
+        if let Some(state_indexes_per_element_index) = self.cell_group_dependencies[self.current_cell_group_dependency_index.unwrap()].shifter.get_state_indexes_per_element_index() {
+            let mut is_state_index_remaining_per_element_index: Vec<BitVec> = Vec::new();
+            for state_indexes in state_indexes_per_element_index.iter() {
+                let mut is_state_index_remaining: BitVec = BitVec::repeat(false, self.current_states_total);
+                for state_index in state_indexes.iter() {
+                    is_state_index_remaining.set(*state_index, true);
+                }
+                is_state_index_remaining_per_element_index.push(is_state_index_remaining);
+            }
+            self.current_is_state_index_remaining_per_element_index = Some(is_state_index_remaining_per_element_index);
+
+            if self.is_arc_consistency_enabled {
+                // the removed states are never restored since they are invalid regardless of the other locations
+                let element_indexes: Vec<usize> = (0..self.current_elements_total).collect();
+                let mut removed_element_index_and_state_index_tuples: Vec<(usize, usize)> = Vec::new();
+                let mut arcs: VecDeque<(usize, usize)> = VecDeque::new();
+                for from_element_index in element_indexes.iter() {
+                    for to_element_index in element_indexes.iter() {
+                        if from_element_index != to_element_index {
+                            arcs.push_back((*from_element_index, *to_element_index));
+                        }
+                    }
+                }
+                self.try_make_arc_consistent(&element_indexes, arcs, &mut removed_element_index_and_state_index_tuples);
+                debug!("removed {} states before searching", removed_element_index_and_state_index_tuples.len());
+            }
+        }
+    }
+    fn is_state_index_remaining(&self, element_index: usize, state_index: usize) -> bool {
+        if let Some(is_state_index_remaining_per_element_index) = &self.current_is_state_index_remaining_per_element_index {
+            return is_state_index_remaining_per_element_index[element_index][state_index];
+        }
+        return true;
+    }
+    fn pop_current_location(&mut self) {
+        self.current_locations.pop();
+        self.current_element_index_and_adjusted_element_index_and_state_index_tuples.pop();
+        if let Some(removed_element_index_and_state_index_tuples) = self.current_removed_element_index_and_state_index_tuples_per_location_index.pop() {
+            self.restore_state_indexes(&removed_element_index_and_state_index_tuples);
+        }
+    }
+    fn restore_state_indexes(&mut self, removed_element_index_and_state_index_tuples: &Vec<(usize, usize)>) {
+        if let Some(is_state_index_remaining_per_element_index) = &mut self.current_is_state_index_remaining_per_element_index {
+            for (element_index, state_index) in removed_element_index_and_state_index_tuples.iter() {
+                is_state_index_remaining_per_element_index[*element_index].set(*state_index, true);
+            }
+        }
+    }
+    fn get_element_index_and_adjusted_element_index_and_state_index_tuple(&self, element_index: usize, state_index: usize) -> (usize, usize, usize) {
+        return (element_index, self.cell_group_dependencies[self.current_cell_group_dependency_index.unwrap()].cell_group_index_mapping[element_index], state_index);
+    }
+    // removes the states of the later elements that are invalid with the current location, returning false if any later element is left without a remaining state
+    // the removed states are stored alongside the current location so that they can be restored when the current location is popped
+    fn try_filter_remaining_state_indexes(&mut self, current_element_index_and_adjusted_element_index_and_state_index_tuple: (usize, usize, usize)) -> bool {
+        let mut removed_element_index_and_state_index_tuples: Vec<(usize, usize)> = Vec::new();
+        if self.is_forward_checking_enabled && self.current_is_state_index_remaining_per_element_index.is_some() {
+            let mut later_element_indexes: Vec<usize> = Vec::new();
+            for element_index in 0..self.current_elements_total {
+                if element_index != current_element_index_and_adjusted_element_index_and_state_index_tuple.0 &&
+                        !self.current_element_index_and_adjusted_element_index_and_state_index_tuples.iter().any(|tuple| tuple.0 == element_index) {
+
+                    later_element_indexes.push(element_index);
+                }
+            }
+            let mut arcs: VecDeque<(usize, usize)> = VecDeque::new();
+            let mut is_wiped_out = false;
+            for later_element_index in later_element_indexes.iter() {
+                let mut is_later_element_changed = false;
+                for state_index in self.current_is_state_index_remaining_per_element_index.as_ref().unwrap()[*later_element_index].iter_ones().collect::<Vec<usize>>() {
+                    let later_element_index_and_adjusted_element_index_and_state_index_tuple = self.get_element_index_and_adjusted_element_index_and_state_index_tuple(*later_element_index, state_index);
+                    if !self.is_pair_valid(later_element_index_and_adjusted_element_index_and_state_index_tuple, current_element_index_and_adjusted_element_index_and_state_index_tuple) {
+                        self.current_is_state_index_remaining_per_element_index.as_mut().unwrap()[*later_element_index].set(state_index, false);
+                        removed_element_index_and_state_index_tuples.push((*later_element_index, state_index));
+                        is_later_element_changed = true;
+                    }
+                }
+                if self.current_is_state_index_remaining_per_element_index.as_ref().unwrap()[*later_element_index].not_any() {
+                    is_wiped_out = true;
+                    break;
+                }
+                if is_later_element_changed {
+                    for other_later_element_index in later_element_indexes.iter() {
+                        if other_later_element_index != later_element_index {
+                            arcs.push_back((*other_later_element_index, *later_element_index));
+                        }
+                    }
+                }
+            }
+            if !is_wiped_out && self.is_arc_consistency_enabled {
+                is_wiped_out = !self.try_make_arc_consistent(&later_element_indexes, arcs, &mut removed_element_index_and_state_index_tuples);
+            }
+            if is_wiped_out {
+                self.restore_state_indexes(&removed_element_index_and_state_index_tuples);
+                return false;
+            }
+        }
+        self.current_removed_element_index_and_state_index_tuples_per_location_index.push(removed_element_index_and_state_index_tuples);
+        return true;
+    }
+    // AC-3 over the provided elements: removes each state of the "from" element that is invalid with every remaining state of the "to" element, revisiting arcs into any element that changed
+    // returns false if any element is left without a remaining state
+    fn try_make_arc_consistent(&mut self, element_indexes: &Vec<usize>, mut arcs: VecDeque<(usize, usize)>, removed_element_index_and_state_index_tuples: &mut Vec<(usize, usize)>) -> bool {
+        while let Some((from_element_index, to_element_index)) = arcs.pop_front() {
+            let to_state_indexes: Vec<usize> = self.current_is_state_index_remaining_per_element_index.as_ref().unwrap()[to_element_index].iter_ones().collect();
+            let mut is_from_element_changed = false;
+            for from_state_index in self.current_is_state_index_remaining_per_element_index.as_ref().unwrap()[from_element_index].iter_ones().collect::<Vec<usize>>() {
+                let from_element_index_and_adjusted_element_index_and_state_index_tuple = self.get_element_index_and_adjusted_element_index_and_state_index_tuple(from_element_index, from_state_index);
+                let mut is_supported = false;
+                for to_state_index in to_state_indexes.iter() {
+                    let to_element_index_and_adjusted_element_index_and_state_index_tuple = self.get_element_index_and_adjusted_element_index_and_state_index_tuple(to_element_index, *to_state_index);
+                    // the element shifted to later is considered the current element, just as it would be while shifting
+                    let is_valid: bool;
+                    if from_element_index > to_element_index {
+                        is_valid = self.is_pair_valid(from_element_index_and_adjusted_element_index_and_state_index_tuple, to_element_index_and_adjusted_element_index_and_state_index_tuple);
+                    }
+                    else {
+                        is_valid = self.is_pair_valid(to_element_index_and_adjusted_element_index_and_state_index_tuple, from_element_index_and_adjusted_element_index_and_state_index_tuple);
+                    }
+                    if is_valid {
+                        is_supported = true;
+                        break;
+                    }
+                }
+                if !is_supported {
+                    self.current_is_state_index_remaining_per_element_index.as_mut().unwrap()[from_element_index].set(from_state_index, false);
+                    removed_element_index_and_state_index_tuples.push((from_element_index, from_state_index));
+                    is_from_element_changed = true;
+                }
+            }
+            if is_from_element_changed {
+                if self.current_is_state_index_remaining_per_element_index.as_ref().unwrap()[from_element_index].not_any() {
+                    return false;
+                }
+                for other_element_index in element_indexes.iter() {
+                    if *other_element_index != from_element_index && *other_element_index != to_element_index {
+                        arcs.push_back((*other_element_index, from_element_index));
+                    }
+                }
+            }
+        }
+        return true;
+    }
+    // determines if the current element at its state is valid alongside the other element at its state, memoizing the result for the pair
+    fn is_pair_valid(&mut self, current_element_index_and_adjusted_element_index_and_state_index_tuple: (usize, usize, usize), other_element_index_and_adjusted_element_index_and_state_index_tuple: (usize, usize, usize)) -> bool {
+        let bitvec_index: usize;
+        {
+            let first_element_index: usize;
+            let second_element_index: usize;
+            let first_state_index: usize;
+            let second_state_index: usize;
+            if other_element_index_and_adjusted_element_index_and_state_index_tuple.0 < current_element_index_and_adjusted_element_index_and_state_index_tuple.0 {
+                first_element_index = other_element_index_and_adjusted_element_index_and_state_index_tuple.0;
+                second_element_index = current_element_index_and_adjusted_element_index_and_state_index_tuple.0;
+                first_state_index = other_element_index_and_adjusted_element_index_and_state_index_tuple.2;
+                second_state_index = current_element_index_and_adjusted_element_index_and_state_index_tuple.2;
+            }
+            else {
+                first_element_index = current_element_index_and_adjusted_element_index_and_state_index_tuple.0;
+                second_element_index = other_element_index_and_adjusted_element_index_and_state_index_tuple.0;
+                first_state_index = current_element_index_and_adjusted_element_index_and_state_index_tuple.2;
+                second_state_index = other_element_index_and_adjusted_element_index_and_state_index_tuple.2;
+            }
+            bitvec_index = ((second_state_index * self.current_states_total + first_state_index) * self.current_elements_total + second_element_index) * self.current_elements_total + first_element_index;
+            //println!("ShiftingCellGroupDependency: try_increment: bitvec_index: {bitvec_index}");
+        }
+
+        // check if the pair of indexed elements have already been compared
+        if !self.current_is_checked[bitvec_index] {
+            //debug!("checking cell group {} against {}", current_element_index_and_adjusted_element_index_and_state_index_tuple.1, other_element_index_and_adjusted_element_index_and_state_index_tuple.1);
+            let current_index_element_location = self.current_states[current_element_index_and_adjusted_element_index_and_state_index_tuple.2].clone();
+            let other_index_element_location = self.current_states[other_element_index_and_adjusted_element_index_and_state_index_tuple.2].clone();
+
+            // verify that the pair of indexed elements are valid at the same time and location
+            let mut is_current_pair_valid = true;
+            let mut is_found_in_nogood_cache = false;
+            'is_current_pair_valid: {
+                if let Some(nogood_cache) = &self.nogood_cache_option {
+                    if nogood_cache.borrow().contains_pair(&self.get_cell_group_index_and_location_tuple(current_element_index_and_adjusted_element_index_and_state_index_tuple), &self.get_cell_group_index_and_location_tuple(other_element_index_and_adjusted_element_index_and_state_index_tuple)) {
+                        //debug!("found pair in nogood cache");
+                        is_found_in_nogood_cache = true;
+                        is_current_pair_valid = false;
+                        break 'is_current_pair_valid;
+                    }
+                }
+                let other_cell_group = &self.cell_groups[other_element_index_and_adjusted_element_index_and_state_index_tuple.1];
+                let current_cell_group = &self.cell_groups[current_element_index_and_adjusted_element_index_and_state_index_tuple.1];
+
+                let is_adjacency_expected_option: Option<bool>;
+                if let Some(is_adjacent_cell_group_index_per_cell_group_index) = &self.is_adjacent_cell_group_index_per_cell_group_index {
+                    is_adjacency_expected_option = Some(is_adjacent_cell_group_index_per_cell_group_index[current_element_index_and_adjusted_element_index_and_state_index_tuple.1][other_element_index_and_adjusted_element_index_and_state_index_tuple.1] || is_adjacent_cell_group_index_per_cell_group_index[other_element_index_and_adjusted_element_index_and_state_index_tuple.1][current_element_index_and_adjusted_element_index_and_state_index_tuple.1]);
+                    //debug!("is_adjacency_expected: {:?} for cell group {} and {}.", is_adjacency_expected_option, other_element_index_and_adjusted_element_index_and_state_index_tuple.1, current_element_index_and_adjusted_element_index_and_state_index_tuple.1);
+                }
+                else {
+                    is_adjacency_expected_option = None;
+                    //debug!("adjacency is not considered");
+                }
+                let mut is_adjacent: bool = false;
+
+                let mut detection_locations: BTreeSet<TLocation> = BTreeSet::new();
+                if let Some(detection_offsets_per_cell_group_index_per_cell_group_index) = &self.detection_offsets_per_cell_group_index_per_cell_group_index {
+                    for detection_offset in detection_offsets_per_cell_group_index_per_cell_group_index[current_element_index_and_adjusted_element_index_and_state_index_tuple.1][other_element_index_and_adjusted_element_index_and_state_index_tuple.1].iter() {
+                        if let Some(detection_location) = current_index_element_location.try_get_offset_location(detection_offset) {
+                            detection_locations.insert(detection_location);
+                        }
+                    }
+                }
+
+                // check for overlap and adjacency
+                for other_cell in other_cell_group.cells.iter() {
+                    let calculated_other_cell: TLocation = other_index_element_location.get_cell_location(other_cell);
+                    for current_cell in current_cell_group.cells.iter() {
+                        let calculated_current_cell: TLocation = current_index_element_location.get_cell_location(current_cell);
+                        if calculated_other_cell == calculated_current_cell {
+                            //debug!("found overlap at ({}, {})", calculated_current_cell.0, calculated_current_cell.1);
+                            is_current_pair_valid = false;
+                            break 'is_current_pair_valid;
+                        }
+                        else if !is_adjacent {
+                            if self.cell_neighborhood.is_adjacent(&calculated_other_cell, &calculated_current_cell) {
+                                is_adjacent = true;
+                            }
+                        }
+                    }
+                    if detection_locations.contains(&calculated_other_cell) {
+                        //debug!("found detection location at ({}, {})", calculated_other_cell.0, calculated_other_cell.1);
+                        is_current_pair_valid = false;
+                        break 'is_current_pair_valid;
+                    }
+                }
+
+                if let Some(is_adjacency_expected) = is_adjacency_expected_option {
+                    if is_adjacency_expected && !is_adjacent {
+                        //debug!("adjacency expected and found");
+                        is_current_pair_valid = false;
+                    }
+                    else if !is_adjacency_expected && is_adjacent {
+                        //debug!("adjacency not expected and yet found");
+                        is_current_pair_valid = false;
+                    }
+                }
+            }
+
+            if !is_current_pair_valid && !is_found_in_nogood_cache {
+                self.add_nogood(vec![self.get_cell_group_index_and_location_tuple(current_element_index_and_adjusted_element_index_and_state_index_tuple), self.get_cell_group_index_and_location_tuple(other_element_index_and_adjusted_element_index_and_state_index_tuple)]);
+            }
+            self.current_is_checked.set(bitvec_index, true);
+            self.current_is_valid.set(bitvec_index, is_current_pair_valid);
         }
+        else {
+            //debug!("already checked cell group {} against {} as {}", current_element_index_and_adjusted_element_index_and_state_index_tuple.1, other_element_index_and_adjusted_element_index_and_state_index_tuple.1, self.current_is_valid[bitvec_index]);
+        }
+        return self.current_is_valid[bitvec_index];
     }
 }
 
 // TODO implement Incrementer
 
-impl Incrementer for ShiftingCellGroupDependencyIncrementer {
-    type T = (u8, u8);
+impl<TLocation: CellLocation, TNeighborhood: Neighborhood<TLocation>> Incrementer for ShiftingCellGroupDependencyIncrementer<TLocation, TNeighborhood> {
+    type T = TLocation;
 
     fn try_increment(&mut self) -> bool {
         let mut is_current_cell_group_dependency_changed = false;
@@ -77,33 +552,27 @@ impl Incrementer for ShiftingCellGroupDependencyIncrementer {
         }
         while self.current_cell_group_dependency_index.unwrap() != self.cell_group_dependencies.len() {
             //debug!("choosing {:?}th dependency", self.current_cell_group_dependency_index);
-            let shifter: &mut Box<dyn Shifter<T = (u8, u8)>>;
-            let cell_group_dependency_cell_group_index_mapping: &Vec<usize>;
-            {
-                // construct the bitvecs for current_is_checked and current_is_valid if cell group dependency has recently changed
-                if is_current_cell_group_dependency_changed {
-                    is_current_cell_group_dependency_changed = false;
-
-                    let cell_group_dependency = &self.cell_group_dependencies[self.current_cell_group_dependency_index.unwrap()];
-                    self.current_elements_total = cell_group_dependency.shifter.get_length();
-                    self.current_states = cell_group_dependency.shifter.get_states();
-                    self.current_states_total = self.current_states.len();
-                    let bits_length = self.current_elements_total * self.current_elements_total * self.current_states_total * self.current_states_total;
-                    self.current_is_checked = BitVec::repeat(false, bits_length);
-                    self.current_is_valid = BitVec::repeat(false, bits_length);
-                }
-
-                let cell_group_dependency = &mut self.cell_group_dependencies[self.current_cell_group_dependency_index.unwrap()];
-                shifter = &mut cell_group_dependency.shifter;
-                cell_group_dependency_cell_group_index_mapping = &cell_group_dependency.cell_group_index_mapping;
-            }
-            let shifter_length = shifter.get_length();
+            // construct the bitvecs for current_is_checked and current_is_valid if cell group dependency has recently changed
+            if is_current_cell_group_dependency_changed {
+                is_current_cell_group_dependency_changed = false;
+
+                let cell_group_dependency = &self.cell_group_dependencies[self.current_cell_group_dependency_index.unwrap()];
+                self.current_elements_total = cell_group_dependency.shifter.get_length();
+                self.current_states = cell_group_dependency.shifter.get_states();
+                self.current_states_total = self.current_states.len();
+                let bits_length = self.current_elements_total * self.current_elements_total * self.current_states_total * self.current_states_total;
+                self.current_is_checked = BitVec::repeat(false, bits_length);
+                self.current_is_valid = BitVec::repeat(false, bits_length);
+                self.initialize_current_is_state_index_remaining_per_element_index();
+                self.initialize_current_is_conflicting_location_index_per_location_index();
+            }
+            let current_cell_group_dependency_index = self.current_cell_group_dependency_index.unwrap();
+            let shifter_length = self.cell_group_dependencies[current_cell_group_dependency_index].shifter.get_length();
             // loop until a valid collection of locations has been discovered
             let mut is_forward_required: bool;
             if self.current_locations.len() == shifter_length {
                 //debug!("popping last location to make room for next possible location");
-                self.current_locations.pop();  // remove the last valid location
-                self.current_element_index_and_adjusted_element_index_and_state_index_tuples.pop();
+                self.pop_current_location();  // remove the last valid location
                 //debug!("determined that forward is not required");
                 is_forward_required = false;
             }
@@ -118,151 +587,92 @@ impl Incrementer for ShiftingCellGroupDependencyIncrementer {
             while self.current_locations.len() != shifter_length && !is_fully_backward {
                 if is_forward_required {
                     //debug!("moving forward to next shift index");
-                    let is_forward_successful = shifter.try_forward();
+                    let is_forward_successful = self.cell_group_dependencies[current_cell_group_dependency_index].shifter.try_forward();
                     if !is_forward_successful {
                         panic!("Unexpectedly failed to move forward.");
                     }
                 }
                 //debug!("incrementing at current shift index");
-                let is_increment_successful = shifter.try_increment();
+                let is_increment_successful = self.cell_group_dependencies[current_cell_group_dependency_index].shifter.try_increment();
                 if !is_increment_successful {
                     //debug!("increment was not successful, so popping and backing up");
-                    if !shifter.try_backward() {
-                        //debug!("done with shifter, so trying next dependency");
-                        // this encapsulated shifter is done, so move onto the next dependency
-                        self.current_cell_group_dependency_index = Some(self.current_cell_group_dependency_index.unwrap() + 1);
-                        is_current_cell_group_dependency_changed = true;
-                        is_fully_backward = true;
-                        if self.current_locations.len() != 0 {
-                            panic!("Unexpected locations when the next dependency is going to be attempted.");
+                    let backward_total = self.get_backward_total(self.current_locations.len());
+                    for _ in 0..backward_total {
+                        if !self.cell_group_dependencies[current_cell_group_dependency_index].shifter.try_backward() {
+                            //debug!("done with shifter, so trying next dependency");
+                            // this encapsulated shifter is done, so move onto the next dependency
+                            self.current_cell_group_dependency_index = Some(current_cell_group_dependency_index + 1);
+                            is_current_cell_group_dependency_changed = true;
+                            is_fully_backward = true;
+                            if self.current_locations.len() != 0 {
+                                panic!("Unexpected locations when the next dependency is going to be attempted.");
+                            }
+                            break;
+                        }
+                        else {
+                            //debug!("moved backwards, so popping value to be replaced");
+                            self.pop_current_location();
                         }
-                    }
-                    else {
-                        //debug!("moved backwards, so popping value to be replaced");
-                        self.current_locations.pop();
-                        self.current_element_index_and_adjusted_element_index_and_state_index_tuples.pop();
                     }
                     is_forward_required = false;
                 }
                 else {
                     //debug!("at a valid shift index, so comparing current indexed elements to cached indexed elements");
-                    let current_element_index_and_adjusted_element_index_and_state_index_tuple;
+                    let current_element_index_and_adjusted_element_index_and_state_index_tuple: (usize, usize, usize);
                     {
-                        let current_element_index_and_state_index_pair = shifter.get_element_index_and_state_index();
-                        current_element_index_and_adjusted_element_index_and_state_index_tuple = (current_element_index_and_state_index_pair.0, cell_group_dependency_cell_group_index_mapping[current_element_index_and_state_index_pair.0], current_element_index_and_state_index_pair.1);
+                        let cell_group_dependency = &self.cell_group_dependencies[current_cell_group_dependency_index];
+                        let current_element_index_and_state_index_pair = cell_group_dependency.shifter.get_element_index_and_state_index();
+                        current_element_index_and_adjusted_element_index_and_state_index_tuple = (current_element_index_and_state_index_pair.0, cell_group_dependency.cell_group_index_mapping[current_element_index_and_state_index_pair.0], current_element_index_and_state_index_pair.1);
                     }
                     let current_index_element_location = self.current_states[current_element_index_and_adjusted_element_index_and_state_index_tuple.2].clone();
 
-                    let is_current_indexed_element_valid: bool;
-                    'is_current_indexed_element_valid: {
-                        for location_index in 0..self.current_locations.len() {
-                            let other_element_index_and_adjusted_element_index_and_state_index_tuple = &self.current_element_index_and_adjusted_element_index_and_state_index_tuples[location_index];
-
-                            let bitvec_index: usize;
-                            {
-                                let first_element_index: usize;
-                                let second_element_index: usize;
-                                let first_state_index: usize;
-                                let second_state_index: usize;
-                                if other_element_index_and_adjusted_element_index_and_state_index_tuple.0 < current_element_index_and_adjusted_element_index_and_state_index_tuple.0 {
-                                    first_element_index = other_element_index_and_adjusted_element_index_and_state_index_tuple.0;
-                                    second_element_index = current_element_index_and_adjusted_element_index_and_state_index_tuple.0;
-                                    first_state_index = other_element_index_and_adjusted_element_index_and_state_index_tuple.2;
-                                    second_state_index = current_element_index_and_adjusted_element_index_and_state_index_tuple.2;
-                                }
-                                else {
-                                    first_element_index = current_element_index_and_adjusted_element_index_and_state_index_tuple.0;
-                                    second_element_index = other_element_index_and_adjusted_element_index_and_state_index_tuple.0;
-                                    first_state_index = current_element_index_and_adjusted_element_index_and_state_index_tuple.2;
-                                    second_state_index = other_element_index_and_adjusted_element_index_and_state_index_tuple.2;
-                                }
-                                bitvec_index = ((second_state_index * self.current_states_total + first_state_index) * self.current_elements_total + second_element_index) * self.current_elements_total + first_element_index;
-                                //println!("ShiftingCellGroupDependency: try_increment: bitvec_index: {bitvec_index}");
+                    let current_location_index = self.current_locations.len();
+                    let mut is_current_indexed_element_valid: bool = self.is_state_index_remaining(current_element_index_and_adjusted_element_index_and_state_index_tuple.0, current_element_index_and_adjusted_element_index_and_state_index_tuple.2);
+                    if !is_current_indexed_element_valid {
+                        self.add_conflicting_location_indexes_for_removed_state_index(current_location_index, current_element_index_and_adjusted_element_index_and_state_index_tuple.0, current_element_index_and_adjusted_element_index_and_state_index_tuple.2);
+                    }
+                    else {
+                        for location_index in 0..current_location_index {
+                            let other_element_index_and_adjusted_element_index_and_state_index_tuple = self.current_element_index_and_adjusted_element_index_and_state_index_tuples[location_index];
+                            if !self.is_pair_valid(current_element_index_and_adjusted_element_index_and_state_index_tuple, other_element_index_and_adjusted_element_index_and_state_index_tuple) {
+                                self.add_conflicting_location_indexes(current_location_index, location_index..(location_index + 1));
+                                is_current_indexed_element_valid = false;
+                                break;
                             }
-
-                            // check if the pair of indexed elements have already been compared
-                            if !self.current_is_checked[bitvec_index] {
-                                //debug!("checking cell group {} against {}", current_element_index_and_adjusted_element_index_and_state_index_tuple.1, other_element_index_and_adjusted_element_index_and_state_index_tuple.1);
-                                let other_index_element_location = self.current_states[other_element_index_and_adjusted_element_index_and_state_index_tuple.2].clone();
-
-                                // verify that the pair of indexed elements are valid at the same time and location
-                                let mut is_current_pair_valid = true;
-                                'is_current_pair_valid: {
-                                    let other_cell_group = &self.cell_groups[other_element_index_and_adjusted_element_index_and_state_index_tuple.1];
-                                    let current_cell_group = &self.cell_groups[current_element_index_and_adjusted_element_index_and_state_index_tuple.1];
-
-                                    let is_adjacency_expected_option: Option<bool>;
-                                    if let Some(is_adjacent_cell_group_index_per_cell_group_index) = &self.is_adjacent_cell_group_index_per_cell_group_index {
-                                        is_adjacency_expected_option = Some(is_adjacent_cell_group_index_per_cell_group_index[current_element_index_and_adjusted_element_index_and_state_index_tuple.1][other_element_index_and_adjusted_element_index_and_state_index_tuple.1] || is_adjacent_cell_group_index_per_cell_group_index[other_element_index_and_adjusted_element_index_and_state_index_tuple.1][current_element_index_and_adjusted_element_index_and_state_index_tuple.1]);
-                                        //debug!("is_adjacency_expected: {:?} for cell group {} and {}.", is_adjacency_expected_option, other_element_index_and_adjusted_element_index_and_state_index_tuple.1, current_element_index_and_adjusted_element_index_and_state_index_tuple.1);
-                                    }
-                                    else {
-                                        is_adjacency_expected_option = None;
-                                        //debug!("adjacency is not considered");
-                                    }
-                                    let mut is_adjacent: bool = false;
-
-                                    let mut detection_locations: BTreeSet<(u8, u8)> = BTreeSet::new();
-                                    if let Some(detection_offsets_per_cell_group_index_per_cell_group_index) = &self.detection_offsets_per_cell_group_index_per_cell_group_index {
-                                        for detection_offset in detection_offsets_per_cell_group_index_per_cell_group_index[current_element_index_and_adjusted_element_index_and_state_index_tuple.1][other_element_index_and_adjusted_element_index_and_state_index_tuple.1].iter() {
-                                            let detection_location_i16 = (detection_offset.0 + current_index_element_location.0 as i16, detection_offset.1 + current_index_element_location.1 as i16);
-                                            if detection_location_i16.0 >= 0 && detection_location_i16.0 < 256 && detection_location_i16.1 >= 0 && detection_location_i16.1 < 256 {
-                                                let detection_location = (detection_location_i16.0 as u8, detection_location_i16.1 as u8);
-                                                detection_locations.insert(detection_location);
-                                            }
-                                        }
-                                    }
-
-                                    // check for overlap and adjacency
-                                    for other_cell in other_cell_group.cells.iter() {
-                                        let calculated_other_cell: (u8, u8) = (other_cell.0 + other_index_element_location.0, other_cell.1 + other_index_element_location.1);
-                                        for current_cell in current_cell_group.cells.iter() {
-                                            let calculated_current_cell: (u8, u8) = (current_cell.0 + current_index_element_location.0, current_cell.1 + current_index_element_location.1);
-                                            if calculated_other_cell == calculated_current_cell {
-                                                //debug!("found overlap at ({}, {})", calculated_current_cell.0, calculated_current_cell.1);
-                                                is_current_pair_valid = false;
-                                                break 'is_current_pair_valid;
-                                            }
-                                            else if !is_adjacent {
-                                                let x_diff = calculated_other_cell.0.abs_diff(calculated_current_cell.0);
-                                                let y_diff = calculated_other_cell.1.abs_diff(calculated_current_cell.1);
-                                                if (x_diff == 1 && y_diff == 0) || (x_diff == 0 && y_diff == 1) {
-                                                    is_adjacent = true;
-                                                }
-                                            }
-                                        }
-                                        if detection_locations.contains(&calculated_other_cell) {
-                                            //debug!("found detection location at ({}, {})", calculated_other_cell.0, calculated_other_cell.1);
-                                            is_current_pair_valid = false;
-                                            break 'is_current_pair_valid;
-                                        }
-                                    }
-
-                                    if let Some(is_adjacency_expected) = is_adjacency_expected_option {
-                                        if is_adjacency_expected && !is_adjacent {
-                                            //debug!("adjacency expected and found");
-                                            is_current_pair_valid = false;
-                                        }
-                                        else if !is_adjacency_expected && is_adjacent {
-                                            //debug!("adjacency not expected and yet found");
-                                            is_current_pair_valid = false;
-                                        }
+                        }
+                    }
+                    if is_current_indexed_element_valid {
+                        if let Some(nogood_cache) = self.nogood_cache_option.as_ref().filter(|nogood_cache| nogood_cache.borrow().has_non_pair_nogoods()) {
+                            // an earlier search may have found this location to be invalid alone or alongside more than one of the earlier locations
+                            let cell_group_index_and_location_tuple = self.get_cell_group_index_and_location_tuple(current_element_index_and_adjusted_element_index_and_state_index_tuple);
+                            let earlier_cell_group_index_and_location_tuples: Vec<(usize, TLocation)> = self.current_locations.iter()
+                                .map(|location| (location.index, *location.element))
+                                .collect();
+                            let nogood_option = nogood_cache.borrow().get_nogood_within(&cell_group_index_and_location_tuple, &earlier_cell_group_index_and_location_tuples);
+                            if let Some(nogood) = nogood_option {
+                                debug!("skipping location found in nogood cache");
+                                for (location_index, earlier_cell_group_index_and_location_tuple) in earlier_cell_group_index_and_location_tuples.iter().enumerate() {
+                                    if nogood.contains(earlier_cell_group_index_and_location_tuple) {
+                                        self.add_conflicting_location_indexes(current_location_index, location_index..(location_index + 1));
                                     }
                                 }
-
-                                self.current_is_checked.set(bitvec_index, true);
-                                self.current_is_valid.set(bitvec_index, is_current_pair_valid);
-                            }
-                            else {
-                                //debug!("already checked cell group {} against {} as {}", current_element_index_and_adjusted_element_index_and_state_index_tuple.1, other_element_index_and_adjusted_element_index_and_state_index_tuple.1, self.current_is_valid[bitvec_index]);
-                            }
-                            if !self.current_is_valid[bitvec_index] {
                                 is_current_indexed_element_valid = false;
-                                break 'is_current_indexed_element_valid;
                             }
                         }
+                    }
+                    if is_current_indexed_element_valid {
                         debug!("cell groups are valid together");
-                        is_current_indexed_element_valid = true;
+                        // the remaining states of later elements may now be found to be invalid, possibly leaving an element without any remaining states
+                        is_current_indexed_element_valid = self.try_filter_remaining_state_indexes(current_element_index_and_adjusted_element_index_and_state_index_tuple);
+                        if !is_current_indexed_element_valid {
+                            // the later element was left without states due to the combination of every location
+                            self.add_conflicting_location_indexes(current_location_index, 0..current_location_index);
+                            let mut cell_group_index_and_location_tuples: Vec<(usize, TLocation)> = self.current_locations.iter()
+                                .map(|location| (location.index, *location.element))
+                                .collect();
+                            cell_group_index_and_location_tuples.push(self.get_cell_group_index_and_location_tuple(current_element_index_and_adjusted_element_index_and_state_index_tuple));
+                            self.add_nogood(cell_group_index_and_location_tuples);
+                        }
                     }
                     if is_current_indexed_element_valid {
                         //debug!("indexed elements are valid together, so storing location and moving forward");
@@ -278,6 +688,7 @@ impl Incrementer for ShiftingCellGroupDependencyIncrementer {
             }
             if self.current_locations.len() == shifter_length {
                 //debug!("collected a valid set of current locations");
+                self.set_all_earlier_location_indexes_conflicting();
                 return true;
             }
             if self.current_locations.len() != 0 {
@@ -288,16 +699,23 @@ impl Incrementer for ShiftingCellGroupDependencyIncrementer {
         debug!("no remaining valid sets of locations");
         return false;
     }
-    fn get(&self) -> Vec<IndexedElement<(u8, u8)>> {
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
         return self.current_locations.clone();
     }
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        visitor(&self.current_locations);
+    }
     fn reset(&mut self) {
-        if self.current_cell_group_dependency_index.is_some() {
-            self.cell_group_dependencies[self.current_cell_group_dependency_index.unwrap()].shifter.reset();
+        if let Some(current_cell_group_dependency_index) = self.current_cell_group_dependency_index {
+            // the index is past the last dependency once every dependency has been incremented over
+            if current_cell_group_dependency_index < self.cell_group_dependencies.len() {
+                self.cell_group_dependencies[current_cell_group_dependency_index].shifter.reset();
+            }
         }
         self.current_cell_group_dependency_index = None;
         self.current_locations.clear();
         self.current_element_index_and_adjusted_element_index_and_state_index_tuples.clear();
+        self.current_removed_element_index_and_state_index_tuples_per_location_index.clear();
     }
     fn randomize(&mut self) {
         for cell_group_dependency in self.cell_group_dependencies.iter_mut() {
@@ -307,22 +725,11 @@ impl Incrementer for ShiftingCellGroupDependencyIncrementer {
     }
 }
 
-impl Iterator for ShiftingCellGroupDependencyIncrementer {
-    type Item = Vec<IndexedElement<(u8, u8)>>;
-
-    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
-        if self.try_increment() {
-            return Some(self.get());
-        }
-        return None;
-    }
-}
-
 #[cfg(test)]
 mod shifting_cell_group_dependency_incrementer_tests {
     use std::{time::{Duration, Instant}, cell::RefCell, collections::BTreeSet};
 
-    use crate::shifter::{index_shifter::IndexShifter, shifting_square_breadth_first_search_shifter::ShiftingSquareBreadthFirstSearchShifter};
+    use crate::{VoxelCellGroup, shifter::{index_shifter::IndexShifter, shifting_square_breadth_first_search_shifter::ShiftingSquareBreadthFirstSearchShifter}};
 
     use super::*;
     use bitvec::{bits, vec::BitVec};
@@ -521,7 +928,7 @@ mod shifting_cell_group_dependency_incrementer_tests {
 
         let validating_start_time = Instant::now();
 
-        let indexed_elements_collection = shifting_cell_group_dependency_incrementer.into_iter().collect::<Vec<Vec<IndexedElement<(u8, u8)>>>>();
+        let indexed_elements_collection = shifting_cell_group_dependency_incrementer.into_incrementer_iter().collect::<Vec<Vec<IndexedElement<(u8, u8)>>>>();
 
         println!("validation time: {:?}", validating_start_time.elapsed());
         println!("validated: {:?}", indexed_elements_collection.len());
@@ -758,4 +1165,395 @@ mod shifting_cell_group_dependency_incrementer_tests {
 
         assert_eq!(valid_pixels_total, cells_total);
     }
-}
\ No newline at end of file
+
+    #[rstest]
+    #[case(true, false)]
+    #[case(false, true)]
+    #[case(true, true)]
+    fn domain_filtering_finds_same_locations(#[case] is_forward_checking_enabled: bool, #[case] is_arc_consistency_enabled: bool) {
+        init();
+
+        // four single cells that must form a path where only consecutive cells are adjacent
+        let cell_groups: Rc<Vec<CellGroup>> = Rc::new((0..4).map(|_| CellGroup { cells: vec![(0, 0)] }).collect());
+        let mut is_adjacent_cell_group_index_per_cell_group_index: Vec<BitVec> = Vec::new();
+        for from_cell_group_index in 0..4 {
+            let mut is_adjacent_cell_group_index: BitVec = BitVec::repeat(false, 4);
+            for to_cell_group_index in 0..4 {
+                if from_cell_group_index + 1 == to_cell_group_index || to_cell_group_index + 1 == from_cell_group_index {
+                    is_adjacent_cell_group_index.set(to_cell_group_index, true);
+                }
+            }
+            is_adjacent_cell_group_index_per_cell_group_index.push(is_adjacent_cell_group_index);
+        }
+        let is_adjacent_cell_group_index_per_cell_group_index = Rc::new(is_adjacent_cell_group_index_per_cell_group_index);
+        let get_cell_group_dependencies = || -> Vec<CellGroupDependency> {
+            let mut locations: Vec<Rc<(u8, u8)>> = Vec::new();
+            for y in 0..4 {
+                for x in 0..4 {
+                    locations.push(Rc::new((x, y)));
+                }
+            }
+            // the third cell group may only be in the top-left corner, so the second and fourth cell groups can only be next to it
+            let states_per_shift_index: Vec<Vec<Rc<(u8, u8)>>> = vec![
+                locations.clone(),
+                locations.clone(),
+                vec![Rc::new((0, 0))],
+                locations
+            ];
+            return vec![
+                CellGroupDependency::new(vec![0, 1, 2, 3], Box::new(IndexShifter::new(&states_per_shift_index)))
+            ];
+        };
+
+        let mut expected_shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(
+            cell_groups.clone(),
+            get_cell_group_dependencies(),
+            None,
+            Some(is_adjacent_cell_group_index_per_cell_group_index.clone())
+        );
+        let mut shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(
+            cell_groups,
+            get_cell_group_dependencies(),
+            None,
+            Some(is_adjacent_cell_group_index_per_cell_group_index)
+        );
+        if is_forward_checking_enabled {
+            shifting_cell_group_dependency_incrementer.enable_forward_checking();
+        }
+        if is_arc_consistency_enabled {
+            shifting_cell_group_dependency_incrementer.enable_arc_consistency();
+        }
+        for _ in 0..2 {
+            let mut locations_total = 0;
+            while expected_shifting_cell_group_dependency_incrementer.try_increment() {
+                assert!(shifting_cell_group_dependency_incrementer.try_increment());
+                assert_eq!(expected_shifting_cell_group_dependency_incrementer.get(), shifting_cell_group_dependency_incrementer.get());
+                locations_total += 1;
+            }
+            assert!(!shifting_cell_group_dependency_incrementer.try_increment());
+            // the second cell group is at (1, 0) or (0, 1), the fourth cell group is at the other, and the first cell group continues straight past the second so that it is not next to the fourth
+            assert_eq!(2, locations_total);
+            expected_shifting_cell_group_dependency_incrementer.reset();
+            shifting_cell_group_dependency_incrementer.reset();
+        }
+    }
+
+    #[rstest]
+    #[case(false, false, false)]
+    #[case(true, false, false)]
+    #[case(true, true, false)]
+    #[case(false, false, true)]
+    #[case(true, true, true)]
+    fn conflict_directed_backjumping_finds_same_locations(#[case] is_forward_checking_enabled: bool, #[case] is_arc_consistency_enabled: bool, #[case] is_square_breadth_first_search: bool) {
+        init();
+
+        // five single cells where only the first and last cell groups must be adjacent, so the last cell group only conflicts with the first when it is placed too far away
+        let cell_groups: Rc<Vec<CellGroup>> = Rc::new((0..5).map(|_| CellGroup { cells: vec![(0, 0)] }).collect());
+        let mut is_adjacent_cell_group_index_per_cell_group_index: Vec<BitVec> = Vec::new();
+        for from_cell_group_index in 0..5 {
+            let mut is_adjacent_cell_group_index: BitVec = BitVec::repeat(false, 5);
+            for to_cell_group_index in 0..5 {
+                if (from_cell_group_index == 0 && to_cell_group_index == 4) || (from_cell_group_index == 4 && to_cell_group_index == 0) {
+                    is_adjacent_cell_group_index.set(to_cell_group_index, true);
+                }
+            }
+            is_adjacent_cell_group_index_per_cell_group_index.push(is_adjacent_cell_group_index);
+        }
+        let is_adjacent_cell_group_index_per_cell_group_index = Rc::new(is_adjacent_cell_group_index_per_cell_group_index);
+        let get_cell_group_dependencies = || -> Vec<CellGroupDependency> {
+            let mut locations: Vec<Rc<(u8, u8)>> = Vec::new();
+            for y in 0..6 {
+                for x in 0..6 {
+                    locations.push(Rc::new((x, y)));
+                }
+            }
+            // the last cell group may only be in the top-left corner and the middle cell groups are never next to each other nor next to the top-left corner
+            let states_per_shift_index: Vec<Vec<Rc<(u8, u8)>>> = vec![
+                locations.clone(),
+                vec![Rc::new((3, 1)), Rc::new((2, 2))],
+                vec![Rc::new((1, 3)), Rc::new((3, 3))],
+                vec![Rc::new((4, 0)), Rc::new((0, 4))],
+                vec![Rc::new((0, 0))]
+            ];
+            if is_square_breadth_first_search {
+                // the states of each shift depend on the states of the earlier shifts, so backjumping must not be applied
+                let shifters: Vec<Box<dyn Shifter<T = (u8, u8)>>> = states_per_shift_index.iter()
+                    .map(|states| Box::new(IndexShifter::new(&vec![states.clone()])) as Box<dyn Shifter<T = (u8, u8)>>)
+                    .collect();
+                return vec![
+                    CellGroupDependency::new(vec![0, 1, 2, 3, 4], Box::new(ShiftingSquareBreadthFirstSearchShifter::new(shifters, true)))
+                ];
+            }
+            return vec![
+                CellGroupDependency::new(vec![0, 1, 2, 3, 4], Box::new(IndexShifter::new(&states_per_shift_index)))
+            ];
+        };
+
+        let mut expected_shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(
+            cell_groups.clone(),
+            get_cell_group_dependencies(),
+            None,
+            Some(is_adjacent_cell_group_index_per_cell_group_index.clone())
+        );
+        let mut shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(
+            cell_groups,
+            get_cell_group_dependencies(),
+            None,
+            Some(is_adjacent_cell_group_index_per_cell_group_index)
+        );
+        shifting_cell_group_dependency_incrementer.enable_conflict_directed_backjumping();
+        if is_forward_checking_enabled {
+            shifting_cell_group_dependency_incrementer.enable_forward_checking();
+        }
+        if is_arc_consistency_enabled {
+            shifting_cell_group_dependency_incrementer.enable_arc_consistency();
+        }
+        for _ in 0..2 {
+            let mut locations_total = 0;
+            while expected_shifting_cell_group_dependency_incrementer.try_increment() {
+                assert!(shifting_cell_group_dependency_incrementer.try_increment());
+                assert_eq!(expected_shifting_cell_group_dependency_incrementer.get(), shifting_cell_group_dependency_incrementer.get());
+                locations_total += 1;
+            }
+            assert!(!shifting_cell_group_dependency_incrementer.try_increment());
+            // the first cell group is at (1, 0) or (0, 1) and each of the three middle cell groups is at either of its two locations
+            assert_eq!(2 * 8, locations_total);
+            expected_shifting_cell_group_dependency_incrementer.reset();
+            shifting_cell_group_dependency_incrementer.reset();
+        }
+    }
+
+    #[rstest]
+    fn nogood_cache_prunes_later_incrementers() {
+        init();
+
+        // the first cell group must be next to the last cell group, which may only be in the top-left corner
+        let cell_groups: Rc<Vec<CellGroup>> = Rc::new((0..3).map(|_| CellGroup { cells: vec![(0, 0)] }).collect());
+        let mut is_adjacent_cell_group_index_per_cell_group_index: Vec<BitVec> = (0..3).map(|_| BitVec::repeat(false, 3)).collect();
+        is_adjacent_cell_group_index_per_cell_group_index[0].set(2, true);
+        is_adjacent_cell_group_index_per_cell_group_index[2].set(0, true);
+        let is_adjacent_cell_group_index_per_cell_group_index = Rc::new(is_adjacent_cell_group_index_per_cell_group_index);
+        let get_cell_group_dependencies = || -> Vec<CellGroupDependency> {
+            let mut locations: Vec<Rc<(u8, u8)>> = Vec::new();
+            for y in 0..4 {
+                for x in 0..4 {
+                    locations.push(Rc::new((x, y)));
+                }
+            }
+            let states_per_shift_index: Vec<Vec<Rc<(u8, u8)>>> = vec![
+                locations,
+                vec![Rc::new((3, 3)), Rc::new((3, 1))],
+                vec![Rc::new((0, 0))]
+            ];
+            return vec![
+                CellGroupDependency::new(vec![0, 1, 2], Box::new(IndexShifter::new(&states_per_shift_index)))
+            ];
+        };
+        let nogood_cache = Rc::new(RefCell::new(NogoodCache::new(100)));
+
+        let mut expected_shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(
+            cell_groups.clone(),
+            get_cell_group_dependencies(),
+            None,
+            Some(is_adjacent_cell_group_index_per_cell_group_index.clone())
+        );
+        let mut learning_shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(
+            cell_groups.clone(),
+            get_cell_group_dependencies(),
+            None,
+            Some(is_adjacent_cell_group_index_per_cell_group_index.clone())
+        );
+        learning_shifting_cell_group_dependency_incrementer.enable_conflict_directed_backjumping();
+        learning_shifting_cell_group_dependency_incrementer.set_nogood_cache(nogood_cache.clone());
+        let mut pruned_shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(
+            cell_groups,
+            get_cell_group_dependencies(),
+            None,
+            Some(is_adjacent_cell_group_index_per_cell_group_index)
+        );
+        pruned_shifting_cell_group_dependency_incrementer.set_nogood_cache(nogood_cache.clone());
+
+        let mut expected_locations: Vec<Vec<IndexedElement<(u8, u8)>>> = Vec::new();
+        while expected_shifting_cell_group_dependency_incrementer.try_increment() {
+            expected_locations.push(expected_shifting_cell_group_dependency_incrementer.get());
+        }
+        // the first cell group is at (1, 0) or (0, 1) and the second cell group is at either of its locations
+        assert_eq!(4, expected_locations.len());
+        let learned_locations: Vec<Vec<IndexedElement<(u8, u8)>>> = learning_shifting_cell_group_dependency_incrementer.into_incrementer_iter().collect();
+        assert_eq!(expected_locations, learned_locations);
+
+        // the first cell group being far from the top-left corner was learned to leave no location for the last cell group
+        assert!(nogood_cache.borrow().contains(&vec![(0, (3, 0))]));
+        assert!(nogood_cache.borrow().contains(&vec![(0, (0, 0)), (2, (0, 0))]));
+        let pruned_locations: Vec<Vec<IndexedElement<(u8, u8)>>> = pruned_shifting_cell_group_dependency_incrementer.into_incrementer_iter().collect();
+        assert_eq!(expected_locations, pruned_locations);
+    }
+
+    #[rstest]
+    fn one_voxel_cell_group_zero_dependencies() {
+        init();
+
+        let cell_groups: Rc<Vec<VoxelCellGroup>> = Rc::new(vec![
+            VoxelCellGroup {
+                cells: vec![(0, 0, 0)]
+            }
+        ]);
+        let mut shifting_voxel_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new_with_neighborhood(
+            cell_groups,
+            Vec::new(),
+            None,
+            None,
+            VoxelNeighborhood::Face
+        );
+        for _ in 0..10 {
+            assert!(!shifting_voxel_cell_group_dependency_incrementer.try_increment());
+        }
+    }
+
+    #[rstest]
+    fn two_voxel_cell_groups_cannot_overlap() {
+        init();
+
+        let cell_groups: Rc<Vec<VoxelCellGroup>> = Rc::new(vec![
+            VoxelCellGroup {
+                cells: vec![(0, 0, 0)]
+            },
+            VoxelCellGroup {
+                cells: vec![(0, 0, 0)]
+            }
+        ]);
+        let states: Vec<Rc<(u8, u8, u8)>> = vec![
+            Rc::new((0, 0, 0)),
+            Rc::new((0, 0, 1))
+        ];
+        let shifters: Vec<Box<dyn Shifter<T = (u8, u8, u8)>>> = vec![
+            Box::new(IndexShifter::new(&vec![states.clone()])),
+            Box::new(IndexShifter::new(&vec![states.clone()]))
+        ];
+        let cell_group_dependencies = vec![
+            CellGroupDependency::new(vec![0, 1], Box::new(ShiftingSquareBreadthFirstSearchShifter::new(shifters, true)))
+        ];
+        let mut shifting_voxel_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new_with_neighborhood(
+            cell_groups,
+            cell_group_dependencies,
+            None,
+            None,
+            VoxelNeighborhood::Face
+        );
+        let mut found_location_pairs: Vec<((u8, u8, u8), (u8, u8, u8))> = Vec::new();
+        while shifting_voxel_cell_group_dependency_incrementer.try_increment() {
+            let indexed_elements = shifting_voxel_cell_group_dependency_incrementer.get();
+            assert_eq!(2, indexed_elements.len());
+            found_location_pairs.push((*indexed_elements[0].element, *indexed_elements[1].element));
+        }
+        found_location_pairs.sort();
+        assert_eq!(vec![((0, 0, 0), (0, 0, 1)), ((0, 0, 1), (0, 0, 0))], found_location_pairs);
+    }
+
+    #[rstest]
+    fn two_voxel_cell_groups_must_remain_adjacent_across_depth() {
+        init();
+
+        let cell_groups: Rc<Vec<VoxelCellGroup>> = Rc::new(vec![
+            VoxelCellGroup {
+                cells: vec![(0, 0, 0)]
+            },
+            VoxelCellGroup {
+                cells: vec![(0, 0, 0)]
+            }
+        ]);
+        let first_states: Vec<Rc<(u8, u8, u8)>> = vec![
+            Rc::new((1, 1, 1))
+        ];
+        let second_states: Vec<Rc<(u8, u8, u8)>> = vec![
+            Rc::new((1, 1, 3)),
+            Rc::new((1, 1, 2)),
+            Rc::new((2, 2, 1))
+        ];
+        let shifters: Vec<Box<dyn Shifter<T = (u8, u8, u8)>>> = vec![
+            Box::new(IndexShifter::new(&vec![first_states])),
+            Box::new(IndexShifter::new(&vec![second_states]))
+        ];
+        let cell_group_dependencies = vec![
+            CellGroupDependency::new(vec![0, 1], Box::new(ShiftingSquareBreadthFirstSearchShifter::new(shifters, true)))
+        ];
+        let mut is_adjacent_per_cell_group_index: BitVec = BitVec::repeat(false, 2);
+        is_adjacent_per_cell_group_index.set(1, true);
+        let mut shifting_voxel_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new_with_neighborhood(
+            cell_groups,
+            cell_group_dependencies,
+            None,
+            Some(Rc::new(vec![is_adjacent_per_cell_group_index, BitVec::repeat(false, 2)])),
+            VoxelNeighborhood::Face
+        );
+        assert!(shifting_voxel_cell_group_dependency_incrementer.try_increment());
+        let indexed_elements = shifting_voxel_cell_group_dependency_incrementer.get();
+        let second_location = indexed_elements.iter().find(|indexed_element| indexed_element.index == 1).unwrap().element.clone();
+        assert_eq!((1, 1, 2), *second_location);
+        assert!(!shifting_voxel_cell_group_dependency_incrementer.try_increment());
+    }
+
+    #[rstest]
+    #[case(false, false, false)]
+    #[case(true, false, false)]
+    #[case(true, true, false)]
+    #[case(false, false, true)]
+    #[case(true, true, true)]
+    fn voxel_cell_groups_support_each_search_option(#[case] is_forward_checking_enabled: bool, #[case] is_arc_consistency_enabled: bool, #[case] is_conflict_directed_backjumping_enabled: bool) {
+        init();
+
+        // three single voxels where the middle voxel is fixed at the origin and the other voxels must each touch a face of it without touching each other
+        let cell_groups: Rc<Vec<VoxelCellGroup>> = Rc::new((0..3).map(|_| VoxelCellGroup { cells: vec![(0, 0, 0)] }).collect());
+        let mut is_adjacent_per_cell_group_index: BitVec = BitVec::repeat(false, 3);
+        is_adjacent_per_cell_group_index.set(1, true);
+        let is_adjacent_cell_group_index_per_cell_group_index = Rc::new(vec![
+            is_adjacent_per_cell_group_index.clone(),
+            BitVec::repeat(false, 3),
+            is_adjacent_per_cell_group_index
+        ]);
+        let mut locations: Vec<Rc<(u8, u8, u8)>> = Vec::new();
+        for z in 0..3 {
+            for y in 0..3 {
+                for x in 0..3 {
+                    locations.push(Rc::new((x, y, z)));
+                }
+            }
+        }
+        let states_per_shift_index: Vec<Vec<Rc<(u8, u8, u8)>>> = vec![
+            locations.clone(),
+            vec![Rc::new((0, 0, 0))],
+            locations
+        ];
+        let mut shifting_voxel_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new_with_neighborhood(
+            cell_groups,
+            vec![CellGroupDependency::new(vec![0, 1, 2], Box::new(IndexShifter::new(&states_per_shift_index)))],
+            None,
+            Some(is_adjacent_cell_group_index_per_cell_group_index),
+            VoxelNeighborhood::Face
+        );
+        if is_forward_checking_enabled {
+            shifting_voxel_cell_group_dependency_incrementer.enable_forward_checking();
+        }
+        if is_arc_consistency_enabled {
+            shifting_voxel_cell_group_dependency_incrementer.enable_arc_consistency();
+        }
+        if is_conflict_directed_backjumping_enabled {
+            shifting_voxel_cell_group_dependency_incrementer.enable_conflict_directed_backjumping();
+        }
+        let nogood_cache: Rc<RefCell<NogoodCache<(u8, u8, u8)>>> = Rc::new(RefCell::new(NogoodCache::new(100)));
+        shifting_voxel_cell_group_dependency_incrementer.set_nogood_cache(nogood_cache.clone());
+        for _ in 0..2 {
+            let mut location_pairs: BTreeSet<((u8, u8, u8), (u8, u8, u8))> = BTreeSet::new();
+            while shifting_voxel_cell_group_dependency_incrementer.try_increment() {
+                let indexed_elements = shifting_voxel_cell_group_dependency_incrementer.get();
+                let first_location = *indexed_elements.iter().find(|indexed_element| indexed_element.index == 0).unwrap().element;
+                let third_location = *indexed_elements.iter().find(|indexed_element| indexed_element.index == 2).unwrap().element;
+                assert!(location_pairs.insert((first_location, third_location)));
+            }
+            // the outer voxels take two different faces of the three faces of the origin that are within the bounds
+            assert_eq!(6, location_pairs.len());
+            shifting_voxel_cell_group_dependency_incrementer.reset();
+        }
+        assert!(!nogood_cache.borrow().is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use crate::IndexedElement;
+use super::{Incrementer};
+
+// Purpose:
+//     To skip past the first few increments of the incrementer, starting over with the same number of skipped increments after being reset
+pub struct SkipIncrementer<T> {
+    incrementer: Box<dyn Incrementer<T = T>>,
+    skip_total: usize,
+    is_skipped: bool
+}
+
+impl<T> SkipIncrementer<T> {
+    pub fn new(incrementer: Box<dyn Incrementer<T = T>>, skip_total: usize) -> Self {
+        SkipIncrementer {
+            incrementer: incrementer,
+            skip_total: skip_total,
+            is_skipped: false
+        }
+    }
+}
+
+impl<T> Incrementer for SkipIncrementer<T> {
+    type T = T;
+
+    fn try_increment(&mut self) -> bool {
+        if !self.is_skipped {
+            self.is_skipped = true;
+            for _ in 0..self.skip_total {
+                if !self.incrementer.try_increment() {
+                    return false;
+                }
+            }
+        }
+        return self.incrementer.try_increment();
+    }
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
+        return self.incrementer.get();
+    }
+    fn visit(&self, visitor: &mut dyn FnMut(&[IndexedElement<Self::T>])) {
+        self.incrementer.visit(visitor);
+    }
+    fn get_element_index_and_state_index_pairs(&self) -> Option<&[(usize, usize)]> {
+        return self.incrementer.get_element_index_and_state_index_pairs();
+    }
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return self.incrementer.get_state_table();
+    }
+    fn get_state(&self, state_index: usize) -> Option<&Self::T> {
+        return self.incrementer.get_state(state_index);
+    }
+    fn reset(&mut self) {
+        self.incrementer.reset();
+        self.is_skipped = false;
+    }
+    fn randomize(&mut self) {
+        self.incrementer.randomize();
+    }
+}
+
+#[cfg(test)]
+mod skip_incrementer_tests {
+    use crate::incrementer::binary_value_incrementer::BinaryValueIncrementer;
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(7)]
+    #[case(8)]
+    #[case(20)]
+    fn skips_first_increments(#[case] skip_total: usize) {
+        init();
+
+        let mut expected_increments: Vec<Vec<IndexedElement<bool>>> = BinaryValueIncrementer::new(3).into_incrementer_iter().collect();
+        assert_eq!(8, expected_increments.len());
+        expected_increments.drain(..skip_total.min(expected_increments.len()));
+        let mut skip_incrementer = SkipIncrementer::new(Box::new(BinaryValueIncrementer::new(3)), skip_total);
+        for _ in 0..2 {
+            let increments: Vec<Vec<IndexedElement<bool>>> = skip_incrementer.iter().collect();
+            assert_eq!(expected_increments, increments);
+            assert!(!skip_incrementer.try_increment());
+            skip_incrementer.reset();
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::IndexedElement;
+use super::{Incrementer};
+
+// Purpose:
+//     To increment each incrementer together, returning the indexed elements of every incrementer as one collection
+//     Once any incrementer is unable to increment, the zip is completed
+pub struct ZipIncrementer<T> {
+    incrementers: Vec<Box<dyn Incrementer<T = T>>>,
+    is_completed: bool
+}
+
+impl<T> ZipIncrementer<T> {
+    pub fn new(incrementers: Vec<Box<dyn Incrementer<T = T>>>) -> Self {
+        let is_completed = incrementers.len() == 0;
+        ZipIncrementer {
+            incrementers: incrementers,
+            is_completed: is_completed
+        }
+    }
+}
+
+impl<T> Incrementer for ZipIncrementer<T> {
+    type T = T;
+
+    fn try_increment(&mut self) -> bool {
+        if self.is_completed {
+            return false;
+        }
+        for incrementer in self.incrementers.iter_mut() {
+            if !incrementer.try_increment() {
+                self.is_completed = true;
+                return false;
+            }
+        }
+        return true;
+    }
+    fn get(&self) -> Vec<IndexedElement<Self::T>> {
+        let mut indexed_elements: Vec<IndexedElement<T>> = Vec::new();
+        for incrementer in self.incrementers.iter() {
+            indexed_elements.append(&mut incrementer.get());
+        }
+        return indexed_elements;
+    }
+    fn reset(&mut self) {
+        for incrementer in self.incrementers.iter_mut() {
+            incrementer.reset();
+        }
+        self.is_completed = self.incrementers.len() == 0;
+    }
+    fn randomize(&mut self) {
+        for incrementer in self.incrementers.iter_mut() {
+            incrementer.randomize();
+        }
+    }
+}
+
+#[cfg(test)]
+mod zip_incrementer_tests {
+    use std::rc::Rc;
+
+    use crate::{incrementer::shifter_incrementer::ShifterIncrementer, shifter::index_shifter::IndexShifter};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    fn get_incrementer(states: Vec<u8>) -> Box<dyn Incrementer<T = u8>> {
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![states.into_iter().map(Rc::new).collect()];
+        return Box::new(ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), vec![0]));
+    }
+
+    #[rstest]
+    fn no_incrementers() {
+        init();
+
+        let mut zip_incrementer: ZipIncrementer<u8> = ZipIncrementer::new(Vec::new());
+        for _ in 0..10 {
+            assert!(!zip_incrementer.try_increment());
+        }
+    }
+
+    #[rstest]
+    fn shortest_incrementer_completes_zip() {
+        init();
+
+        let mut zip_incrementer = ZipIncrementer::new(vec![
+            get_incrementer(vec![1, 2, 3]),
+            get_incrementer(vec![4, 5])
+        ]);
+        for _ in 0..3 {
+            let states_per_increment: Vec<Vec<u8>> = zip_incrementer.iter()
+                .map(|indexed_elements| indexed_elements.iter().map(|indexed_element| *indexed_element.element).collect())
+                .collect();
+            assert_eq!(vec![vec![1, 4], vec![2, 5]], states_per_increment);
+            assert!(!zip_incrementer.try_increment());
+            zip_incrementer.reset();
+        }
+    }
+}
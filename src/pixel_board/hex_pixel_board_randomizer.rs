@@ -0,0 +1,414 @@
+use std::{rc::Rc, collections::BTreeSet};
+use bitvec::vec::BitVec;
+use itertools::Itertools;
+use crate::{CellGroup, incrementer::{Incrementer, shifting_cell_group_dependency_incrementer::{CellGroupDependency, CellNeighborhood, Neighborhood, ShiftingCellGroupDependencyIncrementer}}, shifter::{Shifter, index_shifter::IndexShifter, shifting_square_breadth_first_search_shifter::ShiftingSquareBreadthFirstSearchShifter}};
+use super::{HexPixelBoard, Pixel};
+
+// the axial directions in the order that the outer ring is walked, such that each side of the ring is walked along one direction
+const HEX_DIRECTIONS: [(isize, isize); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+// Purpose:
+//      To randomize the cell groups of a HexPixelBoard, where the outer ring of the board is the wall
+//      Wall cell groups along the outer ring that touch one of the six corners of the ring remain where they are
+//      Wall cell groups that sit between two corners shuffle along their side of the ring
+//      Inner cell groups shuffle anywhere within the ring while keeping the same adjacency to the wall cell groups that they started with
+// Note:
+//      Cells are stored as axial coordinates shifted by the radius so that they are never negative
+pub struct HexPixelBoardRandomizer<TPixel: Pixel> {
+    hex_pixel_board: HexPixelBoard<TPixel>,
+    cell_groups: Rc<Vec<CellGroup>>,
+    hex_pixel_board_coordinate_per_cell_group_index: Vec<(u8, u8)>,
+    index_shifter_per_cell_group_index: Vec<IndexShifter<(u8, u8)>>,
+    detection_offsets_per_cell_group_index_per_cell_group_index: Rc<Vec<Vec<Vec<(i16, i16)>>>>,
+    is_adjacent_cell_group_index_per_cell_group_index: Rc<Vec<BitVec>>
+}
+
+impl<TPixel: Pixel> HexPixelBoardRandomizer<TPixel> {
+    pub fn new(hex_pixel_board: HexPixelBoard<TPixel>) -> Self {
+        let radius = hex_pixel_board.get_radius();
+        let radius_isize = radius as isize;
+        let to_cell = |axial_coordinate: (isize, isize)| -> (u8, u8) {
+            ((axial_coordinate.0 + radius_isize) as u8, (axial_coordinate.1 + radius_isize) as u8)
+        };
+        let to_axial_coordinate = |cell: (u8, u8)| -> (isize, isize) {
+            (cell.0 as isize - radius_isize, cell.1 as isize - radius_isize)
+        };
+        let get_distance_from_center = |axial_coordinate: (isize, isize)| -> usize {
+            axial_coordinate.0.abs().max(axial_coordinate.1.abs()).max((axial_coordinate.0 + axial_coordinate.1).abs()) as usize
+        };
+        let get_top_left_cell = |cells: &Vec<(u8, u8)>| -> (u8, u8) {
+            (cells.iter().map(|cell| cell.0).min().unwrap(), cells.iter().map(|cell| cell.1).min().unwrap())
+        };
+
+        // contains the cells of each cell group as they exist on the original board
+        let mut raw_cell_groups: Vec<Vec<(u8, u8)>> = Vec::new();
+        let mut states_per_cell_group_index: Vec<Vec<Rc<(u8, u8)>>> = Vec::new();
+
+        if radius != 0 {
+            // collect the outer ring such that every radius-th cell is a corner
+            let mut ring_axial_coordinates: Vec<(isize, isize)> = Vec::new();
+            let mut current_axial_coordinate = (HEX_DIRECTIONS[4].0 * radius_isize, HEX_DIRECTIONS[4].1 * radius_isize);
+            for direction in HEX_DIRECTIONS.iter() {
+                for _ in 0..radius {
+                    ring_axial_coordinates.push(current_axial_coordinate);
+                    current_axial_coordinate = (current_axial_coordinate.0 + direction.0, current_axial_coordinate.1 + direction.1);
+                }
+            }
+            let ring_length = ring_axial_coordinates.len();
+
+            // construct the wall cell groups from each contiguous run of pixels along the ring
+            let first_gap_ring_index_option = (0..ring_length).find(|ring_index| {
+                let axial_coordinate = ring_axial_coordinates[*ring_index];
+                !hex_pixel_board.exists(axial_coordinate.0, axial_coordinate.1)
+            });
+            if let Some(first_gap_ring_index) = first_gap_ring_index_option {
+                let mut current_run_ring_indexes: Vec<usize> = Vec::new();
+                for ring_index_offset in 1..=ring_length {
+                    let ring_index = (first_gap_ring_index + ring_index_offset) % ring_length;
+                    let axial_coordinate = ring_axial_coordinates[ring_index];
+                    if hex_pixel_board.exists(axial_coordinate.0, axial_coordinate.1) {
+                        current_run_ring_indexes.push(ring_index);
+                    }
+                    else if !current_run_ring_indexes.is_empty() {
+                        let cells: Vec<(u8, u8)> = current_run_ring_indexes.iter()
+                            .map(|ring_index| to_cell(ring_axial_coordinates[*ring_index]))
+                            .collect();
+                        let mut states: Vec<Rc<(u8, u8)>> = Vec::new();
+                        let is_touching_corner = current_run_ring_indexes.iter().any(|ring_index| ring_index % radius == 0);
+                        if is_touching_corner {
+                            // corner walls remain fixed
+                            states.push(Rc::new(get_top_left_cell(&cells)));
+                        }
+                        else {
+                            // the wall segment may slide anywhere between the two corners of its side
+                            let side_index = current_run_ring_indexes[0] / radius;
+                            let run_length = current_run_ring_indexes.len();
+                            for starting_ring_index in (side_index * radius + 1)..=(side_index * radius + radius - run_length) {
+                                let located_cells: Vec<(u8, u8)> = (starting_ring_index..(starting_ring_index + run_length))
+                                    .map(|ring_index| to_cell(ring_axial_coordinates[ring_index]))
+                                    .collect();
+                                states.push(Rc::new(get_top_left_cell(&located_cells)));
+                            }
+                        }
+                        raw_cell_groups.push(cells);
+                        states_per_cell_group_index.push(states);
+                        current_run_ring_indexes.clear();
+                    }
+                }
+            }
+            else {
+                // the entire ring is one wall
+                let cells: Vec<(u8, u8)> = ring_axial_coordinates.iter()
+                    .map(|axial_coordinate| to_cell(*axial_coordinate))
+                    .collect();
+                states_per_cell_group_index.push(vec![Rc::new(get_top_left_cell(&cells))]);
+                raw_cell_groups.push(cells);
+            }
+        }
+
+        // construct the inner cell groups from each connected collection of pixels inside of the ring
+        {
+            let is_inner_pixel = |axial_coordinate: (isize, isize)| -> bool {
+                hex_pixel_board.is_within_bounds(axial_coordinate.0, axial_coordinate.1) &&
+                    get_distance_from_center(axial_coordinate) < radius &&
+                    hex_pixel_board.exists(axial_coordinate.0, axial_coordinate.1)
+            };
+            let mut visited_axial_coordinates: BTreeSet<(isize, isize)> = BTreeSet::new();
+            for r in -radius_isize..=radius_isize {
+                for q in -radius_isize..=radius_isize {
+                    let axial_coordinate = (q, r);
+                    if is_inner_pixel(axial_coordinate) && !visited_axial_coordinates.contains(&axial_coordinate) {
+                        let mut cells: Vec<(u8, u8)> = Vec::new();
+                        let mut possible_axial_coordinates: Vec<(isize, isize)> = vec![axial_coordinate];
+                        visited_axial_coordinates.insert(axial_coordinate);
+                        while let Some(cell_axial_coordinate) = possible_axial_coordinates.pop() {
+                            cells.push(to_cell(cell_axial_coordinate));
+                            for direction in HEX_DIRECTIONS.iter() {
+                                let next_axial_coordinate = (cell_axial_coordinate.0 + direction.0, cell_axial_coordinate.1 + direction.1);
+                                if is_inner_pixel(next_axial_coordinate) && !visited_axial_coordinates.contains(&next_axial_coordinate) {
+                                    visited_axial_coordinates.insert(next_axial_coordinate);
+                                    possible_axial_coordinates.push(next_axial_coordinate);
+                                }
+                            }
+                        }
+
+                        // the cell group may exist anywhere that all of its cells remain inside of the ring
+                        let top_left_cell = get_top_left_cell(&cells);
+                        let relative_cells: Vec<(u8, u8)> = cells.iter()
+                            .map(|cell| (cell.0 - top_left_cell.0, cell.1 - top_left_cell.1))
+                            .collect();
+                        let mut states: Vec<Rc<(u8, u8)>> = Vec::new();
+                        for y in 0..=(radius * 2) {
+                            for x in 0..=(radius * 2) {
+                                let is_inside_ring = relative_cells.iter().all(|relative_cell| {
+                                    let located_axial_coordinate = to_axial_coordinate((relative_cell.0 + x as u8, relative_cell.1 + y as u8));
+                                    get_distance_from_center(located_axial_coordinate) < radius
+                                });
+                                if is_inside_ring {
+                                    states.push(Rc::new((x as u8, y as u8)));
+                                }
+                            }
+                        }
+                        raw_cell_groups.push(cells);
+                        states_per_cell_group_index.push(states);
+                    }
+                }
+            }
+        }
+
+        // at this point all cell groups are known
+
+        let mut hex_pixel_board_coordinate_per_cell_group_index: Vec<(u8, u8)> = Vec::new();
+        let mut transformed_cell_groups: Vec<CellGroup> = Vec::new();
+        for raw_cell_group in raw_cell_groups.iter() {
+            let top_left_cell = get_top_left_cell(raw_cell_group);
+            hex_pixel_board_coordinate_per_cell_group_index.push(top_left_cell);
+            transformed_cell_groups.push(CellGroup {
+                cells: raw_cell_group.iter()
+                    .map(|cell| (cell.0 - top_left_cell.0, cell.1 - top_left_cell.1))
+                    .collect()
+            });
+        }
+
+        // cell groups that start adjacent to each other must remain adjacent and all others must never become adjacent
+        let mut is_adjacent_cell_group_index_per_cell_group_index: Vec<BitVec> = Vec::new();
+        for (cell_group_index, raw_cell_group) in raw_cell_groups.iter().enumerate() {
+            let mut is_adjacent_per_cell_group_index: BitVec = BitVec::repeat(false, raw_cell_groups.len());
+            for (other_cell_group_index, other_raw_cell_group) in raw_cell_groups.iter().enumerate() {
+                if other_cell_group_index > cell_group_index {
+                    let is_adjacent = raw_cell_group.iter()
+                        .cartesian_product(other_raw_cell_group.iter())
+                        .any(|(cell, other_cell)| CellNeighborhood::Hexagonal.is_adjacent(cell, other_cell));
+                    is_adjacent_per_cell_group_index.set(other_cell_group_index, is_adjacent);
+                }
+            }
+            is_adjacent_cell_group_index_per_cell_group_index.push(is_adjacent_per_cell_group_index);
+        }
+
+        let mut detection_offsets_per_cell_group_index_per_cell_group_index: Vec<Vec<Vec<(i16, i16)>>> = Vec::new();
+        for (cell_group_index, raw_cell_group) in raw_cell_groups.iter().enumerate() {
+            let hex_pixel_board_coordinate = hex_pixel_board_coordinate_per_cell_group_index[cell_group_index];
+            let mut detection_offsets_per_cell_group_index: Vec<Vec<(i16, i16)>> = Vec::new();
+            for (other_cell_group_index, other_raw_cell_group) in raw_cell_groups.iter().enumerate() {
+                let mut raw_detection_offsets: Vec<(i16, i16)> = Vec::new();
+                if other_cell_group_index != cell_group_index {
+                    for cell in raw_cell_group.iter() {
+                        let axial_coordinate = to_axial_coordinate(*cell);
+                        if let Some(pixel) = hex_pixel_board.get(axial_coordinate.0, axial_coordinate.1) {
+                            let borrowed_pixel: &TPixel = &pixel.borrow();
+                            for other_cell in other_raw_cell_group.iter() {
+                                let other_axial_coordinate = to_axial_coordinate(*other_cell);
+                                if let Some(other_pixel) = hex_pixel_board.get(other_axial_coordinate.0, other_axial_coordinate.1) {
+                                    let borrowed_other_pixel: &TPixel = &other_pixel.borrow();
+                                    for invalid_location_offset in borrowed_pixel.get_invalid_location_offsets_for_other_pixel(borrowed_other_pixel).iter() {
+                                        let x = (cell.0 - hex_pixel_board_coordinate.0) as i16 + invalid_location_offset.0;
+                                        let y = (cell.1 - hex_pixel_board_coordinate.1) as i16 + invalid_location_offset.1;
+                                        raw_detection_offsets.push((x, y));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                detection_offsets_per_cell_group_index.push(raw_detection_offsets.into_iter().unique().collect());
+            }
+            detection_offsets_per_cell_group_index_per_cell_group_index.push(detection_offsets_per_cell_group_index);
+        }
+
+        let index_shifter_per_cell_group_index: Vec<IndexShifter<(u8, u8)>> = states_per_cell_group_index.into_iter()
+            .map(|states| IndexShifter::new_hashed(&vec![states]))
+            .collect();
+
+        HexPixelBoardRandomizer {
+            hex_pixel_board: hex_pixel_board,
+            cell_groups: Rc::new(transformed_cell_groups),
+            hex_pixel_board_coordinate_per_cell_group_index: hex_pixel_board_coordinate_per_cell_group_index,
+            index_shifter_per_cell_group_index: index_shifter_per_cell_group_index,
+            detection_offsets_per_cell_group_index_per_cell_group_index: Rc::new(detection_offsets_per_cell_group_index_per_cell_group_index),
+            is_adjacent_cell_group_index_per_cell_group_index: Rc::new(is_adjacent_cell_group_index_per_cell_group_index)
+        }
+    }
+    pub fn get_random_hex_pixel_board(&self) -> HexPixelBoard<TPixel> {
+        if self.cell_groups.is_empty() {
+            // nothing to randomize
+            return self.hex_pixel_board.clone();
+        }
+
+        let mut shifters: Vec<Box<dyn Shifter<T = (u8, u8)>>> = Vec::new();
+        for index_shifter in self.index_shifter_per_cell_group_index.iter() {
+            let mut shifter = index_shifter.clone();
+            shifter.randomize();
+            shifters.push(Box::new(shifter));
+        }
+        let cell_group_dependency = CellGroupDependency::new((0..self.cell_groups.len()).collect(), Box::new(ShiftingSquareBreadthFirstSearchShifter::new(shifters, true)));
+        let mut shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new_with_neighborhood(
+            self.cell_groups.clone(),
+            vec![cell_group_dependency],
+            Some(self.detection_offsets_per_cell_group_index_per_cell_group_index.clone()),
+            Some(self.is_adjacent_cell_group_index_per_cell_group_index.clone()),
+            CellNeighborhood::Hexagonal
+        );
+        if !shifting_cell_group_dependency_incrementer.try_increment() {
+            panic!("Unexpected failure to find the original placement, let alone a new random one.");
+        }
+
+        let radius = self.hex_pixel_board.get_radius() as isize;
+        let mut random_hex_pixel_board: HexPixelBoard<TPixel> = HexPixelBoard::new(self.hex_pixel_board.get_radius());
+        for indexed_element in shifting_cell_group_dependency_incrementer.get() {
+            let location = *indexed_element.element.as_ref();
+            let hex_pixel_board_coordinate = self.hex_pixel_board_coordinate_per_cell_group_index[indexed_element.index];
+            for cell in self.cell_groups[indexed_element.index].cells.iter() {
+                let calculated_q = (location.0 + cell.0) as isize - radius;
+                let calculated_r = (location.1 + cell.1) as isize - radius;
+                let original_q = (hex_pixel_board_coordinate.0 + cell.0) as isize - radius;
+                let original_r = (hex_pixel_board_coordinate.1 + cell.1) as isize - radius;
+                random_hex_pixel_board.set(calculated_q, calculated_r, self.hex_pixel_board.get(original_q, original_r).unwrap());
+            }
+        }
+        return random_hex_pixel_board;
+    }
+}
+
+#[cfg(test)]
+mod hex_pixel_board_randomizer_tests {
+    use std::{cell::RefCell, collections::BTreeSet};
+
+    use super::*;
+    use rstest::rstest;
+    use uuid::Uuid;
+
+    struct Tile {
+        image_id: String
+    }
+
+    enum ExamplePixel {
+        Tile(Tile)
+    }
+
+    impl Pixel for ExamplePixel {
+        fn get_invalid_location_offsets_for_other_pixel(&self, _other_pixel: &ExamplePixel) -> Vec<(i16, i16)> {
+            return Vec::new();
+        }
+    }
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    fn get_tile_pixel() -> Rc<RefCell<ExamplePixel>> {
+        Rc::new(RefCell::new(ExamplePixel::Tile(Tile {
+            image_id: Uuid::new_v4().to_string()
+        })))
+    }
+
+    fn get_image_id_per_axial_coordinate(hex_pixel_board: &HexPixelBoard<ExamplePixel>) -> Vec<((isize, isize), String)> {
+        let radius = hex_pixel_board.get_radius() as isize;
+        let mut image_id_per_axial_coordinate = Vec::new();
+        for r in -radius..=radius {
+            for q in -radius..=radius {
+                if hex_pixel_board.is_within_bounds(q, r) {
+                    if let Some(pixel) = hex_pixel_board.get(q, r) {
+                        let ExamplePixel::Tile(tile) = &*pixel.borrow();
+                        image_id_per_axial_coordinate.push(((q, r), tile.image_id.clone()));
+                    }
+                }
+            }
+        }
+        return image_id_per_axial_coordinate;
+    }
+
+    #[rstest]
+    fn empty_board_remains_empty() {
+        init();
+
+        let hex_pixel_board: HexPixelBoard<ExamplePixel> = HexPixelBoard::new(2);
+        let hex_pixel_board_randomizer = HexPixelBoardRandomizer::new(hex_pixel_board);
+        for _ in 0..10 {
+            let random_hex_pixel_board = hex_pixel_board_randomizer.get_random_hex_pixel_board();
+            assert!(get_image_id_per_axial_coordinate(&random_hex_pixel_board).is_empty());
+        }
+    }
+
+    #[rstest]
+    fn full_ring_and_center_remain_unchanged() {
+        init();
+
+        let mut hex_pixel_board: HexPixelBoard<ExamplePixel> = HexPixelBoard::new(1);
+        for (q, r) in [(0, 0), (1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)] {
+            hex_pixel_board.set(q, r, get_tile_pixel());
+        }
+        let expected_image_id_per_axial_coordinate = get_image_id_per_axial_coordinate(&hex_pixel_board);
+        let hex_pixel_board_randomizer = HexPixelBoardRandomizer::new(hex_pixel_board);
+        for _ in 0..10 {
+            let random_hex_pixel_board = hex_pixel_board_randomizer.get_random_hex_pixel_board();
+            assert_eq!(expected_image_id_per_axial_coordinate, get_image_id_per_axial_coordinate(&random_hex_pixel_board));
+        }
+    }
+
+    #[rstest]
+    fn corner_wall_remains_fixed() {
+        init();
+
+        let mut hex_pixel_board: HexPixelBoard<ExamplePixel> = HexPixelBoard::new(3);
+        hex_pixel_board.set(3, 0, get_tile_pixel());
+        let expected_image_id_per_axial_coordinate = get_image_id_per_axial_coordinate(&hex_pixel_board);
+        let hex_pixel_board_randomizer = HexPixelBoardRandomizer::new(hex_pixel_board);
+        for _ in 0..10 {
+            let random_hex_pixel_board = hex_pixel_board_randomizer.get_random_hex_pixel_board();
+            assert_eq!(expected_image_id_per_axial_coordinate, get_image_id_per_axial_coordinate(&random_hex_pixel_board));
+        }
+    }
+
+    #[rstest]
+    fn side_wall_slides_between_corners() {
+        init();
+
+        // the side of the ring from corner (3, -3) to corner (3, 0) contains (3, -2) and (3, -1) between the corners
+        let mut hex_pixel_board: HexPixelBoard<ExamplePixel> = HexPixelBoard::new(3);
+        hex_pixel_board.set(3, -2, get_tile_pixel());
+        let hex_pixel_board_randomizer = HexPixelBoardRandomizer::new(hex_pixel_board);
+        let mut found_axial_coordinates: BTreeSet<(isize, isize)> = BTreeSet::new();
+        for _ in 0..50 {
+            let random_hex_pixel_board = hex_pixel_board_randomizer.get_random_hex_pixel_board();
+            let image_id_per_axial_coordinate = get_image_id_per_axial_coordinate(&random_hex_pixel_board);
+            assert_eq!(1, image_id_per_axial_coordinate.len());
+            found_axial_coordinates.insert(image_id_per_axial_coordinate[0].0);
+        }
+        assert_eq!(BTreeSet::from([(3, -2), (3, -1)]), found_axial_coordinates);
+    }
+
+    #[rstest]
+    fn inner_floater_never_touches_wall() {
+        init();
+
+        let mut hex_pixel_board: HexPixelBoard<ExamplePixel> = HexPixelBoard::new(3);
+        let wall_pixel = get_tile_pixel();
+        for (q, r) in [(3, 0), (3, -3), (0, -3), (-3, 0), (-3, 3), (0, 3)] {
+            hex_pixel_board.set(q, r, wall_pixel.clone());
+        }
+        let floater_pixel = get_tile_pixel();
+        hex_pixel_board.set(0, 0, floater_pixel.clone());
+        let hex_pixel_board_randomizer = HexPixelBoardRandomizer::new(hex_pixel_board);
+        let mut found_axial_coordinates: BTreeSet<(isize, isize)> = BTreeSet::new();
+        for _ in 0..50 {
+            let random_hex_pixel_board = hex_pixel_board_randomizer.get_random_hex_pixel_board();
+            let image_id_per_axial_coordinate = get_image_id_per_axial_coordinate(&random_hex_pixel_board);
+            assert_eq!(7, image_id_per_axial_coordinate.len());
+            let ExamplePixel::Tile(floater_tile) = &*floater_pixel.borrow();
+            for (axial_coordinate, image_id) in image_id_per_axial_coordinate.iter() {
+                if image_id == &floater_tile.image_id {
+                    // the six corners are only adjacent to the ring cells at distance two, so the floater cannot be next to one
+                    for corner_axial_coordinate in [(3, 0), (3, -3), (0, -3), (-3, 0), (-3, 3), (0, 3)] {
+                        let q_diff = axial_coordinate.0 - corner_axial_coordinate.0;
+                        let r_diff = axial_coordinate.1 - corner_axial_coordinate.1;
+                        let distance = q_diff.abs().max(r_diff.abs()).max((q_diff + r_diff).abs());
+                        assert!(distance > 1);
+                    }
+                    found_axial_coordinates.insert(*axial_coordinate);
+                }
+            }
+        }
+        assert!(found_axial_coordinates.len() > 1);
+    }
+}
@@ -1,5 +1,6 @@
 use std::{rc::Rc, cell::RefCell};
 pub mod pixel_board_randomizer;
+pub mod hex_pixel_board_randomizer;
 
 pub trait Pixel {
     fn get_invalid_location_offsets_for_other_pixel(&self, other_pixel: &Self) -> Vec<(i16, i16)>;
@@ -52,3 +53,62 @@ impl<T: Pixel> PixelBoard<T> {
         self.height
     }
 }
+
+// Purpose:
+//      To represent a hexagon-shaped board of hex tiles using axial coordinates (q, r) where the center tile is at (0, 0)
+//      Every tile within the radius of the center is a valid location, so a radius of 2 contains 19 tiles
+pub struct HexPixelBoard<T: Pixel> {
+    radius: usize,
+    pixels: Vec<Option<Rc<RefCell<T>>>>
+}
+
+impl<T: Pixel> Clone for HexPixelBoard<T> {
+    fn clone(&self) -> Self {
+        Self {
+            radius: self.radius.clone(),
+            pixels: self.pixels.clone()
+        }
+    }
+}
+
+impl<T: Pixel> HexPixelBoard<T> {
+    pub fn new(radius: usize) -> Self {
+        let mut pixels = Vec::new();
+        let diameter = radius * 2 + 1;
+        for _ in 0..(diameter * diameter) {
+            pixels.push(None);
+        }
+        HexPixelBoard {
+            radius: radius,
+            pixels: pixels
+        }
+    }
+    fn get_index(&self, q: isize, r: isize) -> usize {
+        if !self.is_within_bounds(q, r) {
+            panic!("Unexpected axial coordinate ({}, {}) outside of radius {}.", q, r, self.radius);
+        }
+        let diameter = self.radius * 2 + 1;
+        let x = (q + self.radius as isize) as usize;
+        let y = (r + self.radius as isize) as usize;
+        return y * diameter + x;
+    }
+    pub fn is_within_bounds(&self, q: isize, r: isize) -> bool {
+        let radius = self.radius as isize;
+        return q.abs() <= radius && r.abs() <= radius && (q + r).abs() <= radius;
+    }
+    pub fn set(&mut self, q: isize, r: isize, pixel: Rc<RefCell<T>>) {
+        let index = self.get_index(q, r);
+        let _ = self.pixels[index].insert(pixel);
+    }
+    pub fn exists(&self, q: isize, r: isize) -> bool {
+        let index = self.get_index(q, r);
+        self.pixels[index].is_some()
+    }
+    pub fn get(&self, q: isize, r: isize) -> Option<Rc<RefCell<T>>> {
+        let index = self.get_index(q, r);
+        self.pixels[index].clone()
+    }
+    pub fn get_radius(&self) -> usize {
+        self.radius
+    }
+}
@@ -1,13 +1,36 @@
 use std::{rc::Rc, cell::RefCell, collections::{BTreeSet, BTreeMap, HashMap}};
 use bitvec::vec::BitVec;
 use itertools::Itertools;
-use crate::{CellGroup, incrementer::{round_robin_incrementer::RoundRobinIncrementer, Incrementer, shifting_cell_group_dependency_incrementer::{self, CellGroupDependency, ShiftingCellGroupDependencyIncrementer}, shifter_incrementer::ShifterIncrementer, limited_incrementer::LimitedIncrementer}, shifter::{Shifter, segment_permutation_shifter::{Segment, SegmentPermutationShifter}, index_shifter::IndexShifter, combined_shifter::CombinedShifter, shifting_square_breadth_first_search_shifter::ShiftingSquareBreadthFirstSearchShifter, hyper_graph_cliche_shifter::{StatefulHyperGraphNode, HyperGraphClicheShifter}}};
+use crate::{CellGroup, IndexedElement, incrementer::{round_robin_incrementer::{RoundRobinIncrementer, SchedulingPolicy}, Incrementer, nogood_cache::NogoodCache, shifting_cell_group_dependency_incrementer::{self, CellGroupDependency, ShiftingCellGroupDependencyIncrementer}, shifter_incrementer::ShifterIncrementer, limited_incrementer::LimitedIncrementer}, shifter::{Shifter, ShiftOrdering, segment_permutation_shifter::{Segment, SegmentPermutationShifter}, index_shifter::IndexShifter, combined_shifter::CombinedShifter, shifting_square_breadth_first_search_shifter::ShiftingSquareBreadthFirstSearchShifter, hyper_graph_cliche_shifter::{StatefulHyperGraphNode, HyperGraphClicheShifter}}};
 use super::{PixelBoard, Pixel};
 
 // TODO construct an undirected graph, search the graph starting with one of the newest edges, doing a depth-first search starting with the newest edge, only permitting the next node to be a cell group not yet traveled to and a location not yet traveled to.
 //          add each new edge one at a time, performing the search per new edge.
 
 
+// the number of invalid collections of cell group locations remembered across calls before the oldest are forgotten
+const NOGOOD_CACHE_CAPACITY: usize = 1 << 16;
+
+// Purpose:
+//     The scheduling policies of the RoundRobinIncrementer that can be used by the PixelBoardRandomizer
+//     There is no weighted policy since the incrementers are constructed internally, so there is nothing for the weights to correspond to
+#[derive(Clone, Debug, PartialEq)]
+pub enum PixelBoardSchedulingPolicy {
+    RoundRobin,
+    YieldRate,
+    Priority
+}
+
+impl PixelBoardSchedulingPolicy {
+    fn get_scheduling_policy(&self) -> SchedulingPolicy {
+        return match self {
+            PixelBoardSchedulingPolicy::RoundRobin => SchedulingPolicy::RoundRobin,
+            PixelBoardSchedulingPolicy::YieldRate => SchedulingPolicy::YieldRate,
+            PixelBoardSchedulingPolicy::Priority => SchedulingPolicy::Priority
+        };
+    }
+}
+
 pub struct PixelBoardRandomizer<TPixel: Pixel> {
     pixel_board: PixelBoard<TPixel>,
     cell_groups: Rc<Vec<CellGroup>>,
@@ -32,11 +55,44 @@ pub struct PixelBoardRandomizer<TPixel: Pixel> {
     wall_adjacent_index_shifters: Vec<IndexShifter<(u8, u8)>>,
     detection_offsets_per_cell_group_index_per_cell_group_index: Rc<Vec<Vec<Vec<(i16, i16)>>>>,
     is_adjacent_cell_group_index_per_cell_group_index: Rc<Vec<BitVec>>,
-    is_always_valid_cell_group_index_per_cell_group_index: Rc<Vec<BitVec>>
+    is_always_valid_cell_group_index_per_cell_group_index: Rc<Vec<BitVec>>,
+    nogood_cache: Rc<RefCell<NogoodCache>>,
+    scheduling_policy: PixelBoardSchedulingPolicy
 }
 
 impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
     pub fn new(pixel_board: PixelBoard<TPixel>) -> Self {
+        return Self::new_with_optional_location_weights(pixel_board, None);
+    }
+    /// The location weight function is provided the original pixel board coordinate of the top-left of a cell group and a possible location for that cell group, returning how strongly that location should be preferred
+    pub fn new_with_location_weights(pixel_board: PixelBoard<TPixel>, location_weight: Rc<dyn Fn((usize, usize), (u8, u8)) -> f32>) -> Self {
+        return Self::new_with_optional_location_weights(pixel_board, Some(location_weight));
+    }
+    fn new_with_optional_location_weights(pixel_board: PixelBoard<TPixel>, location_weight_option: Option<Rc<dyn Fn((usize, usize), (u8, u8)) -> f32>>) -> Self {
+        // constructs the wall segment permutation shifter, weighting each segment at each position offset if weights were provided
+        let get_wall_segment_permutation_shifter = |segments: Vec<Rc<Segment>>, segment_cell_group_indexes: &Vec<usize>, pixel_board_coordinate_per_cell_group_index: &Vec<(usize, usize)>, origin: (u8, u8), bounding_length: usize, is_horizontal: bool| -> SegmentPermutationShifter {
+            if let Some(location_weight) = &location_weight_option {
+                let position_weights_per_segment_index: Vec<Vec<f32>> = segment_cell_group_indexes.iter()
+                    .map(|cell_group_index| {
+                        (0..bounding_length)
+                            .map(|position_offset| {
+                                let location: (u8, u8);
+                                if is_horizontal {
+                                    location = (origin.0 + position_offset as u8, origin.1);
+                                }
+                                else {
+                                    location = (origin.0, origin.1 + position_offset as u8);
+                                }
+                                location_weight(pixel_board_coordinate_per_cell_group_index[*cell_group_index], location)
+                            })
+                            .collect()
+                    })
+                    .collect();
+                return SegmentPermutationShifter::new_with_position_weights(segments, origin, bounding_length, is_horizontal, 1, false, position_weights_per_segment_index);
+            }
+            return SegmentPermutationShifter::new(segments, origin, bounding_length, is_horizontal, 1, false);
+        };
+
         let mut raw_cell_groups: Vec<CellGroup> = Vec::new();
         // contains the pixel board coordinates that map to which cell group
         // useful for creating the random pixel board instance, copying the exact TPixel value from this instance at the same cell location + coordinate
@@ -488,7 +544,7 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                     if rightmost_bounding_x.is_none() {
                         panic!("Failed to find right-most x bounding length point for bottom wall segments.");
                     }
-                    let top_wall_segment_permutation_shifter = SegmentPermutationShifter::new(segments, (leftmost_bounding_x.unwrap() as u8, 0), rightmost_bounding_x.unwrap() - leftmost_bounding_x.unwrap() + 1, true, 1, false);
+                    let top_wall_segment_permutation_shifter = get_wall_segment_permutation_shifter(segments, &top_wall_segment_cell_group_indexes, &pixel_board_coordinate_per_cell_group_index, (leftmost_bounding_x.unwrap() as u8, 0), rightmost_bounding_x.unwrap() - leftmost_bounding_x.unwrap() + 1, true);
                     top_wall_segment_permutation_shifter_option = Some(top_wall_segment_permutation_shifter);
                 }
             }
@@ -595,7 +651,7 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                     if rightmost_bounding_x.is_none() {
                         panic!("Failed to find right-most x bounding length point for bottom wall segments.");
                     }
-                    let bottom_wall_segment_permutation_shifter = SegmentPermutationShifter::new(segments, (leftmost_bounding_x.unwrap() as u8, bottommost_y as u8), rightmost_bounding_x.unwrap() - leftmost_bounding_x.unwrap() + 1, true, 1, false);
+                    let bottom_wall_segment_permutation_shifter = get_wall_segment_permutation_shifter(segments, &bottom_wall_segment_cell_group_indexes, &pixel_board_coordinate_per_cell_group_index, (leftmost_bounding_x.unwrap() as u8, bottommost_y as u8), rightmost_bounding_x.unwrap() - leftmost_bounding_x.unwrap() + 1, true);
                     bottom_wall_segment_permutation_shifter_option = Some(bottom_wall_segment_permutation_shifter);
                 }
             }
@@ -700,7 +756,7 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                     if bottommost_bounding_y.is_none() {
                         panic!("Failed to find bottom-most y bounding length point for left wall segments.");
                     }
-                    let left_wall_segment_permutation_shifter = SegmentPermutationShifter::new(segments, (0, topmost_bounding_y.unwrap() as u8), bottommost_bounding_y.unwrap() - topmost_bounding_y.unwrap() + 1, false, 1, false);
+                    let left_wall_segment_permutation_shifter = get_wall_segment_permutation_shifter(segments, &left_wall_segment_cell_group_indexes, &pixel_board_coordinate_per_cell_group_index, (0, topmost_bounding_y.unwrap() as u8), bottommost_bounding_y.unwrap() - topmost_bounding_y.unwrap() + 1, false);
                     left_wall_segment_permutation_shifter_option = Some(left_wall_segment_permutation_shifter);
                 }
             }
@@ -805,7 +861,7 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                     if bottommost_bounding_y.is_none() {
                         panic!("Failed to find bottom-most y bounding length point for right wall segments.");
                     }
-                    let right_wall_segment_permutation_shifter = SegmentPermutationShifter::new(segments, (rightmost_x as u8, topmost_bounding_y.unwrap() as u8), bottommost_bounding_y.unwrap() - topmost_bounding_y.unwrap() + 1, false, 1, false);
+                    let right_wall_segment_permutation_shifter = get_wall_segment_permutation_shifter(segments, &right_wall_segment_cell_group_indexes, &pixel_board_coordinate_per_cell_group_index, (rightmost_x as u8, topmost_bounding_y.unwrap() as u8), bottommost_bounding_y.unwrap() - topmost_bounding_y.unwrap() + 1, false);
                     right_wall_segment_permutation_shifter_option = Some(right_wall_segment_permutation_shifter);
                 }
             }
@@ -991,7 +1047,16 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                                             states.push(location_references[location_reference_index].clone());
                                         }
                                     }
-                                    let index_shifter = IndexShifter::new(&vec![states]);
+                                    let index_shifter: IndexShifter<(u8, u8)>;
+                                    if let Some(location_weight) = &location_weight_option {
+                                        let weights: Vec<f32> = states.iter()
+                                            .map(|state| location_weight((leftmost_cell_group_x, topmost_cell_group_y), *state.as_ref()))
+                                            .collect();
+                                        index_shifter = IndexShifter::new_with_weights(&vec![states], &vec![weights]);
+                                    }
+                                    else {
+                                        index_shifter = IndexShifter::new_hashed(&vec![states]);
+                                    }
                                     wall_adjacent_index_shifters.push(index_shifter);
                                 }
                             }
@@ -1180,197 +1245,468 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
             wall_adjacent_index_shifters: wall_adjacent_index_shifters,
             detection_offsets_per_cell_group_index_per_cell_group_index: Rc::new(detection_offsets_per_cell_group_index_per_cell_group_index),
             is_adjacent_cell_group_index_per_cell_group_index: Rc::new(is_adjacent_cell_group_index_per_cell_group_index),
-            is_always_valid_cell_group_index_per_cell_group_index: Rc::new(is_always_valid_cell_group_index_per_cell_group_index)
+            is_always_valid_cell_group_index_per_cell_group_index: Rc::new(is_always_valid_cell_group_index_per_cell_group_index),
+            nogood_cache: Rc::new(RefCell::new(NogoodCache::new(NOGOOD_CACHE_CAPACITY))),
+            scheduling_policy: PixelBoardSchedulingPolicy::RoundRobin
         }
     }
+    /// Replaces the cache of invalid cell group locations that is shared across each search with an empty cache of the provided capacity, where a capacity of zero disables the cache
+    pub fn set_nogood_cache_capacity(&mut self, capacity: usize) {
+        self.nogood_cache = Rc::new(RefCell::new(NogoodCache::new(capacity)));
+    }
+    /// Sets how the incrementers searching over each cell group dependency take turns, where each yield is a newly connected pair of cell group locations.
+    /// PixelBoardSchedulingPolicy::Priority prefers the incrementers over the most cell groups that do not yet have a location connected to every other cell group, since those are the cell groups keeping a cliche from being found.
+    pub fn set_scheduling_policy(&mut self, scheduling_policy: PixelBoardSchedulingPolicy) {
+        self.scheduling_policy = scheduling_policy;
+    }
     pub fn get_random_pixel_board(&self) -> PixelBoard<TPixel> {
         // the structure that will search over each cell group dependency, collecting valid pairs of cell group locations
         let mut round_robin_incrementer: RoundRobinIncrementer<(u8, u8)>;
-
-        {
-            // randomize the shifters
-            let mut corner_wall_index_shifters: Vec<IndexShifter<(u8, u8)>> = Vec::new();
-            let mut corner_wall_cell_group_index_per_shifter: Vec<usize> = Vec::new();
-            for (shifter_option, cell_group_index_option) in [
-                (self.top_left_corner_wall_index_shifter_option.as_ref(), self.top_left_corner_wall_cell_group_index.as_ref()),
-                (self.top_right_corner_wall_index_shifter_option.as_ref(), self.top_right_corner_wall_cell_group_index.as_ref()),
-                (self.bottom_right_corner_wall_index_shifter_option.as_ref(), self.bottom_right_corner_wall_cell_group_index.as_ref()),
-                (self.bottom_left_corner_wall_index_shifter_option.as_ref(), self.bottom_left_corner_wall_cell_group_index.as_ref())
-            ] {
-                if shifter_option.is_some() {
-                    let mut shifter = shifter_option.unwrap().clone();
-                    shifter.randomize();
-                    corner_wall_index_shifters.push(shifter);
-                    corner_wall_cell_group_index_per_shifter.push(*cell_group_index_option.unwrap());
-                }
-            }
-            let mut wall_segment_permutation_shifters: Vec<SegmentPermutationShifter> = Vec::new();
-            let mut wall_segment_cell_group_indexes_per_shifter: Vec<Vec<usize>> = Vec::new();
-            for (shifter_option, cell_group_indexes) in [
-                (self.top_wall_segment_permutation_shifter_option.as_ref(), self.top_wall_segment_cell_group_indexes.clone()),
-                (self.right_wall_segment_permutation_shifter_option.as_ref(), self.right_wall_segment_cell_group_indexes.clone()),
-                (self.bottom_wall_segment_permutation_shifter_option.as_ref(), self.bottom_wall_segment_cell_group_indexes.clone()),
-                (self.left_wall_segment_permutation_shifter_option.as_ref(), self.left_wall_segment_cell_group_indexes.clone())
-            ] {
-                if shifter_option.is_some() {
-                    let mut shifter = shifter_option.unwrap().clone();
-                    shifter.randomize();
-                    wall_segment_permutation_shifters.push(shifter);
-                    wall_segment_cell_group_indexes_per_shifter.push(cell_group_indexes);
-                }
+        let cell_group_indexes_per_incrementer_index: Vec<Vec<usize>>;
+        match self.get_round_robin_incrementer(true, true) {
+            Some((incrementer, incrementer_cell_group_indexes_per_incrementer_index)) => {
+                round_robin_incrementer = incrementer;
+                cell_group_indexes_per_incrementer_index = incrementer_cell_group_indexes_per_incrementer_index;
+            },
+            None => {
+                // nothing to increment over
+                return self.pixel_board.clone();
             }
-            let mut wall_adjacent_index_shifters: Vec<IndexShifter<(u8, u8)>> = Vec::new();
-            let mut wall_adjacent_cell_group_index_per_shifter: Vec<usize> = Vec::new();
-            for (index_shifter, cell_group_index) in self.wall_adjacent_index_shifters.iter().zip(self.wall_adjacent_cell_group_indexes.iter()) {
-                let mut shifter = index_shifter.clone();
-                shifter.randomize();
-                wall_adjacent_index_shifters.push(shifter);
-                wall_adjacent_cell_group_index_per_shifter.push(*cell_group_index);
-            }
-            
-            // TODO construct each shifting cell group dependency incrementer per pair of shifters
-            let mut incrementers: Vec<Box<dyn Incrementer<T = (u8, u8)>>> = Vec::new();
+        }
 
-            // fill the incrementers that will be used by the round-robin
-            {
-                // this structure contains all of the dependent cell groups
-                let mut cell_group_dependencies: Vec<CellGroupDependency> = Vec::new();
-                // this structure contains all of the independent cell groups, needed at the end to get one valid location per cell group
-                let mut independent_shifter_incrementers: Vec<ShifterIncrementer<(u8, u8)>> = Vec::new();
+        // prepare to find the cycle as the RoundRobinIncrementer is iterated over
+        // the idea is that we round-robin across all shifters, building up graphs of connected locations until we find that the next pair to be connected already exist in the same graph, then we check for a cycle
+        
+        // contains all of the states discovered thus far
+        let mut stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>>> = Vec::new();
+        for _ in 0..self.cell_groups.len() {
+            stateful_hyper_graph_nodes_per_hyper_graph_node_index.push(Vec::new());
+        }
 
-                if corner_wall_index_shifters.len() == 0 && wall_segment_permutation_shifters.len() == 0 && wall_adjacent_index_shifters.len() == 0 {
-                    // nothing to increment over
-                    return self.pixel_board.clone();
-                }
-                else if corner_wall_index_shifters.len() + wall_segment_permutation_shifters.len() + wall_adjacent_index_shifters.len() == 1 {
-                    if corner_wall_index_shifters.len() == 1 {
-                        let cell_group_dependency = CellGroupDependency::new(corner_wall_cell_group_index_per_shifter, Box::new(ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(corner_wall_index_shifters[0].clone())], true)));
-                        cell_group_dependencies.push(cell_group_dependency);
-                    }
-                    else if wall_segment_permutation_shifters.len() == 1 {
-                        let cell_group_dependency = CellGroupDependency::new(wall_segment_cell_group_indexes_per_shifter[0].clone(), Box::new(ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_segment_permutation_shifters[0].clone())], true)));
-                        cell_group_dependencies.push(cell_group_dependency);
-                    }
-                    else if wall_adjacent_index_shifters.len() == 1 {
-                        let cell_group_dependency = CellGroupDependency::new(wall_adjacent_cell_group_index_per_shifter, Box::new(ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_adjacent_index_shifters[0].clone())], true)));
-                        cell_group_dependencies.push(cell_group_dependency);
-                    }
-                    else {
-                        panic!("Unexpected difference between encapsulating if-statement and split if-statements.");
+        // the cliche progress is only tracked when it decides the scheduling
+        let is_prioritized = self.scheduling_policy == PixelBoardSchedulingPolicy::Priority;
+        let mut is_fully_connected_per_cell_group_index: BitVec = BitVec::repeat(false, self.cell_groups.len());
+        if is_prioritized {
+            Self::set_priorities_from_cliche_progress(&mut round_robin_incrementer, &cell_group_indexes_per_incrementer_index, &is_fully_connected_per_cell_group_index);
+        }
+
+        let mut connections_total = 0;
+        let mut focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option: Option<Vec<(usize, usize)>> = None;
+        let mut is_incrementer_completed: bool = false;
+        while !is_incrementer_completed {
+            // TODO get the next set of locations
+            is_incrementer_completed = !round_robin_incrementer.try_increment();
+            if !is_incrementer_completed {
+                debug!("round robin incremented");
+                let previous_connections_total = connections_total;
+                let mut added_focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option: Option<Vec<(usize, usize)>> = None;
+                let mut visited_cell_group_indexes: Vec<usize> = Vec::new();
+                round_robin_incrementer.visit(&mut |locations| {
+                    debug!("found locations: {locations:?}");
+                    added_focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option = self.add_locations_to_stateful_hyper_graph_nodes(locations, &self.is_always_valid_cell_group_index_per_cell_group_index, &mut stateful_hyper_graph_nodes_per_hyper_graph_node_index, &mut connections_total);
+                    if is_prioritized {
+                        visited_cell_group_indexes.extend(locations.iter().map(|location| location.index));
                     }
+                });
+                if added_focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option.is_some() {
+                    focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option = added_focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option;
                 }
-                else {
-
-                    // TODO only create the cell group dependencies that are not always valid
-
-                    let mut dependent_corner_wall_shifter_indexes: Vec<usize> = Vec::new();
-                    let mut dependent_wall_segment_shifter_indexes: Vec<usize> = Vec::new();
-                    let mut dependent_wall_adjacent_shifter_indexes: Vec<usize> = Vec::new();
-
-                    // create a combined shifter per pair of corner wall shifters
-                    if !corner_wall_index_shifters.is_empty() {
-                        for shifter_index in 0..(corner_wall_index_shifters.len() - 1) {
-                            for other_shifter_index in (shifter_index + 1)..corner_wall_index_shifters.len() {
-                                let combined_cell_group_indexes: Vec<usize> = vec![corner_wall_cell_group_index_per_shifter[shifter_index], corner_wall_cell_group_index_per_shifter[other_shifter_index]];
-                                let mut is_combined_cell_groups_dependent = false;
-                                'check_if_combined_is_dependent: {
-                                    for from_combined_cell_group_index in 0..(combined_cell_group_indexes.len() - 1) {
-                                        let from_cell_group_index = combined_cell_group_indexes[from_combined_cell_group_index];
-                                        for to_combined_cell_group_index in (from_combined_cell_group_index + 1)..combined_cell_group_indexes.len() {
-                                            let to_cell_group_index = combined_cell_group_indexes[to_combined_cell_group_index];
-                                            if !self.is_always_valid_cell_group_index_per_cell_group_index[from_cell_group_index][to_cell_group_index] ||
-                                                    !self.is_always_valid_cell_group_index_per_cell_group_index[to_cell_group_index][from_cell_group_index] {
-                                                
-                                                is_combined_cell_groups_dependent = true;
-                                                break 'check_if_combined_is_dependent;
-                                            }
-                                        }
-                                    }
-                                }
-                                if is_combined_cell_groups_dependent {
-                                    dependent_corner_wall_shifter_indexes.push(shifter_index);
-                                    dependent_corner_wall_shifter_indexes.push(other_shifter_index);
-
-                                    let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(corner_wall_index_shifters[shifter_index].clone()), Box::new(corner_wall_index_shifters[other_shifter_index].clone())], true);
-                                    let cell_group_dependency = CellGroupDependency::new(combined_cell_group_indexes, Box::new(shifter));
-                                    cell_group_dependencies.push(cell_group_dependency);
-                                }
+                if connections_total != previous_connections_total {
+                    round_robin_incrementer.record_yield(connections_total - previous_connections_total);
+                    if is_prioritized {
+                        // only the cell groups of the latest locations may have gained a location connected to every other cell group
+                        let mut is_cliche_progressed = false;
+                        for cell_group_index in visited_cell_group_indexes {
+                            if !is_fully_connected_per_cell_group_index[cell_group_index] && stateful_hyper_graph_nodes_per_hyper_graph_node_index[cell_group_index].iter().any(|stateful_hyper_graph_node| stateful_hyper_graph_node.borrow().is_connected_to_all_hyper_graph_nodes()) {
+                                is_fully_connected_per_cell_group_index.set(cell_group_index, true);
+                                is_cliche_progressed = true;
                             }
                         }
+                        if is_cliche_progressed {
+                            Self::set_priorities_from_cliche_progress(&mut round_robin_incrementer, &cell_group_indexes_per_incrementer_index, &is_fully_connected_per_cell_group_index);
+                        }
                     }
-                    // create a combined shifter per pair of segment wall shifters
-                    if !wall_segment_permutation_shifters.is_empty() {
-                        for shifter_index in 0..(wall_segment_permutation_shifters.len() - 1) {
-                            for other_shifter_index in (shifter_index + 1)..wall_segment_permutation_shifters.len() {
-                                let mut combined_cell_group_indexes: Vec<usize> = Vec::new();
-                                for wall_segment_cell_group_index in wall_segment_cell_group_indexes_per_shifter[shifter_index].iter().chain(wall_segment_cell_group_indexes_per_shifter[other_shifter_index].iter()) {
-                                    combined_cell_group_indexes.push(*wall_segment_cell_group_index);
-                                }
-                                let mut is_combined_cell_groups_dependent = false;
-                                'check_if_combined_is_dependent: {
-                                    for from_combined_cell_group_index in 0..(combined_cell_group_indexes.len() - 1) {
-                                        let from_cell_group_index = combined_cell_group_indexes[from_combined_cell_group_index];
-                                        for to_combined_cell_group_index in (from_combined_cell_group_index + 1)..combined_cell_group_indexes.len() {
-                                            let to_cell_group_index = combined_cell_group_indexes[to_combined_cell_group_index];
-                                            if !self.is_always_valid_cell_group_index_per_cell_group_index[from_cell_group_index][to_cell_group_index] ||
-                                                    !self.is_always_valid_cell_group_index_per_cell_group_index[to_cell_group_index][from_cell_group_index] {
-                                                
-                                                is_combined_cell_groups_dependent = true;
-                                                break 'check_if_combined_is_dependent;
-                                            }
-                                        }
-                                    }
-                                }
-                                if is_combined_cell_groups_dependent {
-                                    dependent_wall_segment_shifter_indexes.push(shifter_index);
-                                    dependent_wall_segment_shifter_indexes.push(other_shifter_index);
+                }
 
-                                    let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_segment_permutation_shifters[shifter_index].clone()), Box::new(wall_segment_permutation_shifters[other_shifter_index].clone())], true);
-                                    let cell_group_dependency = CellGroupDependency::new(combined_cell_group_indexes, Box::new(shifter));
-                                    cell_group_dependencies.push(cell_group_dependency);
-                                }
+                {
+                    // check to see if it is worth looking for the cliche
+                    let mut is_all_focused_stateful_hyper_graph_nodes_fully_connected = true;
+                    if let Some(focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples) = &focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option {
+                        for (stateful_hyper_graph_node_index, hyper_graph_node_index) in focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples.iter() {
+                            if !stateful_hyper_graph_nodes_per_hyper_graph_node_index[*hyper_graph_node_index][*stateful_hyper_graph_node_index].borrow().is_connected_to_all_hyper_graph_nodes() {
+                                is_all_focused_stateful_hyper_graph_nodes_fully_connected = false;
+                                break;
                             }
                         }
                     }
-                    // create a combined shifter per pair of non-wall shifters
-                    if !wall_adjacent_index_shifters.is_empty() {
-                        for shifter_index in 0..(wall_adjacent_index_shifters.len() - 1) {
-                            for other_shifter_index in (shifter_index + 1)..wall_adjacent_index_shifters.len() {
-                                let combined_cell_group_indexes: Vec<usize> = vec![wall_adjacent_cell_group_index_per_shifter[shifter_index], wall_adjacent_cell_group_index_per_shifter[other_shifter_index]];
-                                let mut is_combined_cell_groups_dependent = false;
-                                'check_if_combined_is_dependent: {
-                                    for from_combined_cell_group_index in 0..(combined_cell_group_indexes.len() - 1) {
-                                        let from_cell_group_index = combined_cell_group_indexes[from_combined_cell_group_index];
-                                        for to_combined_cell_group_index in (from_combined_cell_group_index + 1)..combined_cell_group_indexes.len() {
-                                            let to_cell_group_index = combined_cell_group_indexes[to_combined_cell_group_index];
-                                            if !self.is_always_valid_cell_group_index_per_cell_group_index[from_cell_group_index][to_cell_group_index] ||
-                                                    !self.is_always_valid_cell_group_index_per_cell_group_index[to_cell_group_index][from_cell_group_index] {
-                                                
-                                                is_combined_cell_groups_dependent = true;
-                                                break 'check_if_combined_is_dependent;
-                                            }
-                                        }
-                                    }
-                                }
-                                if is_combined_cell_groups_dependent {
-                                    dependent_wall_adjacent_shifter_indexes.push(shifter_index);
-                                    dependent_wall_adjacent_shifter_indexes.push(other_shifter_index);
+                    if is_all_focused_stateful_hyper_graph_nodes_fully_connected {
 
-                                    let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_adjacent_index_shifters[shifter_index].clone()), Box::new(wall_adjacent_index_shifters[other_shifter_index].clone())], true);
-                                    let cell_group_dependency = CellGroupDependency::new(combined_cell_group_indexes, Box::new(shifter));
-                                    cell_group_dependencies.push(cell_group_dependency);
-                                }
+                        // look for cliches given the stateful hyper graph nodes of the latest set of provided location pairs
+                        let mut hyper_graph_cliche_shifter = HyperGraphClicheShifter::new_hashed_with_islands(stateful_hyper_graph_nodes_per_hyper_graph_node_index.clone(), self.is_always_valid_cell_group_index_per_cell_group_index.clone());
+                        if let Some(focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples) = &focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option {
+                            hyper_graph_cliche_shifter.focus_on_neighbors(focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples.clone());
+                        }
+                        // search the cell groups with the fewest possible locations first so that dead ends are found sooner
+                        hyper_graph_cliche_shifter.set_shift_ordering(ShiftOrdering::MinimumRemainingValues);
+
+                        {
+                            let mut shifter_incrementer = ShifterIncrementer::new(Box::new(hyper_graph_cliche_shifter), (0..stateful_hyper_graph_nodes_per_hyper_graph_node_index.len()).collect());
+                            if shifter_incrementer.try_increment() {
+                                // found cliche
+                                debug!("cliche found");
+                                return self.get_pixel_board_from_incrementer(&shifter_incrementer);
+                            }
+                            else {
+                                debug!("cliche not found");
                             }
                         }
                     }
-                    // create a combined shifter per corner wall shifter and segment wall shifter pair
-                    for corner_wall_shifter_index in 0..corner_wall_index_shifters.len() {
-                        for wall_segment_shifter_index in 0..wall_segment_permutation_shifters.len() {
-                            // TODO refactor into idomatic vector concat
-                            let mut combined_cell_group_indexes: Vec<usize> = vec![corner_wall_cell_group_index_per_shifter[corner_wall_shifter_index]];
-                            for wall_segment_cell_group_index in wall_segment_cell_group_indexes_per_shifter[wall_segment_shifter_index].iter() {
-                                combined_cell_group_indexes.push(*wall_segment_cell_group_index);
-                            }
+                }
+            }
+            else {
+                debug!("round robin done incrementing");
+            }
+        }
+
+        panic!("Unexpected failure to find the original placement, let alone a new random one.");
+    }
+    /// Returns a pixel board chosen uniformly at random from every valid cliche, where a cliche is one location per cell group such that every pair of cell groups is valid.
+    /// Unlike get_random_pixel_board, which returns the first valid cliche found from randomized starting locations, this visits each valid cliche once and keeps one of them by reservoir sampling, so it is slower but unbiased while only keeping a single cliche in memory.
+    /// The distribution is uniform over cliches rather than over distinct pixel boards: if cell groups with the same shape and pixels may trade locations, the resulting pixel board is returned once per way that they can be traded, which is only the same as being uniform over distinct pixel boards when every pixel board can be reached in the same number of ways.
+    pub fn get_uniformly_random_pixel_board(&self) -> PixelBoard<TPixel> {
+        let stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>>>;
+        let is_always_valid_cell_group_index_per_cell_group_index: Rc<Vec<BitVec>>;
+        match self.get_all_stateful_hyper_graph_nodes(false) {
+            Some((all_stateful_hyper_graph_nodes_per_hyper_graph_node_index, all_is_always_valid_cell_group_index_per_cell_group_index)) => {
+                stateful_hyper_graph_nodes_per_hyper_graph_node_index = all_stateful_hyper_graph_nodes_per_hyper_graph_node_index;
+                is_always_valid_cell_group_index_per_cell_group_index = all_is_always_valid_cell_group_index_per_cell_group_index;
+            },
+            None => {
+                // nothing to increment over
+                return self.pixel_board.clone();
+            }
+        }
+
+        // keep each cliche in place of the previously kept cliche with a chance of one over the number of cliches found so far
+        let mut locations: Vec<(usize, (u8, u8))> = Vec::new();
+        let mut cliches_total: usize = 0;
+        {
+            let hyper_graph_cliche_shifter = HyperGraphClicheShifter::new_hashed_with_islands(stateful_hyper_graph_nodes_per_hyper_graph_node_index, is_always_valid_cell_group_index_per_cell_group_index);
+            let mut shifter_incrementer = ShifterIncrementer::new(Box::new(hyper_graph_cliche_shifter), (0..self.cell_groups.len()).collect());
+            while shifter_incrementer.try_increment() {
+                cliches_total += 1;
+                if fastrand::usize(0..cliches_total) == 0 {
+                    locations.clear();
+                    for (cell_group_index, state_index) in shifter_incrementer.get_element_index_and_state_index_pairs().unwrap().iter() {
+                        locations.push((*cell_group_index, *shifter_incrementer.get_state(*state_index).unwrap()));
+                    }
+                }
+            }
+        }
+        debug!("sampled from {cliches_total} cliches");
+
+        if cliches_total == 0 {
+            panic!("Unexpected failure to find the original placement, let alone a new random one.");
+        }
+
+        return self.get_pixel_board_from_locations(locations);
+    }
+    /// Returns the highest scoring pixel board out of the first scored_cliches_total_maximum valid arrangements found, starting from randomized locations.
+    /// If there are fewer valid arrangements than scored_cliches_total_maximum, every arrangement is scored and the best possible pixel board is returned.
+    /// The maximum only limits how many complete arrangements are scored: every valid pair of cell group locations is still collected before the search begins, and the partial arrangements visited while looking for the next complete arrangement are not counted, so it does not bound the time taken on a large pixel board.
+    pub fn get_best_pixel_board(&self, score: &dyn Fn(&PixelBoard<TPixel>) -> f32, scored_cliches_total_maximum: usize) -> PixelBoard<TPixel> {
+        if scored_cliches_total_maximum == 0 {
+            panic!("At least one cliche must be permitted to be scored in order to find the best pixel board.");
+        }
+
+        let stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>>>;
+        let is_always_valid_cell_group_index_per_cell_group_index: Rc<Vec<BitVec>>;
+        match self.get_all_stateful_hyper_graph_nodes(true) {
+            Some((all_stateful_hyper_graph_nodes_per_hyper_graph_node_index, all_is_always_valid_cell_group_index_per_cell_group_index)) => {
+                stateful_hyper_graph_nodes_per_hyper_graph_node_index = all_stateful_hyper_graph_nodes_per_hyper_graph_node_index;
+                is_always_valid_cell_group_index_per_cell_group_index = all_is_always_valid_cell_group_index_per_cell_group_index;
+            },
+            None => {
+                // nothing to increment over
+                return self.pixel_board.clone();
+            }
+        }
+
+        let mut best_pixel_board_option: Option<PixelBoard<TPixel>> = None;
+        let mut best_score: f32 = f32::MIN;
+        let mut cliches_total: usize = 0;
+        {
+            let hyper_graph_cliche_shifter = HyperGraphClicheShifter::new_hashed_with_islands(stateful_hyper_graph_nodes_per_hyper_graph_node_index, is_always_valid_cell_group_index_per_cell_group_index);
+            let mut shifter_incrementer = ShifterIncrementer::new(Box::new(hyper_graph_cliche_shifter), (0..self.cell_groups.len()).collect());
+            while cliches_total < scored_cliches_total_maximum && shifter_incrementer.try_increment() {
+                cliches_total += 1;
+                let pixel_board = self.get_pixel_board_from_incrementer(&shifter_incrementer);
+                let pixel_board_score = score(&pixel_board);
+                if best_pixel_board_option.is_none() || pixel_board_score > best_score {
+                    best_score = pixel_board_score;
+                    best_pixel_board_option = Some(pixel_board);
+                }
+            }
+        }
+        debug!("scored {cliches_total} cliches with best score {best_score}");
+
+        if best_pixel_board_option.is_none() {
+            panic!("Unexpected failure to find the original placement, let alone a new random one.");
+        }
+        return best_pixel_board_option.unwrap();
+    }
+    /// Returns the highest scoring pixel board when the score of a pixel board is the sum of the score of each cell group at its location.
+    /// The cell group score function is provided the original pixel board coordinate of the top-left of a cell group and a possible location for that cell group.
+    /// Since the score is decomposable per cell group, partial arrangements that cannot beat the best arrangement found so far are pruned, so the search will usually visit far fewer than scored_cliches_total_maximum complete arrangements before proving the best.
+    /// As with get_best_pixel_board, the maximum only limits how many complete arrangements are scored, not the collection of every valid pair of cell group locations beforehand nor the partial arrangements visited in between.
+    pub fn get_best_pixel_board_with_cell_group_scores(&self, cell_group_score: &dyn Fn((usize, usize), (u8, u8)) -> f32, scored_cliches_total_maximum: usize) -> PixelBoard<TPixel> {
+        if scored_cliches_total_maximum == 0 {
+            panic!("At least one cliche must be permitted to be scored in order to find the best pixel board.");
+        }
+
+        let stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>>>;
+        let is_always_valid_cell_group_index_per_cell_group_index: Rc<Vec<BitVec>>;
+        match self.get_all_stateful_hyper_graph_nodes(false) {
+            Some((all_stateful_hyper_graph_nodes_per_hyper_graph_node_index, all_is_always_valid_cell_group_index_per_cell_group_index)) => {
+                stateful_hyper_graph_nodes_per_hyper_graph_node_index = all_stateful_hyper_graph_nodes_per_hyper_graph_node_index;
+                is_always_valid_cell_group_index_per_cell_group_index = all_is_always_valid_cell_group_index_per_cell_group_index;
+            },
+            None => {
+                // nothing to increment over
+                return self.pixel_board.clone();
+            }
+        }
+
+        // score each possible location per cell group, searching the highest scoring locations first
+        let mut stateful_hyper_graph_node_index_and_score_tuples_per_hyper_graph_node_index: Vec<Vec<(usize, f32)>> = Vec::new();
+        for (hyper_graph_node_index, stateful_hyper_graph_nodes) in stateful_hyper_graph_nodes_per_hyper_graph_node_index.iter().enumerate() {
+            let pixel_board_coordinate = self.pixel_board_coordinate_per_cell_group_index[hyper_graph_node_index];
+            let mut stateful_hyper_graph_node_index_and_score_tuples: Vec<(usize, f32)> = stateful_hyper_graph_nodes.iter()
+                .enumerate()
+                .map(|(stateful_hyper_graph_node_index, stateful_hyper_graph_node)| (stateful_hyper_graph_node_index, cell_group_score(pixel_board_coordinate, *stateful_hyper_graph_node.borrow().state)))
+                .collect();
+            stateful_hyper_graph_node_index_and_score_tuples.sort_by(|a, b| b.1.total_cmp(&a.1));
+            stateful_hyper_graph_node_index_and_score_tuples_per_hyper_graph_node_index.push(stateful_hyper_graph_node_index_and_score_tuples);
+        }
+
+        // the most that the remaining cell groups could add to the score, per starting hyper graph node index
+        let mut maximum_remaining_score_per_hyper_graph_node_index: Vec<f32> = vec![0.0; stateful_hyper_graph_nodes_per_hyper_graph_node_index.len() + 1];
+        for hyper_graph_node_index in (0..stateful_hyper_graph_nodes_per_hyper_graph_node_index.len()).rev() {
+            let maximum_score = stateful_hyper_graph_node_index_and_score_tuples_per_hyper_graph_node_index[hyper_graph_node_index].first().map_or(0.0, |tuple| tuple.1);
+            maximum_remaining_score_per_hyper_graph_node_index[hyper_graph_node_index] = maximum_remaining_score_per_hyper_graph_node_index[hyper_graph_node_index + 1] + maximum_score;
+        }
+
+        // depth-first search over each cell group, pruning partial arrangements that cannot improve upon the best found so far
+        let mut best_stateful_hyper_graph_node_indexes_option: Option<Vec<usize>> = None;
+        let mut best_score: f32 = f32::MIN;
+        let mut cliches_total: usize = 0;
+        let mut stateful_hyper_graph_node_indexes: Vec<usize> = Vec::new();
+        let mut score_per_depth: Vec<f32> = vec![0.0];
+        let mut tuple_index_per_depth: Vec<usize> = vec![0];
+        while !tuple_index_per_depth.is_empty() && cliches_total < scored_cliches_total_maximum {
+            let hyper_graph_node_index = stateful_hyper_graph_node_indexes.len();
+            let current_score = *score_per_depth.last().unwrap();
+            if hyper_graph_node_index == stateful_hyper_graph_nodes_per_hyper_graph_node_index.len() {
+                // found a complete cliche
+                cliches_total += 1;
+                if best_stateful_hyper_graph_node_indexes_option.is_none() || current_score > best_score {
+                    best_score = current_score;
+                    best_stateful_hyper_graph_node_indexes_option = Some(stateful_hyper_graph_node_indexes.clone());
+                }
+                tuple_index_per_depth.pop();
+                score_per_depth.pop();
+                stateful_hyper_graph_node_indexes.pop();
+                continue;
+            }
+
+            let tuple_index = *tuple_index_per_depth.last().unwrap();
+            let stateful_hyper_graph_node_index_and_score_tuples = &stateful_hyper_graph_node_index_and_score_tuples_per_hyper_graph_node_index[hyper_graph_node_index];
+            if tuple_index == stateful_hyper_graph_node_index_and_score_tuples.len() ||
+                    (best_stateful_hyper_graph_node_indexes_option.is_some() && current_score + stateful_hyper_graph_node_index_and_score_tuples[tuple_index].1 + maximum_remaining_score_per_hyper_graph_node_index[hyper_graph_node_index + 1] <= best_score) {
+
+                // exhausted this cell group or no remaining location can beat the best score since the locations are sorted by score
+                tuple_index_per_depth.pop();
+                score_per_depth.pop();
+                stateful_hyper_graph_node_indexes.pop();
+                continue;
+            }
+            *tuple_index_per_depth.last_mut().unwrap() += 1;
+
+            let (stateful_hyper_graph_node_index, score) = stateful_hyper_graph_node_index_and_score_tuples[tuple_index];
+            let stateful_hyper_graph_node = stateful_hyper_graph_nodes_per_hyper_graph_node_index[hyper_graph_node_index][stateful_hyper_graph_node_index].borrow();
+            let mut is_valid = true;
+            for (other_hyper_graph_node_index, other_stateful_hyper_graph_node_index) in stateful_hyper_graph_node_indexes.iter().enumerate() {
+                if !is_always_valid_cell_group_index_per_cell_group_index[hyper_graph_node_index][other_hyper_graph_node_index] &&
+                        !stateful_hyper_graph_node.is_neighbor(other_hyper_graph_node_index, &stateful_hyper_graph_nodes_per_hyper_graph_node_index[other_hyper_graph_node_index][*other_stateful_hyper_graph_node_index]) {
+
+                    is_valid = false;
+                    break;
+                }
+            }
+            if is_valid {
+                stateful_hyper_graph_node_indexes.push(stateful_hyper_graph_node_index);
+                score_per_depth.push(current_score + score);
+                tuple_index_per_depth.push(0);
+            }
+        }
+        debug!("scored {cliches_total} cliches with best score {best_score}");
+
+        if best_stateful_hyper_graph_node_indexes_option.is_none() {
+            panic!("Unexpected failure to find the original placement, let alone a new random one.");
+        }
+        let mut locations: Vec<(usize, (u8, u8))> = Vec::new();
+        for (hyper_graph_node_index, stateful_hyper_graph_node_index) in best_stateful_hyper_graph_node_indexes_option.unwrap().into_iter().enumerate() {
+            let location = *stateful_hyper_graph_nodes_per_hyper_graph_node_index[hyper_graph_node_index][stateful_hyper_graph_node_index].borrow().state;
+            locations.push((hyper_graph_node_index, location));
+        }
+        return self.get_pixel_board_from_locations(locations);
+    }
+    // collects every valid pair of cell group locations as stateful hyper graph nodes, returning None if there is nothing to increment over
+    // the returned is_always_valid_cell_group_index_per_cell_group_index must be used when searching for cliches since the nodes are not focused on any one arrangement
+    fn get_all_stateful_hyper_graph_nodes(&self, is_randomized: bool) -> Option<(Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>>>, Rc<Vec<BitVec>>)> {
+        let mut round_robin_incrementer: RoundRobinIncrementer<(u8, u8)>;
+        match self.get_round_robin_incrementer(is_randomized, false) {
+            Some((incrementer, _)) => {
+                round_robin_incrementer = incrementer;
+            },
+            None => {
+                return None;
+            }
+        }
+
+        // wall segments along the same wall are always valid with each other because their shared shifter only produces valid arrangements, but without focusing on the latest arrangement they must be connected explicitly
+        let mut is_always_valid_cell_group_index_per_cell_group_index: Vec<BitVec> = self.is_always_valid_cell_group_index_per_cell_group_index.as_ref().clone();
+        for wall_segment_cell_group_indexes in [
+            &self.top_wall_segment_cell_group_indexes,
+            &self.right_wall_segment_cell_group_indexes,
+            &self.bottom_wall_segment_cell_group_indexes,
+            &self.left_wall_segment_cell_group_indexes
+        ] {
+            for from_cell_group_index in wall_segment_cell_group_indexes.iter() {
+                for to_cell_group_index in wall_segment_cell_group_indexes.iter() {
+                    if from_cell_group_index != to_cell_group_index {
+                        is_always_valid_cell_group_index_per_cell_group_index[*from_cell_group_index].set(*to_cell_group_index, false);
+                    }
+                }
+            }
+        }
+
+        // collect every valid pair of locations
+        let mut stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>>> = Vec::new();
+        for _ in 0..self.cell_groups.len() {
+            stateful_hyper_graph_nodes_per_hyper_graph_node_index.push(Vec::new());
+        }
+        let mut connections_total = 0;
+        while round_robin_incrementer.try_increment() {
+            round_robin_incrementer.visit(&mut |locations| {
+                self.add_locations_to_stateful_hyper_graph_nodes(locations, &is_always_valid_cell_group_index_per_cell_group_index, &mut stateful_hyper_graph_nodes_per_hyper_graph_node_index, &mut connections_total);
+            });
+        }
+        debug!("collected {connections_total} connections");
+        return Some((stateful_hyper_graph_nodes_per_hyper_graph_node_index, Rc::new(is_always_valid_cell_group_index_per_cell_group_index)));
+    }
+    // prioritizes each incrementer by how many of its cell groups do not yet have a location connected to every other cell group, since those cell groups are what keep a cliche from being found
+    fn set_priorities_from_cliche_progress(round_robin_incrementer: &mut RoundRobinIncrementer<(u8, u8)>, cell_group_indexes_per_incrementer_index: &[Vec<usize>], is_fully_connected_per_cell_group_index: &BitVec) {
+        for (incrementer_index, cell_group_indexes) in cell_group_indexes_per_incrementer_index.iter().enumerate() {
+            let priority = cell_group_indexes.iter()
+                .filter(|cell_group_index| !is_fully_connected_per_cell_group_index[**cell_group_index])
+                .count();
+            round_robin_incrementer.set_priority(incrementer_index, priority);
+        }
+    }
+    // constructs the incrementer that searches over each cell group dependency, collecting valid pairs of cell group locations, along with the cell group indexes of each of its incrementers, returning None if there is nothing to increment over
+    fn get_round_robin_incrementer(&self, is_randomized: bool, is_independent_limited: bool) -> Option<(RoundRobinIncrementer<(u8, u8)>, Vec<Vec<usize>>)> {
+        // clone the shifters, randomizing them if requested
+        let mut corner_wall_index_shifters: Vec<IndexShifter<(u8, u8)>> = Vec::new();
+        let mut corner_wall_cell_group_index_per_shifter: Vec<usize> = Vec::new();
+        for (shifter_option, cell_group_index_option) in [
+            (self.top_left_corner_wall_index_shifter_option.as_ref(), self.top_left_corner_wall_cell_group_index.as_ref()),
+            (self.top_right_corner_wall_index_shifter_option.as_ref(), self.top_right_corner_wall_cell_group_index.as_ref()),
+            (self.bottom_right_corner_wall_index_shifter_option.as_ref(), self.bottom_right_corner_wall_cell_group_index.as_ref()),
+            (self.bottom_left_corner_wall_index_shifter_option.as_ref(), self.bottom_left_corner_wall_cell_group_index.as_ref())
+        ] {
+            if shifter_option.is_some() {
+                let mut shifter = shifter_option.unwrap().clone();
+                if is_randomized {
+                    shifter.randomize();
+                }
+                corner_wall_index_shifters.push(shifter);
+                corner_wall_cell_group_index_per_shifter.push(*cell_group_index_option.unwrap());
+            }
+        }
+        let mut wall_segment_permutation_shifters: Vec<SegmentPermutationShifter> = Vec::new();
+        let mut wall_segment_cell_group_indexes_per_shifter: Vec<Vec<usize>> = Vec::new();
+        for (shifter_option, cell_group_indexes) in [
+            (self.top_wall_segment_permutation_shifter_option.as_ref(), self.top_wall_segment_cell_group_indexes.clone()),
+            (self.right_wall_segment_permutation_shifter_option.as_ref(), self.right_wall_segment_cell_group_indexes.clone()),
+            (self.bottom_wall_segment_permutation_shifter_option.as_ref(), self.bottom_wall_segment_cell_group_indexes.clone()),
+            (self.left_wall_segment_permutation_shifter_option.as_ref(), self.left_wall_segment_cell_group_indexes.clone())
+        ] {
+            if shifter_option.is_some() {
+                let mut shifter = shifter_option.unwrap().clone();
+                if is_randomized {
+                    shifter.randomize();
+                }
+                wall_segment_permutation_shifters.push(shifter);
+                wall_segment_cell_group_indexes_per_shifter.push(cell_group_indexes);
+            }
+        }
+        let mut wall_adjacent_index_shifters: Vec<IndexShifter<(u8, u8)>> = Vec::new();
+        let mut wall_adjacent_cell_group_index_per_shifter: Vec<usize> = Vec::new();
+        for (index_shifter, cell_group_index) in self.wall_adjacent_index_shifters.iter().zip(self.wall_adjacent_cell_group_indexes.iter()) {
+            let mut shifter = index_shifter.clone();
+            if is_randomized {
+                shifter.randomize();
+            }
+            wall_adjacent_index_shifters.push(shifter);
+            wall_adjacent_cell_group_index_per_shifter.push(*cell_group_index);
+        }
+
+        // TODO construct each shifting cell group dependency incrementer per pair of shifters
+        let mut incrementers: Vec<Box<dyn Incrementer<T = (u8, u8)>>> = Vec::new();
+        let mut cell_group_indexes_per_incrementer_index: Vec<Vec<usize>> = Vec::new();
+
+        // fill the incrementers that will be used by the round-robin
+        {
+            // this structure contains all of the dependent cell groups
+            let mut cell_group_dependencies: Vec<CellGroupDependency> = Vec::new();
+            // this structure contains all of the independent cell groups, needed at the end to get one valid location per cell group
+            let mut independent_shifter_incrementers: Vec<ShifterIncrementer<(u8, u8)>> = Vec::new();
+
+            if corner_wall_index_shifters.len() == 0 && wall_segment_permutation_shifters.len() == 0 && wall_adjacent_index_shifters.len() == 0 {
+                // nothing to increment over
+                return None;
+            }
+            else if corner_wall_index_shifters.len() + wall_segment_permutation_shifters.len() + wall_adjacent_index_shifters.len() == 1 {
+                if corner_wall_index_shifters.len() == 1 {
+                    let cell_group_dependency = CellGroupDependency::new(corner_wall_cell_group_index_per_shifter, Box::new(ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(corner_wall_index_shifters[0].clone())], true)));
+                    cell_group_dependencies.push(cell_group_dependency);
+                }
+                else if wall_segment_permutation_shifters.len() == 1 {
+                    let cell_group_dependency = CellGroupDependency::new(wall_segment_cell_group_indexes_per_shifter[0].clone(), Box::new(ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_segment_permutation_shifters[0].clone())], true)));
+                    cell_group_dependencies.push(cell_group_dependency);
+                }
+                else if wall_adjacent_index_shifters.len() == 1 {
+                    let cell_group_dependency = CellGroupDependency::new(wall_adjacent_cell_group_index_per_shifter, Box::new(ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_adjacent_index_shifters[0].clone())], true)));
+                    cell_group_dependencies.push(cell_group_dependency);
+                }
+                else {
+                    panic!("Unexpected difference between encapsulating if-statement and split if-statements.");
+                }
+            }
+            else {
+
+                // TODO only create the cell group dependencies that are not always valid
+
+                let mut dependent_corner_wall_shifter_indexes: Vec<usize> = Vec::new();
+                let mut dependent_wall_segment_shifter_indexes: Vec<usize> = Vec::new();
+                let mut dependent_wall_adjacent_shifter_indexes: Vec<usize> = Vec::new();
+
+                // create a combined shifter per pair of corner wall shifters
+                if !corner_wall_index_shifters.is_empty() {
+                    for shifter_index in 0..(corner_wall_index_shifters.len() - 1) {
+                        for other_shifter_index in (shifter_index + 1)..corner_wall_index_shifters.len() {
+                            let combined_cell_group_indexes: Vec<usize> = vec![corner_wall_cell_group_index_per_shifter[shifter_index], corner_wall_cell_group_index_per_shifter[other_shifter_index]];
                             let mut is_combined_cell_groups_dependent = false;
                             'check_if_combined_is_dependent: {
                                 for from_combined_cell_group_index in 0..(combined_cell_group_indexes.len() - 1) {
@@ -1379,7 +1715,7 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                                         let to_cell_group_index = combined_cell_group_indexes[to_combined_cell_group_index];
                                         if !self.is_always_valid_cell_group_index_per_cell_group_index[from_cell_group_index][to_cell_group_index] ||
                                                 !self.is_always_valid_cell_group_index_per_cell_group_index[to_cell_group_index][from_cell_group_index] {
-                                            
+
                                             is_combined_cell_groups_dependent = true;
                                             break 'check_if_combined_is_dependent;
                                         }
@@ -1387,19 +1723,24 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                                 }
                             }
                             if is_combined_cell_groups_dependent {
-                                dependent_corner_wall_shifter_indexes.push(corner_wall_shifter_index);
-                                dependent_wall_segment_shifter_indexes.push(wall_segment_shifter_index);
+                                dependent_corner_wall_shifter_indexes.push(shifter_index);
+                                dependent_corner_wall_shifter_indexes.push(other_shifter_index);
 
-                                let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(corner_wall_index_shifters[corner_wall_shifter_index].clone()), Box::new(wall_segment_permutation_shifters[wall_segment_shifter_index].clone())], true);
+                                let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(corner_wall_index_shifters[shifter_index].clone()), Box::new(corner_wall_index_shifters[other_shifter_index].clone())], true);
                                 let cell_group_dependency = CellGroupDependency::new(combined_cell_group_indexes, Box::new(shifter));
                                 cell_group_dependencies.push(cell_group_dependency);
                             }
                         }
                     }
-                    // create a combined shifter per corner wall shifter and non-wall shifter pair
-                    for corner_wall_shifter_index in 0..corner_wall_index_shifters.len() {
-                        for wall_adjacent_shifter_index in 0..wall_adjacent_index_shifters.len() {
-                            let combined_cell_group_indexes: Vec<usize> = vec![corner_wall_cell_group_index_per_shifter[corner_wall_shifter_index], wall_adjacent_cell_group_index_per_shifter[wall_adjacent_shifter_index]];
+                }
+                // create a combined shifter per pair of segment wall shifters
+                if !wall_segment_permutation_shifters.is_empty() {
+                    for shifter_index in 0..(wall_segment_permutation_shifters.len() - 1) {
+                        for other_shifter_index in (shifter_index + 1)..wall_segment_permutation_shifters.len() {
+                            let mut combined_cell_group_indexes: Vec<usize> = Vec::new();
+                            for wall_segment_cell_group_index in wall_segment_cell_group_indexes_per_shifter[shifter_index].iter().chain(wall_segment_cell_group_indexes_per_shifter[other_shifter_index].iter()) {
+                                combined_cell_group_indexes.push(*wall_segment_cell_group_index);
+                            }
                             let mut is_combined_cell_groups_dependent = false;
                             'check_if_combined_is_dependent: {
                                 for from_combined_cell_group_index in 0..(combined_cell_group_indexes.len() - 1) {
@@ -1408,7 +1749,7 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                                         let to_cell_group_index = combined_cell_group_indexes[to_combined_cell_group_index];
                                         if !self.is_always_valid_cell_group_index_per_cell_group_index[from_cell_group_index][to_cell_group_index] ||
                                                 !self.is_always_valid_cell_group_index_per_cell_group_index[to_cell_group_index][from_cell_group_index] {
-                                            
+
                                             is_combined_cell_groups_dependent = true;
                                             break 'check_if_combined_is_dependent;
                                         }
@@ -1416,30 +1757,30 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                                 }
                             }
                             if is_combined_cell_groups_dependent {
-                                dependent_corner_wall_shifter_indexes.push(corner_wall_shifter_index);
-                                dependent_wall_adjacent_shifter_indexes.push(wall_adjacent_shifter_index);
+                                dependent_wall_segment_shifter_indexes.push(shifter_index);
+                                dependent_wall_segment_shifter_indexes.push(other_shifter_index);
 
-                                let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(corner_wall_index_shifters[corner_wall_shifter_index].clone()), Box::new(wall_adjacent_index_shifters[wall_adjacent_shifter_index].clone())], true);
+                                let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_segment_permutation_shifters[shifter_index].clone()), Box::new(wall_segment_permutation_shifters[other_shifter_index].clone())], true);
                                 let cell_group_dependency = CellGroupDependency::new(combined_cell_group_indexes, Box::new(shifter));
                                 cell_group_dependencies.push(cell_group_dependency);
                             }
                         }
                     }
-                    // create a combined shifter per segment wall shifter and non-wall shifter pair
-                    for wall_segment_shifter_index in 0..wall_segment_permutation_shifters.len() {
-                        for wall_adjacent_shifter_index in 0..wall_adjacent_index_shifters.len() {
-                            let mut combined_cell_group_indexes: Vec<usize> = wall_segment_cell_group_indexes_per_shifter[wall_segment_shifter_index].clone();
-                            combined_cell_group_indexes.push(wall_adjacent_cell_group_index_per_shifter[wall_adjacent_shifter_index]);
+                }
+                // create a combined shifter per pair of non-wall shifters
+                if !wall_adjacent_index_shifters.is_empty() {
+                    for shifter_index in 0..(wall_adjacent_index_shifters.len() - 1) {
+                        for other_shifter_index in (shifter_index + 1)..wall_adjacent_index_shifters.len() {
+                            let combined_cell_group_indexes: Vec<usize> = vec![wall_adjacent_cell_group_index_per_shifter[shifter_index], wall_adjacent_cell_group_index_per_shifter[other_shifter_index]];
                             let mut is_combined_cell_groups_dependent = false;
                             'check_if_combined_is_dependent: {
                                 for from_combined_cell_group_index in 0..(combined_cell_group_indexes.len() - 1) {
                                     let from_cell_group_index = combined_cell_group_indexes[from_combined_cell_group_index];
                                     for to_combined_cell_group_index in (from_combined_cell_group_index + 1)..combined_cell_group_indexes.len() {
                                         let to_cell_group_index = combined_cell_group_indexes[to_combined_cell_group_index];
-                                        debug!("checking if {} and {} are dependent", from_cell_group_index, to_cell_group_index);
                                         if !self.is_always_valid_cell_group_index_per_cell_group_index[from_cell_group_index][to_cell_group_index] ||
                                                 !self.is_always_valid_cell_group_index_per_cell_group_index[to_cell_group_index][from_cell_group_index] {
-                                            
+
                                             is_combined_cell_groups_dependent = true;
                                             break 'check_if_combined_is_dependent;
                                         }
@@ -1447,258 +1788,313 @@ impl<TPixel: Pixel> PixelBoardRandomizer<TPixel> {
                                 }
                             }
                             if is_combined_cell_groups_dependent {
-                                dependent_wall_segment_shifter_indexes.push(wall_segment_shifter_index);
-                                dependent_wall_adjacent_shifter_indexes.push(wall_adjacent_shifter_index);
+                                dependent_wall_adjacent_shifter_indexes.push(shifter_index);
+                                dependent_wall_adjacent_shifter_indexes.push(other_shifter_index);
 
-                                let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_segment_permutation_shifters[wall_segment_shifter_index].clone()), Box::new(wall_adjacent_index_shifters[wall_adjacent_shifter_index].clone())], true);
+                                let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_adjacent_index_shifters[shifter_index].clone()), Box::new(wall_adjacent_index_shifters[other_shifter_index].clone())], true);
                                 let cell_group_dependency = CellGroupDependency::new(combined_cell_group_indexes, Box::new(shifter));
                                 cell_group_dependencies.push(cell_group_dependency);
                             }
                         }
                     }
-
-                    // determine which shifters are fully independent and fill independent_shifter_per_cell_group_index
-                    {
-                        dependent_corner_wall_shifter_indexes.sort();
-                        dependent_corner_wall_shifter_indexes.dedup();
-
-                        let mut current_corner_wall_shifter_index = 0;
-                        let mut dependent_corner_wall_shifter_indexes_index = 0;
-                        while current_corner_wall_shifter_index < corner_wall_index_shifters.len() {
-                            if dependent_corner_wall_shifter_indexes_index < dependent_corner_wall_shifter_indexes.len() {
-                                if current_corner_wall_shifter_index != dependent_corner_wall_shifter_indexes[dependent_corner_wall_shifter_indexes_index] {
-                                    // the dependent corner wall shifter index is ahead of the current corner wall shifter index, so the current one (being missing) is independent
-                                    independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(corner_wall_index_shifters[current_corner_wall_shifter_index].clone()), vec![corner_wall_cell_group_index_per_shifter[current_corner_wall_shifter_index]]));
+                }
+                // create a combined shifter per corner wall shifter and segment wall shifter pair
+                for corner_wall_shifter_index in 0..corner_wall_index_shifters.len() {
+                    for wall_segment_shifter_index in 0..wall_segment_permutation_shifters.len() {
+                        // TODO refactor into idomatic vector concat
+                        let mut combined_cell_group_indexes: Vec<usize> = vec![corner_wall_cell_group_index_per_shifter[corner_wall_shifter_index]];
+                        for wall_segment_cell_group_index in wall_segment_cell_group_indexes_per_shifter[wall_segment_shifter_index].iter() {
+                            combined_cell_group_indexes.push(*wall_segment_cell_group_index);
+                        }
+                        let mut is_combined_cell_groups_dependent = false;
+                        'check_if_combined_is_dependent: {
+                            for from_combined_cell_group_index in 0..(combined_cell_group_indexes.len() - 1) {
+                                let from_cell_group_index = combined_cell_group_indexes[from_combined_cell_group_index];
+                                for to_combined_cell_group_index in (from_combined_cell_group_index + 1)..combined_cell_group_indexes.len() {
+                                    let to_cell_group_index = combined_cell_group_indexes[to_combined_cell_group_index];
+                                    if !self.is_always_valid_cell_group_index_per_cell_group_index[from_cell_group_index][to_cell_group_index] ||
+                                            !self.is_always_valid_cell_group_index_per_cell_group_index[to_cell_group_index][from_cell_group_index] {
+
+                                        is_combined_cell_groups_dependent = true;
+                                        break 'check_if_combined_is_dependent;
+                                    }
                                 }
-                                else {
-                                    dependent_corner_wall_shifter_indexes_index += 1;
+                            }
+                        }
+                        if is_combined_cell_groups_dependent {
+                            dependent_corner_wall_shifter_indexes.push(corner_wall_shifter_index);
+                            dependent_wall_segment_shifter_indexes.push(wall_segment_shifter_index);
+
+                            let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(corner_wall_index_shifters[corner_wall_shifter_index].clone()), Box::new(wall_segment_permutation_shifters[wall_segment_shifter_index].clone())], true);
+                            let cell_group_dependency = CellGroupDependency::new(combined_cell_group_indexes, Box::new(shifter));
+                            cell_group_dependencies.push(cell_group_dependency);
+                        }
+                    }
+                }
+                // create a combined shifter per corner wall shifter and non-wall shifter pair
+                for corner_wall_shifter_index in 0..corner_wall_index_shifters.len() {
+                    for wall_adjacent_shifter_index in 0..wall_adjacent_index_shifters.len() {
+                        let combined_cell_group_indexes: Vec<usize> = vec![corner_wall_cell_group_index_per_shifter[corner_wall_shifter_index], wall_adjacent_cell_group_index_per_shifter[wall_adjacent_shifter_index]];
+                        let mut is_combined_cell_groups_dependent = false;
+                        'check_if_combined_is_dependent: {
+                            for from_combined_cell_group_index in 0..(combined_cell_group_indexes.len() - 1) {
+                                let from_cell_group_index = combined_cell_group_indexes[from_combined_cell_group_index];
+                                for to_combined_cell_group_index in (from_combined_cell_group_index + 1)..combined_cell_group_indexes.len() {
+                                    let to_cell_group_index = combined_cell_group_indexes[to_combined_cell_group_index];
+                                    if !self.is_always_valid_cell_group_index_per_cell_group_index[from_cell_group_index][to_cell_group_index] ||
+                                            !self.is_always_valid_cell_group_index_per_cell_group_index[to_cell_group_index][from_cell_group_index] {
+
+                                        is_combined_cell_groups_dependent = true;
+                                        break 'check_if_combined_is_dependent;
+                                    }
                                 }
                             }
-                            else {
-                                // the current corner wall shifter index is finding indendent shifter indexes at the end of the list
-                                independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(corner_wall_index_shifters[current_corner_wall_shifter_index].clone()), vec![corner_wall_cell_group_index_per_shifter[current_corner_wall_shifter_index]]));
+                        }
+                        if is_combined_cell_groups_dependent {
+                            dependent_corner_wall_shifter_indexes.push(corner_wall_shifter_index);
+                            dependent_wall_adjacent_shifter_indexes.push(wall_adjacent_shifter_index);
+
+                            let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(corner_wall_index_shifters[corner_wall_shifter_index].clone()), Box::new(wall_adjacent_index_shifters[wall_adjacent_shifter_index].clone())], true);
+                            let cell_group_dependency = CellGroupDependency::new(combined_cell_group_indexes, Box::new(shifter));
+                            cell_group_dependencies.push(cell_group_dependency);
+                        }
+                    }
+                }
+                // create a combined shifter per segment wall shifter and non-wall shifter pair
+                for wall_segment_shifter_index in 0..wall_segment_permutation_shifters.len() {
+                    for wall_adjacent_shifter_index in 0..wall_adjacent_index_shifters.len() {
+                        let mut combined_cell_group_indexes: Vec<usize> = wall_segment_cell_group_indexes_per_shifter[wall_segment_shifter_index].clone();
+                        combined_cell_group_indexes.push(wall_adjacent_cell_group_index_per_shifter[wall_adjacent_shifter_index]);
+                        let mut is_combined_cell_groups_dependent = false;
+                        'check_if_combined_is_dependent: {
+                            for from_combined_cell_group_index in 0..(combined_cell_group_indexes.len() - 1) {
+                                let from_cell_group_index = combined_cell_group_indexes[from_combined_cell_group_index];
+                                for to_combined_cell_group_index in (from_combined_cell_group_index + 1)..combined_cell_group_indexes.len() {
+                                    let to_cell_group_index = combined_cell_group_indexes[to_combined_cell_group_index];
+                                    debug!("checking if {} and {} are dependent", from_cell_group_index, to_cell_group_index);
+                                    if !self.is_always_valid_cell_group_index_per_cell_group_index[from_cell_group_index][to_cell_group_index] ||
+                                            !self.is_always_valid_cell_group_index_per_cell_group_index[to_cell_group_index][from_cell_group_index] {
+
+                                        is_combined_cell_groups_dependent = true;
+                                        break 'check_if_combined_is_dependent;
+                                    }
+                                }
                             }
-                            current_corner_wall_shifter_index += 1;
+                        }
+                        if is_combined_cell_groups_dependent {
+                            dependent_wall_segment_shifter_indexes.push(wall_segment_shifter_index);
+                            dependent_wall_adjacent_shifter_indexes.push(wall_adjacent_shifter_index);
+
+                            let shifter = ShiftingSquareBreadthFirstSearchShifter::new(vec![Box::new(wall_segment_permutation_shifters[wall_segment_shifter_index].clone()), Box::new(wall_adjacent_index_shifters[wall_adjacent_shifter_index].clone())], true);
+                            let cell_group_dependency = CellGroupDependency::new(combined_cell_group_indexes, Box::new(shifter));
+                            cell_group_dependencies.push(cell_group_dependency);
                         }
                     }
-                    {
-                        dependent_wall_segment_shifter_indexes.sort();
-                        dependent_wall_segment_shifter_indexes.dedup();
+                }
 
-                        let mut current_wall_segment_shifter_index = 0;
-                        let mut dependent_wall_segment_shifter_indexes_index = 0;
-                        while current_wall_segment_shifter_index < wall_segment_permutation_shifters.len() {
-                            if dependent_wall_segment_shifter_indexes_index < dependent_wall_segment_shifter_indexes.len() {
-                                if current_wall_segment_shifter_index != dependent_wall_segment_shifter_indexes[dependent_wall_segment_shifter_indexes_index] {
-                                    // the dependent corner wall shifter index is ahead of the current corner wall shifter index, so the current one (being missing) is independent
-                                    independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(wall_segment_permutation_shifters[current_wall_segment_shifter_index].clone()), wall_segment_cell_group_indexes_per_shifter[current_wall_segment_shifter_index].clone()));
-                                }
-                                else {
-                                    dependent_wall_segment_shifter_indexes_index += 1;
-                                }
+                // determine which shifters are fully independent and fill independent_shifter_per_cell_group_index
+                {
+                    dependent_corner_wall_shifter_indexes.sort();
+                    dependent_corner_wall_shifter_indexes.dedup();
+
+                    let mut current_corner_wall_shifter_index = 0;
+                    let mut dependent_corner_wall_shifter_indexes_index = 0;
+                    while current_corner_wall_shifter_index < corner_wall_index_shifters.len() {
+                        if dependent_corner_wall_shifter_indexes_index < dependent_corner_wall_shifter_indexes.len() {
+                            if current_corner_wall_shifter_index != dependent_corner_wall_shifter_indexes[dependent_corner_wall_shifter_indexes_index] {
+                                // the dependent corner wall shifter index is ahead of the current corner wall shifter index, so the current one (being missing) is independent
+                                independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(corner_wall_index_shifters[current_corner_wall_shifter_index].clone()), vec![corner_wall_cell_group_index_per_shifter[current_corner_wall_shifter_index]]));
                             }
                             else {
-                                // the current corner wall shifter index is finding indendent shifter indexes at the end of the list
-                                independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(wall_segment_permutation_shifters[current_wall_segment_shifter_index].clone()), wall_segment_cell_group_indexes_per_shifter[current_wall_segment_shifter_index].clone()));
+                                dependent_corner_wall_shifter_indexes_index += 1;
                             }
-                            current_wall_segment_shifter_index += 1;
                         }
+                        else {
+                            // the current corner wall shifter index is finding indendent shifter indexes at the end of the list
+                            independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(corner_wall_index_shifters[current_corner_wall_shifter_index].clone()), vec![corner_wall_cell_group_index_per_shifter[current_corner_wall_shifter_index]]));
+                        }
+                        current_corner_wall_shifter_index += 1;
                     }
-                    {
-                        dependent_wall_adjacent_shifter_indexes.sort();
-                        dependent_wall_adjacent_shifter_indexes.dedup();
-
-                        let mut current_wall_adjacent_shifter_index = 0;
-                        let mut dependent_wall_adjacent_shifter_indexes_index = 0;
-                        while current_wall_adjacent_shifter_index < wall_adjacent_index_shifters.len() {
-                            if dependent_wall_adjacent_shifter_indexes_index < dependent_wall_adjacent_shifter_indexes.len() {
-                                if current_wall_adjacent_shifter_index != dependent_wall_adjacent_shifter_indexes[dependent_wall_adjacent_shifter_indexes_index] {
-                                    // the dependent corner wall shifter index is ahead of the current corner wall shifter index, so the current one (being missing) is independent
-                                    independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(wall_adjacent_index_shifters[current_wall_adjacent_shifter_index].clone()), vec![wall_adjacent_cell_group_index_per_shifter[current_wall_adjacent_shifter_index]]));
-                                }
-                                else {
-                                    dependent_wall_adjacent_shifter_indexes_index += 1;
-                                }
+                }
+                {
+                    dependent_wall_segment_shifter_indexes.sort();
+                    dependent_wall_segment_shifter_indexes.dedup();
+
+                    let mut current_wall_segment_shifter_index = 0;
+                    let mut dependent_wall_segment_shifter_indexes_index = 0;
+                    while current_wall_segment_shifter_index < wall_segment_permutation_shifters.len() {
+                        if dependent_wall_segment_shifter_indexes_index < dependent_wall_segment_shifter_indexes.len() {
+                            if current_wall_segment_shifter_index != dependent_wall_segment_shifter_indexes[dependent_wall_segment_shifter_indexes_index] {
+                                // the dependent corner wall shifter index is ahead of the current corner wall shifter index, so the current one (being missing) is independent
+                                independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(wall_segment_permutation_shifters[current_wall_segment_shifter_index].clone()), wall_segment_cell_group_indexes_per_shifter[current_wall_segment_shifter_index].clone()));
                             }
                             else {
-                                // the current corner wall shifter index is finding indendent shifter indexes at the end of the list
+                                dependent_wall_segment_shifter_indexes_index += 1;
+                            }
+                        }
+                        else {
+                            // the current corner wall shifter index is finding indendent shifter indexes at the end of the list
+                            independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(wall_segment_permutation_shifters[current_wall_segment_shifter_index].clone()), wall_segment_cell_group_indexes_per_shifter[current_wall_segment_shifter_index].clone()));
+                        }
+                        current_wall_segment_shifter_index += 1;
+                    }
+                }
+                {
+                    dependent_wall_adjacent_shifter_indexes.sort();
+                    dependent_wall_adjacent_shifter_indexes.dedup();
+
+                    let mut current_wall_adjacent_shifter_index = 0;
+                    let mut dependent_wall_adjacent_shifter_indexes_index = 0;
+                    while current_wall_adjacent_shifter_index < wall_adjacent_index_shifters.len() {
+                        if dependent_wall_adjacent_shifter_indexes_index < dependent_wall_adjacent_shifter_indexes.len() {
+                            if current_wall_adjacent_shifter_index != dependent_wall_adjacent_shifter_indexes[dependent_wall_adjacent_shifter_indexes_index] {
+                                // the dependent corner wall shifter index is ahead of the current corner wall shifter index, so the current one (being missing) is independent
                                 independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(wall_adjacent_index_shifters[current_wall_adjacent_shifter_index].clone()), vec![wall_adjacent_cell_group_index_per_shifter[current_wall_adjacent_shifter_index]]));
                             }
-                            current_wall_adjacent_shifter_index += 1;
+                            else {
+                                dependent_wall_adjacent_shifter_indexes_index += 1;
+                            }
+                        }
+                        else {
+                            // the current corner wall shifter index is finding indendent shifter indexes at the end of the list
+                            independent_shifter_incrementers.push(ShifterIncrementer::new(Box::new(wall_adjacent_index_shifters[current_wall_adjacent_shifter_index].clone()), vec![wall_adjacent_cell_group_index_per_shifter[current_wall_adjacent_shifter_index]]));
                         }
+                        current_wall_adjacent_shifter_index += 1;
                     }
                 }
+            }
 
-                // create the independent limited incrementers
-                for independent_shifter_incrementer in independent_shifter_incrementers {
+            // create the independent incrementers, limiting each to one location if only one valid combination is needed
+            for independent_shifter_incrementer in independent_shifter_incrementers {
+                cell_group_indexes_per_incrementer_index.push(independent_shifter_incrementer.get_index_mapping().to_vec());
+                if is_independent_limited {
                     let limited_incrementer = LimitedIncrementer::new(Box::new(independent_shifter_incrementer), 1);
                     incrementers.push(Box::new(limited_incrementer));
                 }
-
-                // create the shifting cell group dependency incrementers
-                for cell_group_dependency in cell_group_dependencies {
-                    let shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(self.cell_groups.clone(), vec![cell_group_dependency], Some(self.detection_offsets_per_cell_group_index_per_cell_group_index.clone()), Some(self.is_adjacent_cell_group_index_per_cell_group_index.clone()));
-                    incrementers.push(Box::new(shifting_cell_group_dependency_incrementer));
+                else {
+                    incrementers.push(Box::new(independent_shifter_incrementer));
                 }
             }
 
-            // TODO construct each incrementer that equates to each possible combination of cell groups depending on their location in the bounds
-            round_robin_incrementer = RoundRobinIncrementer::new(incrementers);
-        }
-
-        // prepare to find the cycle as the RoundRobinIncrementer is iterated over
-        // the idea is that we round-robin across all shifters, building up graphs of connected locations until we find that the next pair to be connected already exist in the same graph, then we check for a cycle
-        
-        // contains all of the states discovered thus far
-        let mut stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>>> = Vec::new();
-        for _ in 0..self.cell_groups.len() {
-            stateful_hyper_graph_nodes_per_hyper_graph_node_index.push(Vec::new());
+            // create the shifting cell group dependency incrementers
+            for cell_group_dependency in cell_group_dependencies {
+                cell_group_indexes_per_incrementer_index.push(cell_group_dependency.get_cell_group_index_mapping().to_vec());
+                let mut shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(self.cell_groups.clone(), vec![cell_group_dependency], Some(self.detection_offsets_per_cell_group_index_per_cell_group_index.clone()), Some(self.is_adjacent_cell_group_index_per_cell_group_index.clone()));
+                // the invalid locations found by earlier searches are skipped
+                shifting_cell_group_dependency_incrementer.set_nogood_cache(self.nogood_cache.clone());
+                incrementers.push(Box::new(shifting_cell_group_dependency_incrementer));
+            }
         }
 
-        let mut connections_total = 0;
+        // TODO construct each incrementer that equates to each possible combination of cell groups depending on their location in the bounds
+        return Some((RoundRobinIncrementer::new_with_scheduling_policy(incrementers, self.scheduling_policy.get_scheduling_policy()), cell_group_indexes_per_incrementer_index));
+    }
+    // adds the provided locations as stateful hyper graph nodes, connecting each pair of locations, and returns the latest connected pair to focus on if any pair was connected
+    fn add_locations_to_stateful_hyper_graph_nodes(&self, locations: &[IndexedElement<(u8, u8)>], is_always_valid_cell_group_index_per_cell_group_index: &Vec<BitVec>, stateful_hyper_graph_nodes_per_hyper_graph_node_index: &mut Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>>>, connections_total: &mut usize) -> Option<Vec<(usize, usize)>> {
         let mut focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option: Option<Vec<(usize, usize)>> = None;
-        let mut is_incrementer_completed: bool = false;
-        while !is_incrementer_completed {
-            // TODO get the next set of locations
-            is_incrementer_completed = !round_robin_incrementer.try_increment();
-            if !is_incrementer_completed {
-                debug!("round robin incremented");
-                let locations = round_robin_incrementer.get();
-                debug!("found locations: {locations:?}");
-
-                if locations.len() == 1 {
-                    // TODO check for if the element is already a saved state, if it is do not check for a cliche
-                    // TODO set the focused tuple to the previous fully connected focused tuple
-                    let current_indexed_element = &locations[0];
-                    'looking_for_stateful_hyper_graph_node: {
-                        for stateful_hyper_graph_node in stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].iter() {
-                            if stateful_hyper_graph_node.borrow().state == current_indexed_element.element {
-                                // break out since it does not need to be created
-                                break 'looking_for_stateful_hyper_graph_node;
-                            }
-                        }
-
-                        // create the stateful hyper graph node since it does not yet exist
-                        let is_hyper_graph_node_index_connected: BitVec = self.is_always_valid_cell_group_index_per_cell_group_index[current_indexed_element.index].clone();
-                        let current_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(current_indexed_element.element.clone(), is_hyper_graph_node_index_connected)));
-                        stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].push(current_stateful_hyper_graph_node);
+        if locations.len() == 1 {
+            // TODO check for if the element is already a saved state, if it is do not check for a cliche
+            // TODO set the focused tuple to the previous fully connected focused tuple
+            let current_indexed_element = &locations[0];
+            'looking_for_stateful_hyper_graph_node: {
+                for stateful_hyper_graph_node in stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].iter() {
+                    if stateful_hyper_graph_node.borrow().state == current_indexed_element.element {
+                        // break out since it does not need to be created
+                        break 'looking_for_stateful_hyper_graph_node;
                     }
                 }
-                else {
-                    // TODO treat each pair individually, iterating over each pair
-                    for (current_indexed_element_index, current_indexed_element) in locations.iter().enumerate() {
-                        for (other_indexed_element_index, other_indexed_element) in locations.iter().enumerate() {
-                            if current_indexed_element_index < other_indexed_element_index {
-                                let mut current_stateful_hyper_graph_node_option: Option<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>> = None;
-                                let mut current_stateful_hyper_graph_node_index_option: Option<usize> = None;
-                                for (stateful_hyper_graph_node_index, stateful_hyper_graph_node) in stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].iter().enumerate() {
-                                    if stateful_hyper_graph_node.borrow().state == current_indexed_element.element {
-                                        current_stateful_hyper_graph_node_option = Some(stateful_hyper_graph_node.clone());
-                                        current_stateful_hyper_graph_node_index_option = Some(stateful_hyper_graph_node_index);
-                                        break;
-                                    }
-                                }
-                                if current_stateful_hyper_graph_node_option.is_none() {
-                                    current_stateful_hyper_graph_node_index_option = Some(stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].len());
-                                    let is_hyper_graph_node_index_connected: BitVec = self.is_always_valid_cell_group_index_per_cell_group_index[current_indexed_element.index].clone();
-                                    let current_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(current_indexed_element.element.clone(), is_hyper_graph_node_index_connected)));
-                                    stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].push(current_stateful_hyper_graph_node.clone());
-                                    current_stateful_hyper_graph_node_option = Some(current_stateful_hyper_graph_node);
-                                }
-                                let mut other_stateful_hyper_graph_node_option: Option<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>> = None;
-                                let mut other_stateful_hyper_graph_node_index_option: Option<usize> = None;
-                                for (stateful_hyper_graph_node_index, stateful_hyper_graph_node) in stateful_hyper_graph_nodes_per_hyper_graph_node_index[other_indexed_element.index].iter().enumerate() {
-                                    if stateful_hyper_graph_node.borrow().state == other_indexed_element.element {
-                                        other_stateful_hyper_graph_node_option = Some(stateful_hyper_graph_node.clone());
-                                        other_stateful_hyper_graph_node_index_option = Some(stateful_hyper_graph_node_index);
-                                        break;
-                                    }
-                                }
-                                if other_stateful_hyper_graph_node_option.is_none() {
-                                    other_stateful_hyper_graph_node_index_option = Some(stateful_hyper_graph_nodes_per_hyper_graph_node_index[other_indexed_element.index].len());
-                                    let is_hyper_graph_node_index_connected: BitVec = self.is_always_valid_cell_group_index_per_cell_group_index[other_indexed_element.index].clone();
-                                    let other_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(other_indexed_element.element.clone(), is_hyper_graph_node_index_connected)));
-                                    stateful_hyper_graph_nodes_per_hyper_graph_node_index[other_indexed_element.index].push(other_stateful_hyper_graph_node.clone());
-                                    other_stateful_hyper_graph_node_option = Some(other_stateful_hyper_graph_node);
-                                }
-                                
-                                // set each as neighbors to each other
-                                let current_stateful_hyper_graph_node = current_stateful_hyper_graph_node_option.unwrap();
-                                let other_stateful_hyper_graph_node = other_stateful_hyper_graph_node_option.unwrap();
-                                connections_total += 1;
-                                debug!("connecting {} cell group at {:?} to {} cell group at {:?} totaling {}", other_indexed_element.index, other_stateful_hyper_graph_node.borrow().state, current_indexed_element.index, current_stateful_hyper_graph_node.borrow().state, connections_total);
-                                current_stateful_hyper_graph_node.borrow_mut().add_neighbor(other_indexed_element.index, other_stateful_hyper_graph_node.clone());
-                                other_stateful_hyper_graph_node.borrow_mut().add_neighbor(current_indexed_element.index, current_stateful_hyper_graph_node);
 
-                                // change to focus on this pair
-                                focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option = Some(vec![
-                                    (current_stateful_hyper_graph_node_index_option.unwrap(), current_indexed_element.index),
-                                    (other_stateful_hyper_graph_node_index_option.unwrap(), other_indexed_element.index)
-                                ]);
+                // create the stateful hyper graph node since it does not yet exist
+                let is_hyper_graph_node_index_connected: BitVec = is_always_valid_cell_group_index_per_cell_group_index[current_indexed_element.index].clone();
+                let current_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(current_indexed_element.element.clone(), is_hyper_graph_node_index_connected)));
+                stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].push(current_stateful_hyper_graph_node);
+            }
+        }
+        else {
+            // TODO treat each pair individually, iterating over each pair
+            for (current_indexed_element_index, current_indexed_element) in locations.iter().enumerate() {
+                for (other_indexed_element_index, other_indexed_element) in locations.iter().enumerate() {
+                    if current_indexed_element_index < other_indexed_element_index {
+                        let mut current_stateful_hyper_graph_node_option: Option<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>> = None;
+                        let mut current_stateful_hyper_graph_node_index_option: Option<usize> = None;
+                        for (stateful_hyper_graph_node_index, stateful_hyper_graph_node) in stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].iter().enumerate() {
+                            if stateful_hyper_graph_node.borrow().state == current_indexed_element.element {
+                                current_stateful_hyper_graph_node_option = Some(stateful_hyper_graph_node.clone());
+                                current_stateful_hyper_graph_node_index_option = Some(stateful_hyper_graph_node_index);
+                                break;
                             }
                         }
-                    }
-                }
-
-                {
-                    // check to see if it is worth looking for the cliche
-                    let mut is_all_focused_stateful_hyper_graph_nodes_fully_connected = true;
-                    if let Some(focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples) = &focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option {
-                        for (stateful_hyper_graph_node_index, hyper_graph_node_index) in focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples.iter() {
-                            if !stateful_hyper_graph_nodes_per_hyper_graph_node_index[*hyper_graph_node_index][*stateful_hyper_graph_node_index].borrow().is_connected_to_all_hyper_graph_nodes() {
-                                is_all_focused_stateful_hyper_graph_nodes_fully_connected = false;
+                        if current_stateful_hyper_graph_node_option.is_none() {
+                            current_stateful_hyper_graph_node_index_option = Some(stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].len());
+                            let is_hyper_graph_node_index_connected: BitVec = is_always_valid_cell_group_index_per_cell_group_index[current_indexed_element.index].clone();
+                            let current_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(current_indexed_element.element.clone(), is_hyper_graph_node_index_connected)));
+                            stateful_hyper_graph_nodes_per_hyper_graph_node_index[current_indexed_element.index].push(current_stateful_hyper_graph_node.clone());
+                            current_stateful_hyper_graph_node_option = Some(current_stateful_hyper_graph_node);
+                        }
+                        let mut other_stateful_hyper_graph_node_option: Option<Rc<RefCell<StatefulHyperGraphNode<(u8, u8)>>>> = None;
+                        let mut other_stateful_hyper_graph_node_index_option: Option<usize> = None;
+                        for (stateful_hyper_graph_node_index, stateful_hyper_graph_node) in stateful_hyper_graph_nodes_per_hyper_graph_node_index[other_indexed_element.index].iter().enumerate() {
+                            if stateful_hyper_graph_node.borrow().state == other_indexed_element.element {
+                                other_stateful_hyper_graph_node_option = Some(stateful_hyper_graph_node.clone());
+                                other_stateful_hyper_graph_node_index_option = Some(stateful_hyper_graph_node_index);
                                 break;
                             }
                         }
-                    }
-                    if is_all_focused_stateful_hyper_graph_nodes_fully_connected {
-
-                        // look for cliches given the stateful hyper graph nodes of the latest set of provided location pairs
-                        let mut hyper_graph_cliche_shifter = HyperGraphClicheShifter::new_with_islands(stateful_hyper_graph_nodes_per_hyper_graph_node_index.clone(), self.is_always_valid_cell_group_index_per_cell_group_index.clone());
-                        if let Some(focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples) = &focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option {
-                            hyper_graph_cliche_shifter.focus_on_neighbors(focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples.clone());
+                        if other_stateful_hyper_graph_node_option.is_none() {
+                            other_stateful_hyper_graph_node_index_option = Some(stateful_hyper_graph_nodes_per_hyper_graph_node_index[other_indexed_element.index].len());
+                            let is_hyper_graph_node_index_connected: BitVec = is_always_valid_cell_group_index_per_cell_group_index[other_indexed_element.index].clone();
+                            let other_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(other_indexed_element.element.clone(), is_hyper_graph_node_index_connected)));
+                            stateful_hyper_graph_nodes_per_hyper_graph_node_index[other_indexed_element.index].push(other_stateful_hyper_graph_node.clone());
+                            other_stateful_hyper_graph_node_option = Some(other_stateful_hyper_graph_node);
                         }
 
-                        {
-                            let mut shifter_incrementer = ShifterIncrementer::new(Box::new(hyper_graph_cliche_shifter), (0..stateful_hyper_graph_nodes_per_hyper_graph_node_index.len()).collect());
-                            if shifter_incrementer.try_increment() {
-                                // found cliche
-                                debug!("cliche found");
-                                let cliche = shifter_incrementer.get();
-                                let mut random_pixel_board: PixelBoard<TPixel> = PixelBoard::new(self.pixel_board.get_width(), self.pixel_board.get_height());
-                                for indexed_element in cliche {
-                                    let location = *indexed_element.element.as_ref();
-                                    for cell in self.cell_groups[indexed_element.index].cells.iter() {
-                                        let calculated_pixel_board_index_x: usize = (location.0 + cell.0) as usize;
-                                        let calculated_pixel_board_index_y: usize = (location.1 + cell.1) as usize;
-                                        let pixel_board_coordinate = self.pixel_board_coordinate_per_cell_group_index[indexed_element.index];
-                                        let original_pixel_board_index_x: usize = (cell.0 as usize + pixel_board_coordinate.0);
-                                        let original_pixel_board_index_y: usize = (cell.1 as usize + pixel_board_coordinate.1);
-                                        random_pixel_board.set(calculated_pixel_board_index_x, calculated_pixel_board_index_y, self.pixel_board.get(original_pixel_board_index_x, original_pixel_board_index_y).unwrap());
-                                    }
-                                }
-                                return random_pixel_board;
-                            }
-                            else {
-                                debug!("cliche not found");
-                            }
-                        }
+                        // set each as neighbors to each other
+                        let current_stateful_hyper_graph_node = current_stateful_hyper_graph_node_option.unwrap();
+                        let other_stateful_hyper_graph_node = other_stateful_hyper_graph_node_option.unwrap();
+                        *connections_total += 1;
+                        debug!("connecting {} cell group at {:?} to {} cell group at {:?} totaling {}", other_indexed_element.index, other_stateful_hyper_graph_node.borrow().state, current_indexed_element.index, current_stateful_hyper_graph_node.borrow().state, connections_total);
+                        current_stateful_hyper_graph_node.borrow_mut().add_neighbor(other_indexed_element.index, other_stateful_hyper_graph_node.clone());
+                        other_stateful_hyper_graph_node.borrow_mut().add_neighbor(current_indexed_element.index, current_stateful_hyper_graph_node);
+
+                        // change to focus on this pair
+                        focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option = Some(vec![
+                            (current_stateful_hyper_graph_node_index_option.unwrap(), current_indexed_element.index),
+                            (other_stateful_hyper_graph_node_index_option.unwrap(), other_indexed_element.index)
+                        ]);
                     }
                 }
             }
-            else {
-                debug!("round robin done incrementing");
+        }
+        return focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples_option;
+    }
+    // constructs the pixel board with each cell group at the provided location
+    // reads the current location of each cell group by state index so that no location is cloned out of the incrementer
+    fn get_pixel_board_from_incrementer(&self, incrementer: &dyn Incrementer<T = (u8, u8)>) -> PixelBoard<TPixel> {
+        let locations = incrementer.get_element_index_and_state_index_pairs().unwrap()
+            .iter()
+            .map(|(cell_group_index, state_index)| (*cell_group_index, *incrementer.get_state(*state_index).unwrap()));
+        return self.get_pixel_board_from_locations(locations);
+    }
+    fn get_pixel_board_from_locations(&self, locations: impl IntoIterator<Item = (usize, (u8, u8))>) -> PixelBoard<TPixel> {
+        let mut random_pixel_board: PixelBoard<TPixel> = PixelBoard::new(self.pixel_board.get_width(), self.pixel_board.get_height());
+        for (cell_group_index, location) in locations {
+            for cell in self.cell_groups[cell_group_index].cells.iter() {
+                let calculated_pixel_board_index_x: usize = (location.0 + cell.0) as usize;
+                let calculated_pixel_board_index_y: usize = (location.1 + cell.1) as usize;
+                let pixel_board_coordinate = self.pixel_board_coordinate_per_cell_group_index[cell_group_index];
+                let original_pixel_board_index_x: usize = (cell.0 as usize + pixel_board_coordinate.0);
+                let original_pixel_board_index_y: usize = (cell.1 as usize + pixel_board_coordinate.1);
+                random_pixel_board.set(calculated_pixel_board_index_x, calculated_pixel_board_index_y, self.pixel_board.get(original_pixel_board_index_x, original_pixel_board_index_y).unwrap());
             }
         }
-
-        panic!("Unexpected failure to find the original placement, let alone a new random one.");
+        return random_pixel_board;
     }
 }
 
 // TODO add test where the walls are double thick and there is a single, adjacent floater
 #[cfg(test)]
 mod pixel_board_randomizer_tests {
-    use std::{time::{Duration, Instant}, cell::RefCell};
+    use std::{time::{Duration, Instant}, cell::{Cell, RefCell}};
 
     use super::*;
     use rstest::rstest;
@@ -2936,6 +3332,367 @@ mod pixel_board_randomizer_tests {
         }
     }
 
+    #[rstest]
+    fn top_wall_segments_one_alone_with_location_weights() {
+        let segment_image_id = Uuid::new_v4().to_string();
+        let board_width = 6;
+        let board_height = 3;
+        let mut pixel_board = PixelBoard::new(board_width, board_height);
+        pixel_board.set(1, 0, Rc::new(RefCell::new(ExamplePixel::Tile(Tile {
+            image_id: segment_image_id
+        }))));
+        let pixel_board_randomizer = PixelBoardRandomizer::new_with_location_weights(pixel_board, Rc::new(|pixel_board_coordinate: (usize, usize), location: (u8, u8)| -> f32 {
+            assert_eq!((1, 0), pixel_board_coordinate);
+            if location == (3, 0) {
+                return 3.0;
+            }
+            return 1.0;
+        }));
+        let mut count_per_x: BTreeMap<usize, usize> = BTreeMap::new();
+        let iterations_total = 3000;
+        for _ in 0..iterations_total {
+            let random_pixel_board = pixel_board_randomizer.get_random_pixel_board();
+            for x in 0..board_width {
+                if random_pixel_board.exists(x, 0) {
+                    *count_per_x.entry(x).or_insert(0) += 1;
+                }
+            }
+        }
+        println!("count_per_x: {:?}", count_per_x);
+        for x in 1..(board_width - 1) {
+            let count = *count_per_x.get(&x).unwrap_or(&0) as f32;
+            let expected: f32;
+            if x == 3 {
+                expected = iterations_total as f32 * 3.0 / 6.0;
+            }
+            else {
+                expected = iterations_total as f32 / 6.0;
+            }
+            assert!((expected - count).abs() < (iterations_total as f32 / 20.0));
+        }
+    }
+
+    #[rstest]
+    fn uniformly_random_pixel_board_is_uniform() {
+        init();
+
+        // the top wall has two single pixels and the left wall has one single pixel
+        let image_id = Uuid::new_v4().to_string();
+        let board_width = 7;
+        let board_height = 5;
+        let mut pixel_board = PixelBoard::new(board_width, board_height);
+        for (x, y) in [(1, 0), (3, 0), (0, 2)] {
+            pixel_board.set(x, y, Rc::new(RefCell::new(ExamplePixel::Tile(Tile {
+                image_id: image_id.clone()
+            }))));
+        }
+        let pixel_board_randomizer = PixelBoardRandomizer::new(pixel_board);
+
+        // the top wall has 6 arrangements and the left wall has 3 positions
+        let expected_pixel_boards_total = 18;
+        let iterations_total = 3600;
+        let mut count_per_pixel_locations: BTreeMap<Vec<(usize, usize)>, usize> = BTreeMap::new();
+        for _ in 0..iterations_total {
+            let random_pixel_board = pixel_board_randomizer.get_uniformly_random_pixel_board();
+            let mut pixel_locations: Vec<(usize, usize)> = Vec::new();
+            for y in 0..board_height {
+                for x in 0..board_width {
+                    if random_pixel_board.exists(x, y) {
+                        pixel_locations.push((x, y));
+                    }
+                }
+            }
+            assert_eq!(3, pixel_locations.len());
+            *count_per_pixel_locations.entry(pixel_locations).or_insert(0) += 1;
+        }
+        println!("count_per_pixel_locations: {:?}", count_per_pixel_locations);
+        assert_eq!(expected_pixel_boards_total, count_per_pixel_locations.len());
+
+        // chi-squared goodness of fit against the uniform distribution, where 40.79 is the critical value for 17 degrees of freedom at p = 0.001
+        let expected = iterations_total as f32 / expected_pixel_boards_total as f32;
+        let mut chi_squared = 0.0;
+        for count in count_per_pixel_locations.values() {
+            chi_squared += (*count as f32 - expected).powi(2) / expected;
+        }
+        println!("chi_squared: {chi_squared}");
+        assert!(chi_squared < 40.79);
+    }
+
+    #[rstest]
+    fn uniformly_random_pixel_board_is_uniform_with_interchangeable_floaters() {
+        init();
+
+        // two floaters with the same pixel can trade locations, so every pixel board is reached by two cliches
+        let image_id = Uuid::new_v4().to_string();
+        let board_width = 6;
+        let board_height = 6;
+        let mut pixel_board = PixelBoard::new(board_width, board_height);
+        for (x, y) in [(1, 1), (3, 3)] {
+            pixel_board.set(x, y, Rc::new(RefCell::new(ExamplePixel::Tile(Tile {
+                image_id: image_id.clone()
+            }))));
+        }
+        let pixel_board_randomizer = PixelBoardRandomizer::new(pixel_board);
+
+        // the 120 pairs of the inner 4x4 locations less the 24 pairs that are next to each other
+        let expected_pixel_boards_total = 96;
+        let iterations_total = 9600;
+        let mut count_per_pixel_locations: BTreeMap<Vec<(usize, usize)>, usize> = BTreeMap::new();
+        for _ in 0..iterations_total {
+            let random_pixel_board = pixel_board_randomizer.get_uniformly_random_pixel_board();
+            let mut pixel_locations: Vec<(usize, usize)> = Vec::new();
+            for y in 0..board_height {
+                for x in 0..board_width {
+                    if random_pixel_board.exists(x, y) {
+                        pixel_locations.push((x, y));
+                    }
+                }
+            }
+            assert_eq!(2, pixel_locations.len());
+            *count_per_pixel_locations.entry(pixel_locations).or_insert(0) += 1;
+        }
+        assert_eq!(expected_pixel_boards_total, count_per_pixel_locations.len());
+
+        // chi-squared goodness of fit against the uniform distribution, where 143.40 is the critical value for 95 degrees of freedom at p = 0.001
+        let expected = iterations_total as f32 / expected_pixel_boards_total as f32;
+        let mut chi_squared = 0.0;
+        for count in count_per_pixel_locations.values() {
+            chi_squared += (*count as f32 - expected).powi(2) / expected;
+        }
+        println!("chi_squared: {chi_squared}");
+        assert!(chi_squared < 143.40);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(4)]
+    #[case(NOGOOD_CACHE_CAPACITY)]
+    fn nogood_cache_is_shared_across_calls(#[case] nogood_cache_capacity: usize) {
+        init();
+
+        // two floaters that may neither overlap nor be next to each other
+        let image_id = Uuid::new_v4().to_string();
+        let board_width = 6;
+        let board_height = 6;
+        let mut pixel_board = PixelBoard::new(board_width, board_height);
+        for (x, y) in [(1, 1), (3, 3)] {
+            pixel_board.set(x, y, Rc::new(RefCell::new(ExamplePixel::Tile(Tile {
+                image_id: image_id.clone()
+            }))));
+        }
+        let mut pixel_board_randomizer = PixelBoardRandomizer::new(pixel_board);
+        pixel_board_randomizer.set_nogood_cache_capacity(nogood_cache_capacity);
+
+        let mut pixel_locations_per_pixel_board: BTreeSet<Vec<(usize, usize)>> = BTreeSet::new();
+        for _ in 0..200 {
+            let random_pixel_board = pixel_board_randomizer.get_random_pixel_board();
+            let mut pixel_locations: Vec<(usize, usize)> = Vec::new();
+            for y in 0..board_height {
+                for x in 0..board_width {
+                    if random_pixel_board.exists(x, y) {
+                        assert!(x != 0 && y != 0 && x != board_width - 1 && y != board_height - 1);
+                        pixel_locations.push((x, y));
+                    }
+                }
+            }
+            assert_eq!(2, pixel_locations.len());
+            assert!(pixel_locations[0].0.abs_diff(pixel_locations[1].0) + pixel_locations[0].1.abs_diff(pixel_locations[1].1) > 1);
+            pixel_locations_per_pixel_board.insert(pixel_locations);
+        }
+        assert!(pixel_locations_per_pixel_board.len() > 1);
+
+        // the invalid pairs found by earlier calls remain for later calls
+        let nogoods_total = pixel_board_randomizer.nogood_cache.borrow().len();
+        if nogood_cache_capacity == 0 {
+            assert_eq!(0, nogoods_total);
+        }
+        else {
+            assert!(nogoods_total > 0);
+            assert!(nogoods_total <= nogood_cache_capacity);
+        }
+    }
+
+    #[rstest]
+    #[case(PixelBoardSchedulingPolicy::RoundRobin)]
+    #[case(PixelBoardSchedulingPolicy::YieldRate)]
+    #[case(PixelBoardSchedulingPolicy::Priority)]
+    fn scheduling_policy_finds_valid_pixel_boards(#[case] scheduling_policy: PixelBoardSchedulingPolicy) {
+        init();
+
+        // two floaters that may neither overlap nor be next to each other
+        let image_id = Uuid::new_v4().to_string();
+        let board_width = 6;
+        let board_height = 6;
+        let mut pixel_board = PixelBoard::new(board_width, board_height);
+        for (x, y) in [(1, 1), (3, 3)] {
+            pixel_board.set(x, y, Rc::new(RefCell::new(ExamplePixel::Tile(Tile {
+                image_id: image_id.clone()
+            }))));
+        }
+        let mut pixel_board_randomizer = PixelBoardRandomizer::new(pixel_board);
+        pixel_board_randomizer.set_scheduling_policy(scheduling_policy);
+
+        let mut pixel_locations_per_pixel_board: BTreeSet<Vec<(usize, usize)>> = BTreeSet::new();
+        for _ in 0..100 {
+            let random_pixel_board = pixel_board_randomizer.get_random_pixel_board();
+            let mut pixel_locations: Vec<(usize, usize)> = Vec::new();
+            for y in 0..board_height {
+                for x in 0..board_width {
+                    if random_pixel_board.exists(x, y) {
+                        pixel_locations.push((x, y));
+                    }
+                }
+            }
+            assert_eq!(2, pixel_locations.len());
+            assert!(pixel_locations[0].0.abs_diff(pixel_locations[1].0) + pixel_locations[0].1.abs_diff(pixel_locations[1].1) > 1);
+            pixel_locations_per_pixel_board.insert(pixel_locations);
+        }
+        assert!(pixel_locations_per_pixel_board.len() > 1);
+    }
+
+    #[rstest]
+    fn priority_scheduling_follows_cliche_progress() {
+        init();
+
+        // each incrementer places its cell groups at one of two locations
+        let cell_group_indexes_per_incrementer_index: Vec<Vec<usize>> = vec![
+            vec![0],
+            vec![1, 2],
+            vec![2]
+        ];
+        let mut incrementers: Vec<Box<dyn Incrementer<T = (u8, u8)>>> = Vec::new();
+        for cell_group_indexes in cell_group_indexes_per_incrementer_index.iter() {
+            let states_per_shift_index: Vec<Vec<Rc<(u8, u8)>>> = cell_group_indexes.iter().map(|_| vec![Rc::new((0, 0)), Rc::new((1, 0))]).collect();
+            incrementers.push(Box::new(ShifterIncrementer::new(Box::new(IndexShifter::new(&states_per_shift_index)), cell_group_indexes.clone())));
+        }
+        let mut round_robin_incrementer = RoundRobinIncrementer::new_with_scheduling_policy(incrementers, SchedulingPolicy::Priority);
+
+        // the first cell group already has a location connected to every other cell group, so the incrementer over the most unconnected cell groups goes first
+        let mut is_fully_connected_per_cell_group_index: BitVec = BitVec::repeat(false, 3);
+        is_fully_connected_per_cell_group_index.set(0, true);
+        PixelBoardRandomizer::<ExamplePixel>::set_priorities_from_cliche_progress(&mut round_robin_incrementer, &cell_group_indexes_per_incrementer_index, &is_fully_connected_per_cell_group_index);
+        let mut cell_group_indexes_per_increment: Vec<Vec<usize>> = Vec::new();
+        for _ in 0..2 {
+            assert!(round_robin_incrementer.try_increment());
+            cell_group_indexes_per_increment.push(round_robin_incrementer.get().iter().map(|indexed_element| indexed_element.index).collect());
+        }
+
+        // once the second cell group is connected, the incrementer over only the third cell group is just as important and so they take turns
+        is_fully_connected_per_cell_group_index.set(1, true);
+        PixelBoardRandomizer::<ExamplePixel>::set_priorities_from_cliche_progress(&mut round_robin_incrementer, &cell_group_indexes_per_incrementer_index, &is_fully_connected_per_cell_group_index);
+        while round_robin_incrementer.try_increment() {
+            cell_group_indexes_per_increment.push(round_robin_incrementer.get().iter().map(|indexed_element| indexed_element.index).collect());
+        }
+        assert_eq!(vec![
+            vec![1, 2],
+            vec![1, 2],
+            vec![2],
+            vec![1, 2],
+            vec![2],
+            vec![1, 2],
+            vec![0],
+            vec![0]
+        ], cell_group_indexes_per_increment);
+    }
+
+    #[rstest]
+    #[case(1, false)]
+    #[case(1, true)]
+    #[case(100, false)]
+    #[case(100, true)]
+    fn best_pixel_board_prefers_highest_score(#[case] scored_cliches_total_maximum: usize, #[case] is_decomposable: bool) {
+        init();
+
+        let image_id = Uuid::new_v4().to_string();
+        let board_width = 7;
+        let board_height = 5;
+        let mut pixel_board = PixelBoard::new(board_width, board_height);
+        for (x, y) in [(1, 0), (3, 0), (0, 2)] {
+            pixel_board.set(x, y, Rc::new(RefCell::new(ExamplePixel::Tile(Tile {
+                image_id: image_id.clone()
+            }))));
+        }
+        let pixel_board_randomizer = PixelBoardRandomizer::new(pixel_board);
+
+        // prefer the pixels being as far right and down as possible
+        let scored_pixel_boards_total: Cell<usize> = Cell::new(0);
+        let best_pixel_board = if is_decomposable {
+            pixel_board_randomizer.get_best_pixel_board_with_cell_group_scores(&|_: (usize, usize), location: (u8, u8)| -> f32 {
+                return (location.0 + location.1) as f32;
+            }, scored_cliches_total_maximum)
+        }
+        else {
+            pixel_board_randomizer.get_best_pixel_board(&|pixel_board: &PixelBoard<ExamplePixel>| -> f32 {
+                scored_pixel_boards_total.set(scored_pixel_boards_total.get() + 1);
+                let mut score = 0.0;
+                for y in 0..pixel_board.get_height() {
+                    for x in 0..pixel_board.get_width() {
+                        if pixel_board.exists(x, y) {
+                            score += (x + y) as f32;
+                        }
+                    }
+                }
+                return score;
+            }, scored_cliches_total_maximum)
+        };
+
+        let mut pixel_locations: Vec<(usize, usize)> = Vec::new();
+        for y in 0..board_height {
+            for x in 0..board_width {
+                if best_pixel_board.exists(x, y) {
+                    pixel_locations.push((x, y));
+                }
+            }
+        }
+        assert_eq!(3, pixel_locations.len());
+        if scored_cliches_total_maximum == 100 || is_decomposable {
+            // every arrangement fits within the budget or the search is guided toward the best arrangement first
+            assert_eq!(vec![(3, 0), (5, 0), (0, 3)], pixel_locations);
+        }
+        else {
+            // only the first arrangement found is scored, which must still be one of the 18 valid arrangements
+            let top_wall_xs: Vec<usize> = pixel_locations.iter().filter(|(_, y)| *y == 0).map(|(x, _)| *x).collect();
+            assert_eq!(2, top_wall_xs.len());
+            assert!(top_wall_xs.iter().all(|x| (1..=5).contains(x)));
+            assert!(top_wall_xs[1] - top_wall_xs[0] > 1);
+            let left_wall_ys: Vec<usize> = pixel_locations.iter().filter(|(x, _)| *x == 0).map(|(_, y)| *y).collect();
+            assert_eq!(1, left_wall_ys.len());
+            assert!((1..=3).contains(&left_wall_ys[0]));
+        }
+        if !is_decomposable {
+            assert_eq!(scored_cliches_total_maximum.min(18), scored_pixel_boards_total.get());
+        }
+    }
+
+    #[rstest]
+    fn top_left_corner_wall_with_wall_adjacent_and_location_weights() {
+        let board_width = 4;
+        let wall_height = 6;
+        let mut pixel_board: PixelBoard<ExamplePixel> = PixelBoard::new(board_width, wall_height);
+        for height_index in 0..(wall_height - 1) {
+            pixel_board.set(0, height_index, Rc::new(RefCell::new(ExamplePixel::Tile(Tile {
+                image_id: Uuid::new_v4().to_string()
+            }))));
+        }
+        pixel_board.set(1, 1, Rc::new(RefCell::new(ExamplePixel::Tile(Tile {
+            image_id: Uuid::new_v4().to_string()
+        }))));
+        // only the location at the bottom of the wall has any weight
+        let pixel_board_randomizer = PixelBoardRandomizer::new_with_location_weights(pixel_board, Rc::new(|_: (usize, usize), location: (u8, u8)| -> f32 {
+            if location == (1, 4) {
+                return 1.0;
+            }
+            return 0.0;
+        }));
+        for _ in 0..100 {
+            let random_pixel_board = pixel_board_randomizer.get_random_pixel_board();
+            assert!(random_pixel_board.exists(1, 4));
+            for height_index in 0..4 {
+                assert!(!random_pixel_board.exists(1, height_index));
+            }
+        }
+    }
+
     #[rstest]
     fn bottom_wall_segments_one_alone() {
         let segment_image_id = Uuid::new_v4().to_string();
@@ -0,0 +1,239 @@
+use std::rc::Rc;
+use crate::IndexedElement;
+use super::{Shifter, segment_permutation_shifter::{Segment, SegmentPermutationShifter}};
+
+// Purpose:
+//     To shift segments along a closed loop of locations, where the last location of the path neighbors the first location, such that segments may wrap around from the end of the path to the start
+//     The first segment is anchored at each location of the path in turn while the remaining segments are shifted by a SegmentPermutationShifter along the part of the loop after the first segment and its padding, leaving the padding before the first segment free
+//     Anchoring the first segment ensures that each arrangement around the loop is returned exactly once
+pub struct ClosedLoopSegmentPermutationShifter {
+    segments_length: usize,
+    states: Vec<Rc<(u8, u8)>>,
+    remaining_position_offset: usize,
+    remaining_segment_permutation_shifter_option: Option<SegmentPermutationShifter>,
+    anchor_position_offsets: Vec<usize>,
+    current_shift_index: Option<usize>,
+    current_anchor_position_offsets_index: Option<usize>
+}
+
+impl ClosedLoopSegmentPermutationShifter {
+    pub fn new(segments: Vec<Rc<Segment>>, path: Vec<(u8, u8)>, padding: usize, is_swapping_permitted: bool) -> Self {
+        if segments.is_empty() {
+            panic!("Unexpected closed loop without any segments.");
+        }
+        let path_length = path.len();
+        let mut minimum_path_length: usize = segments.iter().map(|segment| segment.get_length()).sum();
+        if segments.len() != 1 {
+            // every segment is followed by padding, including the last segment before the loop returns to the first segment
+            minimum_path_length += padding * segments.len();
+        }
+        if minimum_path_length > path_length {
+            panic!("Unexpected segments not fitting within the closed loop.");
+        }
+
+        let remaining_position_offset = segments[0].get_length() + padding;
+        let remaining_segment_permutation_shifter_option = if segments.len() == 1 {
+            None
+        }
+        else {
+            // the remaining segments are shifted along the locations after the first segment at position offset zero, leaving the padding before the first segment free
+            let remaining_path_length = path_length - remaining_position_offset - padding;
+            let remaining_path: Vec<(u8, u8)> = (0..remaining_path_length)
+                .map(|remaining_path_index| path[(remaining_position_offset + remaining_path_index) % path_length])
+                .collect();
+            Some(SegmentPermutationShifter::new_along_path(segments[1..].to_vec(), remaining_path, padding, is_swapping_permitted))
+        };
+        ClosedLoopSegmentPermutationShifter {
+            segments_length: segments.len(),
+            states: path.into_iter().map(Rc::new).collect(),
+            remaining_position_offset: remaining_position_offset,
+            remaining_segment_permutation_shifter_option: remaining_segment_permutation_shifter_option,
+            anchor_position_offsets: (0..path_length).collect(),
+            current_shift_index: None,
+            current_anchor_position_offsets_index: None
+        }
+    }
+}
+
+impl Shifter for ClosedLoopSegmentPermutationShifter {
+    type T = (u8, u8);
+
+    fn try_forward(&mut self) -> bool {
+        match self.current_shift_index {
+            Some(current_shift_index) => {
+                if current_shift_index == self.segments_length {
+                    return false;
+                }
+                self.current_shift_index = Some(current_shift_index + 1);
+                let mut is_forward = false;
+                if let Some(remaining_segment_permutation_shifter) = self.remaining_segment_permutation_shifter_option.as_mut() {
+                    is_forward = remaining_segment_permutation_shifter.try_forward();
+                }
+                return is_forward;
+            },
+            None => {
+                self.current_shift_index = Some(0);
+                self.current_anchor_position_offsets_index = None;
+                return true;
+            }
+        }
+    }
+    fn try_backward(&mut self) -> bool {
+        match self.current_shift_index {
+            Some(current_shift_index) => {
+                if current_shift_index == 0 {
+                    self.current_shift_index = None;
+                    return false;
+                }
+                if let Some(remaining_segment_permutation_shifter) = self.remaining_segment_permutation_shifter_option.as_mut() {
+                    remaining_segment_permutation_shifter.try_backward();
+                }
+                self.current_shift_index = Some(current_shift_index - 1);
+                return true;
+            },
+            None => {
+                return false;
+            }
+        }
+    }
+    fn try_increment(&mut self) -> bool {
+        match self.current_shift_index {
+            Some(0) => {
+                let next_anchor_position_offsets_index = match self.current_anchor_position_offsets_index {
+                    Some(current_anchor_position_offsets_index) => current_anchor_position_offsets_index + 1,
+                    None => 0
+                };
+                if next_anchor_position_offsets_index == self.anchor_position_offsets.len() {
+                    return false;
+                }
+                self.current_anchor_position_offsets_index = Some(next_anchor_position_offsets_index);
+                return true;
+            },
+            Some(current_shift_index) => {
+                if current_shift_index == self.segments_length {
+                    return false;
+                }
+                return self.remaining_segment_permutation_shifter_option.as_mut().unwrap().try_increment();
+            },
+            None => {
+                return false;
+            }
+        }
+    }
+    fn get_indexed_element(&self) -> IndexedElement<Self::T> {
+        let (element_index, state_index) = self.get_element_index_and_state_index();
+        return IndexedElement::new(self.states[state_index].clone(), element_index);
+    }
+    fn get_length(&self) -> usize {
+        return self.segments_length;
+    }
+    fn get_element_index_and_state_index(&self) -> (usize, usize) {
+        let anchor_position_offset = self.anchor_position_offsets[self.current_anchor_position_offsets_index.unwrap()];
+        if self.current_shift_index.unwrap() == 0 {
+            return (0, anchor_position_offset);
+        }
+        let (remaining_segment_index, remaining_position_offset) = self.remaining_segment_permutation_shifter_option.as_ref().unwrap().get_element_index_and_state_index();
+        return (remaining_segment_index + 1, (anchor_position_offset + self.remaining_position_offset + remaining_position_offset) % self.states.len());
+    }
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.states;
+    }
+    fn randomize(&mut self) {
+        fastrand::shuffle(&mut self.anchor_position_offsets);
+        if let Some(remaining_segment_permutation_shifter) = self.remaining_segment_permutation_shifter_option.as_mut() {
+            remaining_segment_permutation_shifter.randomize();
+        }
+    }
+}
+
+#[cfg(test)]
+mod closed_loop_segment_permutation_shifter_tests {
+    use std::collections::BTreeSet;
+
+    use crate::incrementer::{Incrementer, shifter_incrementer::ShifterIncrementer};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    // returns the position offset of each segment index for every arrangement around the loop, found by checking every combination of position offsets
+    fn get_looped_arrangements(segment_lengths: &Vec<usize>, path_length: usize, padding: usize, is_swapping_permitted: bool) -> BTreeSet<Vec<usize>> {
+        let mut arrangements: BTreeSet<Vec<usize>> = BTreeSet::new();
+        let mut position_offsets: Vec<usize> = vec![0; segment_lengths.len()];
+        'arrangement: loop {
+            let mut is_valid = true;
+            for (segment_index, position_offset) in position_offsets.iter().enumerate() {
+                for (other_segment_index, other_position_offset) in position_offsets.iter().enumerate() {
+                    if segment_index != other_segment_index {
+                        // the other segment must start after this segment and its padding and end before this segment starts again
+                        let distance = (other_position_offset + path_length - position_offset) % path_length;
+                        if distance < segment_lengths[segment_index] + padding || distance + segment_lengths[other_segment_index] > path_length - padding {
+                            is_valid = false;
+                        }
+                    }
+                }
+            }
+            if is_valid && !is_swapping_permitted && segment_lengths.len() > 2 {
+                // the segments must be in their original order around the loop, starting from the first segment
+                let distances: Vec<usize> = position_offsets.iter()
+                    .map(|position_offset| (position_offset + path_length - position_offsets[0]) % path_length)
+                    .collect();
+                is_valid = distances.windows(2).all(|distance_pair| distance_pair[0] < distance_pair[1]);
+            }
+            if is_valid {
+                arrangements.insert(position_offsets.clone());
+            }
+            for segment_index in 0..segment_lengths.len() {
+                position_offsets[segment_index] += 1;
+                if position_offsets[segment_index] != path_length {
+                    continue 'arrangement;
+                }
+                position_offsets[segment_index] = 0;
+            }
+            break;
+        }
+        return arrangements;
+    }
+
+    #[rstest]
+    #[case(vec![2], 4, 1, false)]
+    #[case(vec![1, 1], 5, 1, false)]
+    #[case(vec![1, 2, 1], 8, 1, false)]
+    #[case(vec![1, 2, 1], 8, 1, true)]
+    #[case(vec![1, 1, 1, 1], 7, 0, true)]
+    fn each_arrangement_around_loop_once(#[case] segment_lengths: Vec<usize>, #[case] path_length: usize, #[case] padding: usize, #[case] is_swapping_permitted: bool) {
+        init();
+
+        let expected_arrangements = get_looped_arrangements(&segment_lengths, path_length, padding, is_swapping_permitted);
+        assert!(expected_arrangements.len() > 1);
+        let segments: Vec<Rc<Segment>> = segment_lengths.iter().map(|segment_length| Rc::new(Segment::new(*segment_length))).collect();
+        // the path is a ring of locations where the x coordinate is the position offset
+        let path: Vec<(u8, u8)> = (0..path_length as u8).map(|x| (x, 7)).collect();
+        for is_randomized in [false, true] {
+            let mut closed_loop_segment_permutation_shifter = ClosedLoopSegmentPermutationShifter::new(segments.clone(), path.clone(), padding, is_swapping_permitted);
+            if is_randomized {
+                closed_loop_segment_permutation_shifter.randomize();
+            }
+            let shifter_incrementer = ShifterIncrementer::new(Box::new(closed_loop_segment_permutation_shifter), (0..segment_lengths.len()).collect());
+            let mut arrangements: BTreeSet<Vec<usize>> = BTreeSet::new();
+            for indexed_elements in shifter_incrementer.into_incrementer_iter() {
+                let mut position_offsets: Vec<usize> = vec![0; segment_lengths.len()];
+                for indexed_element in indexed_elements.iter() {
+                    position_offsets[indexed_element.index] = indexed_element.element.0 as usize;
+                }
+                assert!(arrangements.insert(position_offsets.clone()), "arrangement {position_offsets:?} returned more than once when randomized {is_randomized}");
+            }
+            assert_eq!(expected_arrangements, arrangements, "randomized {is_randomized}");
+        }
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn segments_must_fit_within_loop() {
+        ClosedLoopSegmentPermutationShifter::new(vec![Rc::new(Segment::new(2)), Rc::new(Segment::new(2))], vec![(0, 0), (1, 0), (1, 1), (0, 1), (0, 2)], 1, false);
+    }
+}
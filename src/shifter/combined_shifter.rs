@@ -1,6 +1,6 @@
-use std::{rc::Rc, cell::RefCell};
+use std::{rc::Rc, cell::RefCell, collections::{HashMap, hash_map::RandomState}, hash::{Hash, BuildHasher}};
 use crate::IndexedElement;
-use super::{Shifter};
+use super::{Shifter, ShiftOrdering, get_or_push_state_index, get_or_push_hashed_state_index};
 
 #[derive(Clone)]
 pub struct CombinedShifter<T> {
@@ -10,11 +10,12 @@ pub struct CombinedShifter<T> {
     current_shifter_index: Option<usize>,
     index_offset_per_shifter: Vec<usize>,
     shifters_segments_length_total: usize,
-    is_shifter_order_preserved_on_randomize: bool
+    is_shifter_order_preserved_on_randomize: bool,
+    shift_ordering: ShiftOrdering
 }
 
-impl<T: PartialEq> CombinedShifter<T> {
-    pub fn new(shifters: &Vec<Rc<RefCell<dyn Shifter<T = T>>>>, is_shifter_order_preserved_on_randomize: bool) -> Self {
+impl<T> CombinedShifter<T> {
+    fn new_with_state_index_getter(shifters: &Vec<Rc<RefCell<dyn Shifter<T = T>>>>, is_shifter_order_preserved_on_randomize: bool, get_or_push_state_index: &mut dyn FnMut(&mut Vec<Rc<T>>, &Rc<T>) -> usize) -> Self {
         // TODO determine how to share this functionality between CombinedShifter and ShiftingSquareBreadthFirstSearchShifter
         let mut index_offset_per_shifter: Vec<usize> = Vec::new();
         let mut current_index_offset: usize = 0;
@@ -25,19 +26,7 @@ impl<T: PartialEq> CombinedShifter<T> {
             let mut state_index_mapping: Vec<usize> = Vec::new();
             let current_shifter_possible_states = shifter.borrow().get_states();
             for current_shifter_state in current_shifter_possible_states.iter() {
-                let mut existing_state_index: usize = 0;
-                let mut is_existing: bool = false;
-                for (possible_state_index, possible_state) in possible_states.iter().enumerate() {
-                    if possible_state == current_shifter_state {
-                        existing_state_index = possible_state_index;
-                        is_existing = true;
-                        break;
-                    }
-                }
-                if !is_existing {
-                    existing_state_index = possible_states.len();
-                    possible_states.push(current_shifter_state.clone());
-                }
+                let existing_state_index = get_or_push_state_index(&mut possible_states, current_shifter_state);
                 state_index_mapping.push(existing_state_index);
             }
             state_index_mapping_per_shifter_index.push(state_index_mapping);
@@ -50,9 +39,44 @@ impl<T: PartialEq> CombinedShifter<T> {
             current_shifter_index: None,
             index_offset_per_shifter: index_offset_per_shifter,
             shifters_segments_length_total: current_index_offset,
-            is_shifter_order_preserved_on_randomize: is_shifter_order_preserved_on_randomize
+            is_shifter_order_preserved_on_randomize: is_shifter_order_preserved_on_randomize,
+            shift_ordering: ShiftOrdering::Fixed
         }
     }
+    /// Reorders the shifters for the next traversal, keeping the element indexes of each shifter the same, so this should only be called before moving forward or after a reset
+    /// The minimum remaining values ordering places the shifters with the fewest distinct states first and the degree ordering places the shifters containing the highest degree elements first
+    pub fn set_shift_ordering(&mut self, shift_ordering: ShiftOrdering) {
+        self.shift_ordering = shift_ordering;
+        self.apply_shift_ordering();
+    }
+    fn apply_shift_ordering(&mut self) {
+        let states_length_per_shifter_index: Vec<usize> = self.state_index_mapping_per_shifter_index.iter().map(|state_index_mapping| state_index_mapping.len()).collect();
+        let element_indexes_per_shifter_index: Vec<Vec<usize>> = self.shifters.iter()
+            .zip(self.index_offset_per_shifter.iter())
+            .map(|(shifter, index_offset)| (*index_offset..(*index_offset + shifter.borrow().get_length())).collect())
+            .collect();
+        let shifter_indexes = self.shift_ordering.get_ordered_shift_indexes(&states_length_per_shifter_index, &element_indexes_per_shifter_index);
+        self.shifters = shifter_indexes.iter().map(|shifter_index| self.shifters[*shifter_index].clone()).collect();
+        self.state_index_mapping_per_shifter_index = shifter_indexes.iter().map(|shifter_index| self.state_index_mapping_per_shifter_index[*shifter_index].clone()).collect();
+        self.index_offset_per_shifter = shifter_indexes.iter().map(|shifter_index| self.index_offset_per_shifter[*shifter_index]).collect();
+    }
+}
+
+impl<T: PartialEq> CombinedShifter<T> {
+    pub fn new(shifters: &Vec<Rc<RefCell<dyn Shifter<T = T>>>>, is_shifter_order_preserved_on_randomize: bool) -> Self {
+        return Self::new_with_state_index_getter(shifters, is_shifter_order_preserved_on_randomize, &mut get_or_push_state_index);
+    }
+}
+
+impl<T: Eq + Hash> CombinedShifter<T> {
+    /// Produces the same shifter as new, but finds duplicate states across the shifters by hashing instead of comparing every pair of states
+    pub fn new_hashed(shifters: &Vec<Rc<RefCell<dyn Shifter<T = T>>>>, is_shifter_order_preserved_on_randomize: bool) -> Self {
+        return Self::new_hashed_with_hasher::<RandomState>(shifters, is_shifter_order_preserved_on_randomize);
+    }
+    pub fn new_hashed_with_hasher<S: BuildHasher + Default>(shifters: &Vec<Rc<RefCell<dyn Shifter<T = T>>>>, is_shifter_order_preserved_on_randomize: bool) -> Self {
+        let mut state_index_per_state: HashMap<Rc<T>, usize, S> = HashMap::default();
+        return Self::new_with_state_index_getter(shifters, is_shifter_order_preserved_on_randomize, &mut |possible_states, state| get_or_push_hashed_state_index(possible_states, &mut state_index_per_state, state));
+    }
 }
 
 impl<T> Shifter for CombinedShifter<T> {
@@ -118,8 +142,14 @@ impl<T> Shifter for CombinedShifter<T> {
     fn get_length(&self) -> usize {
         return self.shifters_segments_length_total;
     }
-    fn get_states(&self) -> Vec<Rc<Self::T>> {
-        return self.possible_states.clone();
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.possible_states;
+    }
+    fn is_each_shift_independent(&self) -> bool {
+        return self.shifters.iter().all(|shifter| shifter.borrow().is_each_shift_independent());
+    }
+    fn is_rotation_possible(&self) -> bool {
+        return self.shifters.iter().any(|shifter| shifter.borrow().is_rotation_possible());
     }
     fn randomize(&mut self) {
         // TODO determine if this misorders indexes - should a mapper be used and randomized instead?
@@ -129,6 +159,7 @@ impl<T> Shifter for CombinedShifter<T> {
         if !self.is_shifter_order_preserved_on_randomize {
             fastrand::shuffle(&mut self.shifters);
         }
+        self.apply_shift_ordering();
     }
 }
 
@@ -368,4 +399,54 @@ mod combined_shifter_tests {
     /*fn decrement_incrementer() {
         todo!();
     }*/
-}
\ No newline at end of file
+
+    #[rstest]
+    fn hashed_matches_unhashed() {
+        init();
+
+        let shifters: Vec<Rc<RefCell<dyn Shifter<T = (u8, u8)>>>> = vec![
+            Rc::new(RefCell::new(IndexShifter::new(&vec![
+                vec![Rc::new((1, 1)), Rc::new((2, 2)), Rc::new((3, 3))]
+            ]))),
+            Rc::new(RefCell::new(IndexShifter::new(&vec![
+                vec![Rc::new((3, 3)), Rc::new((4, 4)), Rc::new((1, 1))]
+            ])))
+        ];
+        let unhashed_combined_shifter = CombinedShifter::new(&shifters, false);
+        let hashed_combined_shifter = CombinedShifter::new_hashed(&shifters, false);
+        assert_eq!(4, unhashed_combined_shifter.get_states().len());
+        assert_eq!(unhashed_combined_shifter.get_states(), hashed_combined_shifter.get_states());
+        assert_eq!(unhashed_combined_shifter.state_index_mapping_per_shifter_index, hashed_combined_shifter.state_index_mapping_per_shifter_index);
+    }
+
+    #[rstest]
+    fn minimum_remaining_values_places_smallest_shifter_first() {
+        init();
+
+        let shifters: Vec<Rc<RefCell<dyn Shifter<T = (u8, u8)>>>> = vec![
+            Rc::new(RefCell::new(IndexShifter::new(&vec![
+                vec![Rc::new((1, 1)), Rc::new((2, 2)), Rc::new((3, 3))]
+            ]))),
+            Rc::new(RefCell::new(IndexShifter::new(&vec![
+                vec![Rc::new((4, 4))]
+            ])))
+        ];
+        let mut combined_shifter = CombinedShifter::new(&shifters, true);
+        combined_shifter.set_shift_ordering(ShiftOrdering::MinimumRemainingValues);
+        let mut indexed_elements_per_increment: Vec<Vec<(usize, (u8, u8))>> = Vec::new();
+        assert!(combined_shifter.try_forward());
+        assert!(combined_shifter.try_increment());
+        let first_indexed_element = combined_shifter.get_indexed_element();
+        assert_eq!((1, (4, 4)), (first_indexed_element.index, *first_indexed_element.element));
+        assert!(combined_shifter.try_forward());
+        while combined_shifter.try_increment() {
+            let indexed_element = combined_shifter.get_indexed_element();
+            indexed_elements_per_increment.push(vec![(1, (4, 4)), (indexed_element.index, *indexed_element.element)]);
+        }
+        assert_eq!(vec![
+            vec![(1, (4, 4)), (0, (1, 1))],
+            vec![(1, (4, 4)), (0, (2, 2))],
+            vec![(1, (4, 4)), (0, (3, 3))]
+        ], indexed_elements_per_increment);
+    }
+}
@@ -0,0 +1,134 @@
+use std::rc::Rc;
+use crate::IndexedElement;
+use super::Shifter;
+
+// Purpose:
+//     To shift over the same states as another shifter except that any state failing the predicate is skipped past when incrementing
+//     The predicate is given the indexed element of the state so that states may be skipped for some elements but not others
+pub struct FilteredShifter<T> {
+    shifter: Box<dyn Shifter<T = T>>,
+    predicate: Box<dyn Fn(&IndexedElement<T>) -> bool>
+}
+
+impl<T> FilteredShifter<T> {
+    pub fn new(shifter: Box<dyn Shifter<T = T>>, predicate: Box<dyn Fn(&IndexedElement<T>) -> bool>) -> Self {
+        FilteredShifter {
+            shifter: shifter,
+            predicate: predicate
+        }
+    }
+}
+
+impl<T> Shifter for FilteredShifter<T> {
+    type T = T;
+
+    fn try_forward(&mut self) -> bool {
+        return self.shifter.try_forward();
+    }
+    fn try_backward(&mut self) -> bool {
+        return self.shifter.try_backward();
+    }
+    fn try_increment(&mut self) -> bool {
+        while self.shifter.try_increment() {
+            if (self.predicate)(&self.shifter.get_indexed_element()) {
+                return true;
+            }
+        }
+        return false;
+    }
+    fn get_indexed_element(&self) -> IndexedElement<Self::T> {
+        return self.shifter.get_indexed_element();
+    }
+    fn get_length(&self) -> usize {
+        return self.shifter.get_length();
+    }
+    fn get_element_index_and_state_index(&self) -> (usize, usize) {
+        return self.shifter.get_element_index_and_state_index();
+    }
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return self.shifter.get_states_ref();
+    }
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return self.shifter.get_state_table();
+    }
+    fn randomize(&mut self) {
+        self.shifter.randomize();
+    }
+    fn get_state_indexes_per_element_index(&self) -> Option<Vec<Vec<usize>>> {
+        if let Some(state_indexes_per_element_index) = self.shifter.get_state_indexes_per_element_index() {
+            let states = self.shifter.get_states();
+            let filtered_state_indexes_per_element_index = state_indexes_per_element_index.into_iter()
+                .enumerate()
+                .map(|(element_index, state_indexes)| {
+                    state_indexes.into_iter()
+                        .filter(|state_index| (self.predicate)(&IndexedElement::new(states[*state_index].clone(), element_index)))
+                        .collect()
+                })
+                .collect();
+            return Some(filtered_state_indexes_per_element_index);
+        }
+        return None;
+    }
+    fn is_each_shift_independent(&self) -> bool {
+        return self.shifter.is_each_shift_independent();
+    }
+    fn is_rotation_possible(&self) -> bool {
+        return self.shifter.is_rotation_possible();
+    }
+}
+
+#[cfg(test)]
+mod filtered_shifter_tests {
+    use crate::shifter::{index_shifter::IndexShifter, segment_permutation_shifter::{Segment, SegmentPermutationShifter}};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    #[rstest]
+    fn index_shifter_skips_odd_states() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![
+            vec![Rc::new(0), Rc::new(1), Rc::new(2)],
+            vec![Rc::new(3), Rc::new(4)]
+        ];
+        let mut filtered_shifter = FilteredShifter::new(
+            Box::new(IndexShifter::new(&states_per_shift_index)),
+            Box::new(|indexed_element: &IndexedElement<u8>| *indexed_element.element % 2 == 0)
+        );
+        let states_per_path: Vec<Vec<u8>> = filtered_shifter.iter_paths()
+            .map(|path| path.iter().map(|indexed_element| *indexed_element.element).collect())
+            .collect();
+        assert_eq!(vec![vec![0, 4], vec![2, 4]], states_per_path);
+        let states = filtered_shifter.get_states();
+        let states_per_element_index: Vec<Vec<u8>> = filtered_shifter.get_state_indexes_per_element_index().unwrap().iter()
+            .map(|state_indexes| state_indexes.iter().map(|state_index| *states[*state_index]).collect())
+            .collect();
+        assert_eq!(vec![vec![0, 2], vec![4]], states_per_element_index);
+        assert!(filtered_shifter.is_each_shift_independent());
+    }
+
+    #[rstest]
+    fn segment_permutation_shifter_skips_locations_per_element() {
+        init();
+
+        let segments: Vec<Rc<Segment>> = vec![
+            Rc::new(Segment::new(1)),
+            Rc::new(Segment::new(1))
+        ];
+        // the second segment may not be placed at the end
+        let mut filtered_shifter = FilteredShifter::new(
+            Box::new(SegmentPermutationShifter::new(segments, (0, 0), 4, true, 1, false)),
+            Box::new(|indexed_element: &IndexedElement<(u8, u8)>| indexed_element.index != 1 || indexed_element.element.0 != 3)
+        );
+        let locations_per_path: Vec<Vec<(u8, u8)>> = filtered_shifter.iter_paths()
+            .map(|path| path.iter().map(|indexed_element| *indexed_element.element).collect())
+            .collect();
+        assert_eq!(vec![vec![(0, 0), (2, 0)]], locations_per_path);
+    }
+}
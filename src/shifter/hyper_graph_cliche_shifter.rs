@@ -1,8 +1,8 @@
-use std::{rc::Rc, cell::RefCell};
+use std::{rc::Rc, cell::RefCell, collections::{HashMap, hash_map::RandomState}, hash::{Hash, BuildHasher}};
 use bitvec::vec::BitVec;
 use bitvec::prelude::*;
 use crate::IndexedElement;
-use super::{Shifter, index_shifter::IndexShifter};
+use super::{Shifter, ShiftOrdering, index_shifter::IndexShifter, get_or_push_state_index, get_or_push_hashed_state_index};
 
 
 // Purpose:
@@ -21,7 +21,8 @@ pub struct HyperGraphClicheShifter<T: PartialEq + std::fmt::Debug> {
     current_stateful_hyper_graph_node_per_hyper_graph_node_index: Vec<Rc<RefCell<StatefulHyperGraphNode<T>>>>,
     current_stateful_hyper_graph_node_index_per_hyper_graph_node_index: Vec<Option<usize>>,
     possible_states: Vec<Rc<T>>,
-    focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples: Option<Vec<(usize, usize)>>
+    focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples: Option<Vec<(usize, usize)>>,
+    shift_ordering: ShiftOrdering
 }
 
 impl<T: PartialEq + std::fmt::Debug> HyperGraphClicheShifter<T> {
@@ -34,22 +35,15 @@ impl<T: PartialEq + std::fmt::Debug> HyperGraphClicheShifter<T> {
         return Self::new_with_islands(stateful_hyper_graph_nodes_per_hyper_graph_node_index, Rc::new(is_independent_hyper_graph_node_per_hyper_graph_node_index));
     }
     pub fn new_with_islands(stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<T>>>>>, is_independent_hyper_graph_node_per_hyper_graph_node_index: Rc<Vec<BitVec>>) -> Self {
+        return Self::new_with_state_index_getter(stateful_hyper_graph_nodes_per_hyper_graph_node_index, is_independent_hyper_graph_node_per_hyper_graph_node_index, &mut get_or_push_state_index);
+    }
+    fn new_with_state_index_getter(stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<T>>>>>, is_independent_hyper_graph_node_per_hyper_graph_node_index: Rc<Vec<BitVec>>, get_or_push_state_index: &mut dyn FnMut(&mut Vec<Rc<T>>, &Rc<T>) -> usize) -> Self {
         let hyper_graph_nodes_length = stateful_hyper_graph_nodes_per_hyper_graph_node_index.len();
         let mut possible_states: Vec<Rc<T>> = Vec::new();
         for hyper_graph_node_index in 0..stateful_hyper_graph_nodes_per_hyper_graph_node_index.len() {
             for wrapped_stateful_hyper_graph_node in stateful_hyper_graph_nodes_per_hyper_graph_node_index[hyper_graph_node_index].iter() {
                 let borrowed_stateful_hyper_graph_node = wrapped_stateful_hyper_graph_node.borrow();
-                let mut is_state_in_possible_states = false;
-                for possible_state in possible_states.iter() {
-                    if possible_state == &borrowed_stateful_hyper_graph_node.state {
-                        is_state_in_possible_states = true;
-                        break;
-                    }
-                }
-                if !is_state_in_possible_states {
-                    let possible_state = borrowed_stateful_hyper_graph_node.state.clone();
-                    possible_states.push(possible_state);
-                }
+                get_or_push_state_index(&mut possible_states, &borrowed_stateful_hyper_graph_node.state);
             }
         }
         let mut current_hyper_graph_node_index_mapping: Vec<usize> = Vec::new();
@@ -65,7 +59,29 @@ impl<T: PartialEq + std::fmt::Debug> HyperGraphClicheShifter<T> {
             current_stateful_hyper_graph_node_per_hyper_graph_node_index: Vec::new(),
             current_stateful_hyper_graph_node_index_per_hyper_graph_node_index: Vec::new(),
             possible_states: possible_states,
-            focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples: None
+            focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples: None,
+            shift_ordering: ShiftOrdering::Fixed
+        }
+    }
+    /// Reorders the hyper graph nodes that are not focused on for the next traversal, so this should only be called before moving forward or after a reset
+    /// The minimum remaining values ordering places the hyper graph nodes with the fewest stateful hyper graph nodes first and the degree ordering places the hyper graph nodes with the highest provided degree first
+    pub fn set_shift_ordering(&mut self, shift_ordering: ShiftOrdering) {
+        self.shift_ordering = shift_ordering;
+        self.apply_shift_ordering();
+    }
+    fn apply_shift_ordering(&mut self) {
+        let focused_length: usize;
+        if let Some(focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples) = &self.focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples {
+            focused_length = focused_stateful_hyper_graph_node_index_and_hyper_graph_node_index_tuples.len();
+        }
+        else {
+            focused_length = 0;
+        }
+        let unfocused_hyper_graph_node_indexes: Vec<usize> = self.current_hyper_graph_node_index_mapping.split_off(focused_length);
+        let states_length_per_unfocused_index: Vec<usize> = unfocused_hyper_graph_node_indexes.iter().map(|hyper_graph_node_index| self.stateful_hyper_graph_nodes_per_hyper_graph_node_index[*hyper_graph_node_index].len()).collect();
+        let element_indexes_per_unfocused_index: Vec<Vec<usize>> = unfocused_hyper_graph_node_indexes.iter().map(|hyper_graph_node_index| vec![*hyper_graph_node_index]).collect();
+        for unfocused_index in self.shift_ordering.get_ordered_shift_indexes(&states_length_per_unfocused_index, &element_indexes_per_unfocused_index) {
+            self.current_hyper_graph_node_index_mapping.push(unfocused_hyper_graph_node_indexes[unfocused_index]);
         }
     }
 
@@ -85,6 +101,7 @@ impl<T: PartialEq + std::fmt::Debug> HyperGraphClicheShifter<T> {
             }
         }
         self.current_hyper_graph_node_index_mapping.append(&mut remaining_hyper_graph_node_indexes);
+        self.apply_shift_ordering();
     }
 
     pub fn unfocus_neighbors(&mut self) {
@@ -95,6 +112,18 @@ impl<T: PartialEq + std::fmt::Debug> HyperGraphClicheShifter<T> {
         for hyper_graph_node_index in 0..self.hyper_graph_nodes_length {
             self.current_hyper_graph_node_index_mapping.push(hyper_graph_node_index);
         }
+        self.apply_shift_ordering();
+    }
+}
+
+impl<T: Eq + Hash + std::fmt::Debug> HyperGraphClicheShifter<T> {
+    /// Produces the same shifter as new_with_islands, but finds duplicate states by hashing instead of comparing every pair of states
+    pub fn new_hashed_with_islands(stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<T>>>>>, is_independent_hyper_graph_node_per_hyper_graph_node_index: Rc<Vec<BitVec>>) -> Self {
+        return Self::new_hashed_with_islands_and_hasher::<RandomState>(stateful_hyper_graph_nodes_per_hyper_graph_node_index, is_independent_hyper_graph_node_per_hyper_graph_node_index);
+    }
+    pub fn new_hashed_with_islands_and_hasher<S: BuildHasher + Default>(stateful_hyper_graph_nodes_per_hyper_graph_node_index: Vec<Vec<Rc<RefCell<StatefulHyperGraphNode<T>>>>>, is_independent_hyper_graph_node_per_hyper_graph_node_index: Rc<Vec<BitVec>>) -> Self {
+        let mut state_index_per_state: HashMap<Rc<T>, usize, S> = HashMap::default();
+        return Self::new_with_state_index_getter(stateful_hyper_graph_nodes_per_hyper_graph_node_index, is_independent_hyper_graph_node_per_hyper_graph_node_index, &mut |possible_states, state| get_or_push_hashed_state_index(possible_states, &mut state_index_per_state, state));
     }
 }
 
@@ -251,8 +280,8 @@ impl<T: PartialEq + std::fmt::Debug> Shifter for HyperGraphClicheShifter<T> {
         }
         panic!("Unexpected attempt to get element index and state index when not moved forward and incremented.");
     }
-    fn get_states(&self) -> Vec<Rc<Self::T>> {
-        return self.possible_states.clone();
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.possible_states;
     }
     fn randomize(&mut self) {
         todo!();
@@ -285,6 +314,13 @@ impl<T: PartialEq + std::fmt::Debug> StatefulHyperGraphNode<T> {
         self.is_hyper_graph_node_index_connected.set(hyper_graph_node_index, true);
     }
 
+    pub fn is_neighbor(&self, hyper_graph_node_index: usize, stateful_hyper_graph_node: &Rc<RefCell<StatefulHyperGraphNode<T>>>) -> bool {
+        if hyper_graph_node_index >= self.neighbor_stateful_hyper_graph_nodes_per_hyper_graph_node_index.len() {
+            return false;
+        }
+        return self.neighbor_stateful_hyper_graph_nodes_per_hyper_graph_node_index[hyper_graph_node_index].iter().any(|neighbor_stateful_hyper_graph_node| Rc::ptr_eq(neighbor_stateful_hyper_graph_node, stateful_hyper_graph_node));
+    }
+
     pub fn is_connected_to_all_hyper_graph_nodes(&self) -> bool {
         debug!("state {:?} has {} connections", self.state, self.is_hyper_graph_node_index_connected.count_ones());
         return self.is_hyper_graph_node_index_connected.all();
@@ -775,4 +811,76 @@ mod hyper_graph_cliche_shifter_tests {
             assert!(!shifter.try_backward());
         }
     }
-}
\ No newline at end of file
+
+    #[rstest]
+    fn hashed_matches_unhashed() {
+        init();
+
+        let first_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(Rc::new((1 as u8, 1 as u8)), bitvec![1, 0])));
+        let second_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(Rc::new((1 as u8, 1 as u8)), bitvec![0, 1])));
+        let third_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(Rc::new((2 as u8, 2 as u8)), bitvec![0, 1])));
+        first_stateful_hyper_graph_node.borrow_mut().add_neighbor(1, second_stateful_hyper_graph_node.clone());
+        second_stateful_hyper_graph_node.borrow_mut().add_neighbor(0, first_stateful_hyper_graph_node.clone());
+        first_stateful_hyper_graph_node.borrow_mut().add_neighbor(1, third_stateful_hyper_graph_node.clone());
+        third_stateful_hyper_graph_node.borrow_mut().add_neighbor(0, first_stateful_hyper_graph_node.clone());
+        let stateful_hyper_graph_nodes_per_hyper_graph_node_index = vec![
+            vec![first_stateful_hyper_graph_node],
+            vec![second_stateful_hyper_graph_node, third_stateful_hyper_graph_node]
+        ];
+        let is_independent_hyper_graph_node_per_hyper_graph_node_index = Rc::new(vec![bitvec![1, 0], bitvec![0, 1]]);
+
+        let unhashed_shifter = HyperGraphClicheShifter::new_with_islands(stateful_hyper_graph_nodes_per_hyper_graph_node_index.clone(), is_independent_hyper_graph_node_per_hyper_graph_node_index.clone());
+        let hashed_shifter = HyperGraphClicheShifter::new_hashed_with_islands(stateful_hyper_graph_nodes_per_hyper_graph_node_index, is_independent_hyper_graph_node_per_hyper_graph_node_index);
+        assert_eq!(vec![Rc::new((1, 1)), Rc::new((2, 2))], unhashed_shifter.get_states());
+        assert_eq!(unhashed_shifter.get_states(), hashed_shifter.get_states());
+    }
+
+    #[rstest]
+    fn minimum_remaining_values_finds_same_cliches() {
+        init();
+
+        let first_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(Rc::new((1 as u8, 1 as u8)), bitvec![1, 0])));
+        let second_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(Rc::new((2 as u8, 2 as u8)), bitvec![1, 0])));
+        let third_stateful_hyper_graph_node = Rc::new(RefCell::new(StatefulHyperGraphNode::new(Rc::new((3 as u8, 3 as u8)), bitvec![0, 1])));
+        first_stateful_hyper_graph_node.borrow_mut().add_neighbor(1, third_stateful_hyper_graph_node.clone());
+        third_stateful_hyper_graph_node.borrow_mut().add_neighbor(0, first_stateful_hyper_graph_node.clone());
+        second_stateful_hyper_graph_node.borrow_mut().add_neighbor(1, third_stateful_hyper_graph_node.clone());
+        third_stateful_hyper_graph_node.borrow_mut().add_neighbor(0, second_stateful_hyper_graph_node.clone());
+        let stateful_hyper_graph_nodes_per_hyper_graph_node_index = vec![
+            vec![first_stateful_hyper_graph_node, second_stateful_hyper_graph_node],
+            vec![third_stateful_hyper_graph_node]
+        ];
+
+        let mut cliches_per_shift_ordering: Vec<Vec<Vec<(usize, (u8, u8))>>> = Vec::new();
+        for shift_ordering in [ShiftOrdering::Fixed, ShiftOrdering::MinimumRemainingValues] {
+            let is_minimum_remaining_values = shift_ordering == ShiftOrdering::MinimumRemainingValues;
+            let mut shifter = HyperGraphClicheShifter::new(stateful_hyper_graph_nodes_per_hyper_graph_node_index.clone());
+            shifter.set_shift_ordering(shift_ordering);
+            let mut cliches: Vec<Vec<(usize, (u8, u8))>> = Vec::new();
+            assert!(shifter.try_forward());
+            while shifter.try_increment() {
+                let first_indexed_element = shifter.get_indexed_element();
+                // the hyper graph node with only one stateful hyper graph node is traversed first
+                assert_eq!(if is_minimum_remaining_values { 1 } else { 0 }, first_indexed_element.index);
+                assert!(shifter.try_forward());
+                while shifter.try_increment() {
+                    let second_indexed_element = shifter.get_indexed_element();
+                    let mut cliche = vec![
+                        (first_indexed_element.index, *first_indexed_element.element),
+                        (second_indexed_element.index, *second_indexed_element.element)
+                    ];
+                    cliche.sort();
+                    cliches.push(cliche);
+                }
+                assert!(shifter.try_backward());
+            }
+            cliches.sort();
+            cliches_per_shift_ordering.push(cliches);
+        }
+        assert_eq!(vec![
+            vec![(0, (1, 1)), (1, (3, 3))],
+            vec![(0, (2, 2)), (1, (3, 3))]
+        ], cliches_per_shift_ordering[0]);
+        assert_eq!(cliches_per_shift_ordering[0], cliches_per_shift_ordering[1]);
+    }
+}
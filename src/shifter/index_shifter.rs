@@ -1,6 +1,6 @@
-use std::{collections::VecDeque, rc::Rc};
+use std::{collections::{VecDeque, HashMap, hash_map::RandomState}, rc::Rc, hash::{Hash, BuildHasher}};
 use crate::IndexedElement;
-use super::Shifter;
+use super::{Shifter, ShiftOrdering, get_or_push_state_index, get_or_push_hashed_state_index};
 
 // Purpose:
 // This represents a collection of items or states that exist at indexes of a list.
@@ -13,30 +13,21 @@ pub struct IndexShifter<T> {
     is_incremented_at_least_once_per_shift_index: VecDeque<bool>,
     possible_states: Vec<Rc<T>>,
     state_indexes_per_shift_index: Vec<Vec<usize>>,
+    weights_per_shift_index: Option<Vec<Vec<f32>>>,
+    element_index_per_shift_index: Vec<usize>,
+    shift_ordering: ShiftOrdering,
     shifts_length: usize
 }
 
-impl<T: PartialEq> IndexShifter<T> {
-    pub fn new(states_per_shift_index: &Vec<Vec<Rc<T>>>) -> Self {
+impl<T> IndexShifter<T> {
+    fn new_with_state_index_getter(states_per_shift_index: &Vec<Vec<Rc<T>>>, get_or_push_state_index: &mut dyn FnMut(&mut Vec<Rc<T>>, &Rc<T>) -> usize) -> Self {
         let shifts_length: usize = states_per_shift_index.len();
         let mut possible_states: Vec<Rc<T>> = Vec::new();
         let mut state_indexes_per_shift_index: Vec<Vec<usize>> = Vec::new();
         for states in states_per_shift_index.iter() {
             let mut state_indexes: Vec<usize> = Vec::new();
             for state in states.iter() {
-                let mut state_index: usize = 0;
-                let mut is_existing = false;
-                for (existing_state_index, existing_state) in possible_states.iter().enumerate() {
-                    if existing_state == state {
-                        state_index = existing_state_index;
-                        is_existing = true;
-                        break;
-                    }
-                }
-                if !is_existing {
-                    state_index = possible_states.len();
-                    possible_states.push(state.clone());
-                }
+                let state_index = get_or_push_state_index(&mut possible_states, state);
                 state_indexes.push(state_index);
             }
             state_indexes_per_shift_index.push(state_indexes);
@@ -47,9 +38,63 @@ impl<T: PartialEq> IndexShifter<T> {
             is_incremented_at_least_once_per_shift_index: VecDeque::new(),
             possible_states: possible_states,
             state_indexes_per_shift_index: state_indexes_per_shift_index,
+            weights_per_shift_index: None,
+            element_index_per_shift_index: (0..shifts_length).collect(),
+            shift_ordering: ShiftOrdering::Fixed,
             shifts_length: shifts_length
         }
     }
+    /// Reorders the shifts for the next traversal, keeping each element index with its states, so this should only be called before moving forward or after a reset
+    pub fn set_shift_ordering(&mut self, shift_ordering: ShiftOrdering) {
+        self.shift_ordering = shift_ordering;
+        self.apply_shift_ordering();
+    }
+    fn apply_shift_ordering(&mut self) {
+        let states_length_per_shift_index: Vec<usize> = self.state_indexes_per_shift_index.iter().map(|state_indexes| state_indexes.len()).collect();
+        let element_indexes_per_shift_index: Vec<Vec<usize>> = self.element_index_per_shift_index.iter().map(|element_index| vec![*element_index]).collect();
+        let shift_indexes = self.shift_ordering.get_ordered_shift_indexes(&states_length_per_shift_index, &element_indexes_per_shift_index);
+        self.state_indexes_per_shift_index = shift_indexes.iter().map(|shift_index| self.state_indexes_per_shift_index[*shift_index].clone()).collect();
+        self.element_index_per_shift_index = shift_indexes.iter().map(|shift_index| self.element_index_per_shift_index[*shift_index]).collect();
+        if let Some(weights_per_shift_index) = &mut self.weights_per_shift_index {
+            *weights_per_shift_index = shift_indexes.iter().map(|shift_index| weights_per_shift_index[*shift_index].clone()).collect();
+        }
+    }
+}
+
+impl<T: PartialEq> IndexShifter<T> {
+    pub fn new(states_per_shift_index: &Vec<Vec<Rc<T>>>) -> Self {
+        return Self::new_with_state_index_getter(states_per_shift_index, &mut get_or_push_state_index);
+    }
+    /// The weights are parallel to the states per shift index such that randomizing will order the states of each shift index by weighted random sampling, so the first state is chosen proportional to its weight. States with a weight of zero are still iterated over, but only after all states with a positive weight.
+    /// Only the first state of each shift index is sampled proportional to its weight, since the later states are what remains of a weighted shuffle rather than independent samples, so randomize again for each weighted sample.
+    pub fn new_with_weights(states_per_shift_index: &Vec<Vec<Rc<T>>>, weights_per_shift_index: &Vec<Vec<f32>>) -> Self {
+        if states_per_shift_index.len() != weights_per_shift_index.len() {
+            panic!("Unexpected mismatch between the number of shift indexes for states and for weights.");
+        }
+        for (states, weights) in states_per_shift_index.iter().zip(weights_per_shift_index.iter()) {
+            if states.len() != weights.len() {
+                panic!("Unexpected mismatch between the number of states and the number of weights for a shift index.");
+            }
+            if weights.iter().any(|weight| !(*weight >= 0.0)) {
+                panic!("Unexpected negative or undefined weight.");
+            }
+        }
+        let mut index_shifter = Self::new(states_per_shift_index);
+        index_shifter.weights_per_shift_index = Some(weights_per_shift_index.clone());
+        return index_shifter;
+    }
+}
+
+impl<T: Eq + Hash> IndexShifter<T> {
+    /// Produces the same shifter as new, but finds duplicate states by hashing instead of comparing every pair of states, which is much faster when there are many states
+    pub fn new_hashed(states_per_shift_index: &Vec<Vec<Rc<T>>>) -> Self {
+        return Self::new_hashed_with_hasher::<RandomState>(states_per_shift_index);
+    }
+    /// Allows for a faster hasher, such as nohash_hasher::BuildNoHashHasher when the states are integers
+    pub fn new_hashed_with_hasher<S: BuildHasher + Default>(states_per_shift_index: &Vec<Vec<Rc<T>>>) -> Self {
+        let mut state_index_per_state: HashMap<Rc<T>, usize, S> = HashMap::default();
+        return Self::new_with_state_index_getter(states_per_shift_index, &mut |possible_states, state| get_or_push_hashed_state_index(possible_states, &mut state_index_per_state, state));
+    }
 }
 
 impl<T> Shifter for IndexShifter<T> {
@@ -184,17 +229,58 @@ impl<T> Shifter for IndexShifter<T> {
     fn get_element_index_and_state_index(&self) -> (usize, usize) {
         let current_shift_index = self.current_shift_index.unwrap();
         let current_state_index = self.current_state_index_per_shift_index[current_shift_index].unwrap();
-        return (current_shift_index, self.state_indexes_per_shift_index[current_shift_index][current_state_index]);
+        return (self.element_index_per_shift_index[current_shift_index], self.state_indexes_per_shift_index[current_shift_index][current_state_index]);
+    }
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.possible_states;
     }
-    fn get_states(&self) -> Vec<Rc<Self::T>> {
-        return self.possible_states.clone();
+    fn is_each_shift_independent(&self) -> bool {
+        return true;
+    }
+    fn get_state_indexes_per_element_index(&self) -> Option<Vec<Vec<usize>>> {
+        let mut state_indexes_per_element_index: Vec<Vec<usize>> = vec![Vec::new(); self.shifts_length];
+        for (shift_index, element_index) in self.element_index_per_shift_index.iter().enumerate() {
+            state_indexes_per_element_index[*element_index] = self.state_indexes_per_shift_index[shift_index].clone();
+        }
+        return Some(state_indexes_per_element_index);
     }
     fn randomize(&mut self) {
         // TODO panic if already iterating
-        fastrand::shuffle(&mut self.state_indexes_per_shift_index);
-        for shift_index in 0..self.state_indexes_per_shift_index.len() {
-            fastrand::shuffle(&mut self.state_indexes_per_shift_index[shift_index]);
+        if let Some(weights_per_shift_index) = &mut self.weights_per_shift_index {
+            // the weights must be kept parallel to the state indexes as they are shuffled
+            let mut shift_indexes: Vec<usize> = (0..self.state_indexes_per_shift_index.len()).collect();
+            fastrand::shuffle(&mut shift_indexes);
+            self.state_indexes_per_shift_index = shift_indexes.iter().map(|shift_index| self.state_indexes_per_shift_index[*shift_index].clone()).collect();
+            *weights_per_shift_index = shift_indexes.iter().map(|shift_index| weights_per_shift_index[*shift_index].clone()).collect();
+
+            // order by weighted random sampling without replacement (Efraimidis-Spirakis) where each key is ln(u) / weight, and larger keys come first
+            for (state_indexes, weights) in self.state_indexes_per_shift_index.iter_mut().zip(weights_per_shift_index.iter_mut()) {
+                let mut keyed_pairs: Vec<(f64, usize, f32)> = state_indexes.iter()
+                    .zip(weights.iter())
+                    .map(|(state_index, weight)| {
+                        let key: f64;
+                        if *weight > 0.0 {
+                            key = (1.0 - fastrand::f64()).ln() / (*weight as f64);
+                        }
+                        else {
+                            key = f64::NEG_INFINITY;
+                        }
+                        (key, *state_index, *weight)
+                    })
+                    .collect();
+                fastrand::shuffle(&mut keyed_pairs);
+                keyed_pairs.sort_by(|first, second| second.0.partial_cmp(&first.0).unwrap());
+                *state_indexes = keyed_pairs.iter().map(|keyed_pair| keyed_pair.1).collect();
+                *weights = keyed_pairs.iter().map(|keyed_pair| keyed_pair.2).collect();
+            }
+        }
+        else {
+            fastrand::shuffle(&mut self.state_indexes_per_shift_index);
+            for shift_index in 0..self.state_indexes_per_shift_index.len() {
+                fastrand::shuffle(&mut self.state_indexes_per_shift_index[shift_index]);
+            }
         }
+        self.apply_shift_ordering();
     }
 }
 
@@ -366,4 +452,114 @@ mod index_shifter_tests {
         let states = shifter.get_states();
         assert_eq!(4, states.len());
     }
+
+    #[rstest]
+    fn weighted_randomize_prefers_heavier_states() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<usize>>> = vec![
+            vec![Rc::new(0), Rc::new(1), Rc::new(2)]
+        ];
+        let weights_per_shift_index: Vec<Vec<f32>> = vec![
+            vec![1.0, 3.0, 0.0]
+        ];
+        let mut first_state_totals: Vec<usize> = vec![0; 3];
+        let iterations_total = 4000;
+        for _ in 0..iterations_total {
+            let mut index_shifter = IndexShifter::new_with_weights(&states_per_shift_index, &weights_per_shift_index);
+            index_shifter.randomize();
+            assert!(index_shifter.try_forward());
+            assert!(index_shifter.try_increment());
+            first_state_totals[*index_shifter.get_indexed_element().element] += 1;
+            // the zero-weight state is still reachable, but always last
+            assert!(index_shifter.try_increment());
+            assert!(index_shifter.try_increment());
+            assert_eq!(2, *index_shifter.get_indexed_element().element);
+            assert!(!index_shifter.try_increment());
+        }
+        assert_eq!(0, first_state_totals[2]);
+        // the expected ratio is 3:1, so allow for plenty of sampling noise
+        let heavier_ratio = first_state_totals[1] as f32 / iterations_total as f32;
+        assert!(heavier_ratio > 0.7 && heavier_ratio < 0.8, "unexpected ratio {}", heavier_ratio);
+    }
+
+    #[rstest]
+    fn hashed_matches_unhashed() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u32>>> = (0..4)
+            .map(|shift_index| (0..50).map(|state| Rc::new((shift_index * 20 + state) % 70)).collect())
+            .collect();
+        let mut unhashed_index_shifter = IndexShifter::new(&states_per_shift_index);
+        let mut hashed_index_shifter = IndexShifter::new_hashed(&states_per_shift_index);
+        let mut nohash_index_shifter = IndexShifter::new_hashed_with_hasher::<nohash_hasher::BuildNoHashHasher<u32>>(&states_per_shift_index);
+        assert_eq!(70, unhashed_index_shifter.get_states().len());
+        assert_eq!(unhashed_index_shifter.get_states(), hashed_index_shifter.get_states());
+        assert_eq!(unhashed_index_shifter.get_states(), nohash_index_shifter.get_states());
+        for _ in 0..4 {
+            assert!(unhashed_index_shifter.try_forward());
+            assert!(hashed_index_shifter.try_forward());
+            assert!(nohash_index_shifter.try_forward());
+            while unhashed_index_shifter.try_increment() {
+                assert!(hashed_index_shifter.try_increment());
+                assert!(nohash_index_shifter.try_increment());
+                assert_eq!(unhashed_index_shifter.get_element_index_and_state_index(), hashed_index_shifter.get_element_index_and_state_index());
+                assert_eq!(unhashed_index_shifter.get_element_index_and_state_index(), nohash_index_shifter.get_element_index_and_state_index());
+            }
+            assert!(!hashed_index_shifter.try_increment());
+            assert!(!nohash_index_shifter.try_increment());
+        }
+    }
+
+    #[rstest]
+    #[case(ShiftOrdering::MinimumRemainingValues, vec![1, 2, 0])]
+    #[case(ShiftOrdering::Degree(Rc::new(vec![0, 1, 2])), vec![2, 1, 0])]
+    #[case(ShiftOrdering::Fixed, vec![0, 1, 2])]
+    fn shift_ordering_keeps_element_indexes(#[case] shift_ordering: ShiftOrdering, #[case] expected_element_indexes: Vec<usize>) {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![
+            vec![Rc::new(0), Rc::new(1), Rc::new(2)],
+            vec![Rc::new(3)],
+            vec![Rc::new(4), Rc::new(5)]
+        ];
+        let mut index_shifter = IndexShifter::new(&states_per_shift_index);
+        index_shifter.set_shift_ordering(shift_ordering);
+        let mut element_indexes: Vec<usize> = Vec::new();
+        let mut assignments: Vec<Vec<u8>> = Vec::new();
+        let mut current_states: Vec<u8> = vec![0; 3];
+        let mut shifts_total = 0;
+        // depth first traversal, recording each full assignment per element index
+        if index_shifter.try_forward() {
+            loop {
+                if index_shifter.try_increment() {
+                    let indexed_element = index_shifter.get_indexed_element();
+                    if shifts_total == element_indexes.len() {
+                        element_indexes.push(indexed_element.index);
+                    }
+                    current_states[indexed_element.index] = *indexed_element.element;
+                    if shifts_total + 1 == states_per_shift_index.len() {
+                        assignments.push(current_states.clone());
+                    }
+                    else {
+                        assert!(index_shifter.try_forward());
+                        shifts_total += 1;
+                    }
+                }
+                else if index_shifter.try_backward() {
+                    shifts_total -= 1;
+                }
+                else {
+                    break;
+                }
+            }
+        }
+        assert_eq!(expected_element_indexes, element_indexes);
+        assignments.sort();
+        assert_eq!(vec![
+            vec![0, 3, 4], vec![0, 3, 5],
+            vec![1, 3, 4], vec![1, 3, 5],
+            vec![2, 3, 4], vec![2, 3, 5]
+        ], assignments);
+    }
 }
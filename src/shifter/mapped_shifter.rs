@@ -0,0 +1,120 @@
+use std::rc::Rc;
+use crate::IndexedElement;
+use super::Shifter;
+
+// Purpose:
+//     To shift over the same element indexes and state indexes as another shifter while returning states of another type
+//     Each state is mapped once when constructed, so the shifter being wrapped must not change its states afterwards
+pub struct MappedShifter<TFrom, TTo> {
+    shifter: Box<dyn Shifter<T = TFrom>>,
+    mapped_states: Vec<Rc<TTo>>
+}
+
+impl<TFrom, TTo> MappedShifter<TFrom, TTo> {
+    pub fn new(shifter: Box<dyn Shifter<T = TFrom>>, map: &dyn Fn(&Rc<TFrom>) -> Rc<TTo>) -> Self {
+        let mapped_states: Vec<Rc<TTo>> = shifter.get_states().iter()
+            .map(map)
+            .collect();
+        MappedShifter {
+            shifter: shifter,
+            mapped_states: mapped_states
+        }
+    }
+}
+
+impl<TFrom, TTo> Shifter for MappedShifter<TFrom, TTo> {
+    type T = TTo;
+
+    fn try_forward(&mut self) -> bool {
+        return self.shifter.try_forward();
+    }
+    fn try_backward(&mut self) -> bool {
+        return self.shifter.try_backward();
+    }
+    fn try_increment(&mut self) -> bool {
+        return self.shifter.try_increment();
+    }
+    fn get_indexed_element(&self) -> IndexedElement<Self::T> {
+        let (element_index, state_index) = self.shifter.get_element_index_and_state_index();
+        return IndexedElement::new(self.mapped_states[state_index].clone(), element_index);
+    }
+    fn get_length(&self) -> usize {
+        return self.shifter.get_length();
+    }
+    fn get_element_index_and_state_index(&self) -> (usize, usize) {
+        return self.shifter.get_element_index_and_state_index();
+    }
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.mapped_states;
+    }
+    fn randomize(&mut self) {
+        self.shifter.randomize();
+    }
+    fn get_state_indexes_per_element_index(&self) -> Option<Vec<Vec<usize>>> {
+        return self.shifter.get_state_indexes_per_element_index();
+    }
+    fn is_each_shift_independent(&self) -> bool {
+        return self.shifter.is_each_shift_independent();
+    }
+    fn is_rotation_possible(&self) -> bool {
+        return self.shifter.is_rotation_possible();
+    }
+}
+
+#[cfg(test)]
+mod mapped_shifter_tests {
+    use crate::shifter::{index_shifter::IndexShifter, segment_permutation_shifter::{Segment, SegmentPermutationShifter}};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    #[rstest]
+    fn index_shifter_states_are_mapped() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![
+            vec![Rc::new(1), Rc::new(2)],
+            vec![Rc::new(3)]
+        ];
+        let mut mapped_shifter = MappedShifter::new(
+            Box::new(IndexShifter::new(&states_per_shift_index)),
+            &|state: &Rc<u8>| Rc::new(format!("s{}", state))
+        );
+        let states_per_path: Vec<Vec<(usize, String)>> = mapped_shifter.iter_paths()
+            .map(|path| path.iter().map(|indexed_element| (indexed_element.index, indexed_element.element.as_ref().clone())).collect())
+            .collect();
+        assert_eq!(vec![
+            vec![(0, String::from("s1")), (1, String::from("s3"))],
+            vec![(0, String::from("s2")), (1, String::from("s3"))]
+        ], states_per_path);
+        assert!(mapped_shifter.is_each_shift_independent());
+    }
+
+    #[rstest]
+    fn segment_permutation_shifter_locations_are_mapped() {
+        init();
+
+        let segments: Vec<Rc<Segment>> = vec![
+            Rc::new(Segment::new(1)),
+            Rc::new(Segment::new(1))
+        ];
+        let mut segment_permutation_shifter = SegmentPermutationShifter::new(segments.clone(), (0, 0), 4, true, 1, false);
+        let expected_paths: Vec<Vec<(usize, u8)>> = segment_permutation_shifter.iter_paths()
+            .map(|path| path.iter().map(|indexed_element| (indexed_element.index, indexed_element.element.0)).collect())
+            .collect();
+        let mut mapped_shifter = MappedShifter::new(
+            Box::new(SegmentPermutationShifter::new(segments, (0, 0), 4, true, 1, false)),
+            &|location: &Rc<(u8, u8)>| Rc::new(location.0)
+        );
+        let paths: Vec<Vec<(usize, u8)>> = mapped_shifter.iter_paths()
+            .map(|path| path.iter().map(|indexed_element| (indexed_element.index, *indexed_element.element)).collect())
+            .collect();
+        assert_eq!(3, paths.len());
+        assert_eq!(expected_paths, paths);
+    }
+}
@@ -4,9 +4,52 @@ pub mod combined_shifter;
 pub mod shifting_square_breadth_first_search_shifter;
 pub mod scaling_square_breadth_first_search_shifter;
 pub mod hyper_graph_cliche_shifter;
-use std::rc::Rc;
+pub mod shifter_path_iterator;
+pub mod filtered_shifter;
+pub mod mapped_shifter;
+pub mod reversed_shifter;
+pub mod closed_loop_segment_permutation_shifter;
+pub mod rectangle_packing_shifter;
+use std::{rc::Rc, collections::HashMap, hash::{Hash, BuildHasher}};
 
 use crate::IndexedElement;
+use self::shifter_path_iterator::ShifterPathIterator;
+
+/// This enum specifies the order that the shifts of a shifter are traversed in, where placing the most constrained shifts first lets invalid states be found before many later shifts are shifted over
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShiftOrdering {
+    /// shifts are traversed in the order that they were constructed in
+    Fixed,
+    /// shifts with the fewest possible states are traversed first
+    MinimumRemainingValues,
+    /// shifts whose elements constrain the most other elements are traversed first, given the degree per element index
+    Degree(Rc<Vec<usize>>)
+}
+
+impl ShiftOrdering {
+    // returns the shift indexes in the order that they should be traversed, given the number of states and the element indexes per shift index, keeping the original order between equal shifts
+    pub(crate) fn get_ordered_shift_indexes(&self, states_length_per_shift_index: &Vec<usize>, element_indexes_per_shift_index: &Vec<Vec<usize>>) -> Vec<usize> {
+        let mut shift_indexes: Vec<usize> = (0..states_length_per_shift_index.len()).collect();
+        match self {
+            ShiftOrdering::Fixed => {
+                // keep the existing order
+            },
+            ShiftOrdering::MinimumRemainingValues => {
+                shift_indexes.sort_by_key(|shift_index| states_length_per_shift_index[*shift_index]);
+            },
+            ShiftOrdering::Degree(degree_per_element_index) => {
+                shift_indexes.sort_by_key(|shift_index| {
+                    let maximum_degree = element_indexes_per_shift_index[*shift_index].iter()
+                        .map(|element_index| degree_per_element_index[*element_index])
+                        .max()
+                        .unwrap_or(0);
+                    return std::cmp::Reverse(maximum_degree);
+                });
+            }
+        }
+        return shift_indexes;
+    }
+}
 
 /// Purpose:
 ///      To allow for shifting forward-and-backward across elements, incrementing their states individually
@@ -25,13 +68,71 @@ pub trait Shifter {
     fn get_length(&self) -> usize;
     // returns the current element index and current state index which can be used against the states()
     fn get_element_index_and_state_index(&self) -> (usize, usize);
-    // returns the distinct states possible from this shifter
-    fn get_states(&self) -> Vec<Rc<Self::T>>;
+    // returns the distinct states possible from this shifter, borrowed so that the states can be looked up by state index without allocating
+    fn get_states_ref(&self) -> &[Rc<Self::T>];
     fn randomize(&mut self);
+    // returns the distinct states possible from this shifter
+    fn get_states(&self) -> Vec<Rc<Self::T>> {
+        return self.get_states_ref().to_vec();
+    }
+    // returns the distinct states possible from this shifter as a borrowed table of plain states, ordered the same as the states(), if the shifter keeps its states without an Rc
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return None;
+    }
+    // returns a reference to the state at the state index, as used against the states(), reading from the state table when the shifter provides one
+    fn get_state(&self, state_index: usize) -> &Self::T {
+        if let Some(state_table) = self.get_state_table() {
+            return &state_table[state_index];
+        }
+        return &self.get_states_ref()[state_index];
+    }
+    // returns the current element index and a reference to the current state, the same as get_indexed_element() but without cloning the state
+    fn get_element_index_and_state(&self) -> (usize, &Self::T) {
+        let (element_index, state_index) = self.get_element_index_and_state_index();
+        return (element_index, self.get_state(state_index));
+    }
+    // returns the state indexes, as used against the states(), that each element index may be shifted to, if every element can be shifted to each of its states regardless of the states of the other elements
+    fn get_state_indexes_per_element_index(&self) -> Option<Vec<Vec<usize>>> {
+        return None;
+    }
+    // returns true if each shift is incremented across the same states regardless of the states of the earlier shifts, such that moving backward over multiple shifts at once never skips a state that may have become possible
+    fn is_each_shift_independent(&self) -> bool {
+        return false;
+    }
+    // returns true if a state may rotate its element, such that the element no longer has its original shape at that state
+    fn is_rotation_possible(&self) -> bool {
+        return false;
+    }
 
     fn reset(&mut self) {
         while self.try_backward() {
             // move back again
         }
     }
+    // returns an iterator over each full path of indexed elements from the first shift to the last shift, resetting the shifter first
+    fn iter_paths(&mut self) -> ShifterPathIterator<'_, Self> where Self: Sized {
+        return ShifterPathIterator::new(self);
+    }
+}
+
+// returns the index of the state within the possible states, appending the state if it is not yet a possible state
+pub(crate) fn get_or_push_state_index<T: PartialEq>(possible_states: &mut Vec<Rc<T>>, state: &Rc<T>) -> usize {
+    for (possible_state_index, possible_state) in possible_states.iter().enumerate() {
+        if possible_state == state {
+            return possible_state_index;
+        }
+    }
+    possible_states.push(state.clone());
+    return possible_states.len() - 1;
+}
+
+// returns the same index as get_or_push_state_index, but finds existing states in constant time by keeping each possible state in the hash map
+pub(crate) fn get_or_push_hashed_state_index<T: Eq + Hash, S: BuildHasher>(possible_states: &mut Vec<Rc<T>>, state_index_per_state: &mut HashMap<Rc<T>, usize, S>, state: &Rc<T>) -> usize {
+    if let Some(state_index) = state_index_per_state.get(state) {
+        return *state_index;
+    }
+    let state_index = possible_states.len();
+    possible_states.push(state.clone());
+    state_index_per_state.insert(state.clone(), state_index);
+    return state_index;
 }
@@ -0,0 +1,368 @@
+use std::rc::Rc;
+use crate::IndexedElement;
+use super::Shifter;
+
+/// This struct is where a rectangle is placed within the bounding box, relative to the origin.
+#[derive(Clone, Debug, PartialEq)]
+struct RectanglePlacement {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    is_rotated: bool
+}
+
+impl RectanglePlacement {
+    // returns true if the rectangles overlap or are closer to each other than the padding
+    fn is_overlapping(&self, other: &RectanglePlacement, padding: usize) -> bool {
+        return self.x < other.x + other.width + padding &&
+            other.x < self.x + self.width + padding &&
+            self.y < other.y + other.height + padding &&
+            other.y < self.y + self.height + padding;
+    }
+}
+
+// Purpose:
+//     To shift over each placement of each rectangle within the bounding box such that no rectangle overlaps, or is within the padding of, any rectangle placed by an earlier shift
+//     Each state is the top-left location of the rectangle and each element index is the rectangle index, so that the rectangle can be mapped to a cell group by its element index
+// Notes:
+//     When rotation is permitted, the states are followed by a second copy of the locations for the rotated placements, so that the state index of a rotated placement can be told apart via is_rotated_state_index
+//     Square rectangles are never rotated since the rotated placement would be the same as the unrotated placement
+//     A shifter that may rotate its rectangles is rejected by CellGroupDependency, since the cell groups would still be compared by their unrotated cells at the rotated placements
+//     The bounding box must fit within the u8 locations after the origin
+pub struct RectanglePackingShifter {
+    rectangles_length: usize,
+    is_rotation_possible: bool,
+    bounding_width: usize,
+    rotated_states_offset: usize,
+    states: Vec<Rc<(u8, u8)>>,
+    padding: usize,
+    placements_per_rectangle_index: Vec<Vec<RectanglePlacement>>,
+    current_shift_index: Option<usize>,
+    current_placement_index_per_shift_index: Vec<Option<usize>>
+}
+
+impl RectanglePackingShifter {
+    pub fn new(rectangles: Vec<(usize, usize)>, origin: (u8, u8), bounding_size: (usize, usize), padding: usize, is_rotation_permitted: bool) -> Self {
+        let (bounding_width, bounding_height) = bounding_size;
+        if origin.0 as usize + bounding_width > u8::MAX as usize + 1 || origin.1 as usize + bounding_height > u8::MAX as usize + 1 {
+            panic!("Unexpected bounding size {bounding_size:?} that extends beyond the largest u8 location after the origin {origin:?}.");
+        }
+        let mut states: Vec<Rc<(u8, u8)>> = Vec::new();
+        for y in 0..bounding_height {
+            for x in 0..bounding_width {
+                states.push(Rc::new((origin.0 + x as u8, origin.1 + y as u8)));
+            }
+        }
+        let rotated_states_offset = states.len();
+        if is_rotation_permitted {
+            states.extend_from_within(..);
+        }
+        let mut placements_per_rectangle_index: Vec<Vec<RectanglePlacement>> = Vec::new();
+        for (width, height) in rectangles.iter() {
+            let mut placements: Vec<RectanglePlacement> = Vec::new();
+            for y in 0..bounding_height {
+                for x in 0..bounding_width {
+                    if x + width <= bounding_width && y + height <= bounding_height {
+                        placements.push(RectanglePlacement {
+                            x: x,
+                            y: y,
+                            width: *width,
+                            height: *height,
+                            is_rotated: false
+                        });
+                    }
+                    if is_rotation_permitted && width != height && x + height <= bounding_width && y + width <= bounding_height {
+                        placements.push(RectanglePlacement {
+                            x: x,
+                            y: y,
+                            width: *height,
+                            height: *width,
+                            is_rotated: true
+                        });
+                    }
+                }
+            }
+            placements_per_rectangle_index.push(placements);
+        }
+        let is_rotation_possible = placements_per_rectangle_index.iter().any(|placements| placements.iter().any(|placement| placement.is_rotated));
+        RectanglePackingShifter {
+            rectangles_length: rectangles.len(),
+            is_rotation_possible: is_rotation_possible,
+            bounding_width: bounding_width,
+            rotated_states_offset: rotated_states_offset,
+            states: states,
+            padding: padding,
+            placements_per_rectangle_index: placements_per_rectangle_index,
+            current_shift_index: None,
+            current_placement_index_per_shift_index: Vec::new()
+        }
+    }
+    /// Returns true if the state index, as returned by get_element_index_and_state_index(), is of a rotated placement.
+    pub fn is_rotated_state_index(&self, state_index: usize) -> bool {
+        return state_index >= self.rotated_states_offset;
+    }
+}
+
+impl Shifter for RectanglePackingShifter {
+    type T = (u8, u8);
+
+    fn try_forward(&mut self) -> bool {
+        let next_shift_index = match self.current_shift_index {
+            Some(current_shift_index) => {
+                if current_shift_index == self.rectangles_length {
+                    return false;
+                }
+                current_shift_index + 1
+            },
+            None => 0
+        };
+        self.current_shift_index = Some(next_shift_index);
+        if next_shift_index == self.rectangles_length {
+            return false;
+        }
+        self.current_placement_index_per_shift_index.push(None);
+        return true;
+    }
+    fn try_backward(&mut self) -> bool {
+        match self.current_shift_index {
+            Some(current_shift_index) => {
+                if current_shift_index != self.rectangles_length {
+                    self.current_placement_index_per_shift_index.pop();
+                }
+                if current_shift_index == 0 {
+                    self.current_shift_index = None;
+                    return false;
+                }
+                self.current_shift_index = Some(current_shift_index - 1);
+                return true;
+            },
+            None => {
+                return false;
+            }
+        }
+    }
+    fn try_increment(&mut self) -> bool {
+        if let Some(current_shift_index) = self.current_shift_index {
+            if current_shift_index == self.rectangles_length {
+                return false;
+            }
+            let placements = &self.placements_per_rectangle_index[current_shift_index];
+            let mut next_placement_index = match self.current_placement_index_per_shift_index[current_shift_index] {
+                Some(current_placement_index) => current_placement_index + 1,
+                None => 0
+            };
+            while next_placement_index < placements.len() {
+                let placement = &placements[next_placement_index];
+                let is_overlapping = (0..current_shift_index).any(|earlier_shift_index| {
+                    let earlier_placement = &self.placements_per_rectangle_index[earlier_shift_index][self.current_placement_index_per_shift_index[earlier_shift_index].unwrap()];
+                    return placement.is_overlapping(earlier_placement, self.padding);
+                });
+                if !is_overlapping {
+                    self.current_placement_index_per_shift_index[current_shift_index] = Some(next_placement_index);
+                    return true;
+                }
+                next_placement_index += 1;
+            }
+            self.current_placement_index_per_shift_index[current_shift_index] = Some(placements.len());
+        }
+        return false;
+    }
+    fn get_indexed_element(&self) -> IndexedElement<Self::T> {
+        let (element_index, state_index) = self.get_element_index_and_state_index();
+        return IndexedElement::new(self.states[state_index].clone(), element_index);
+    }
+    fn get_length(&self) -> usize {
+        return self.rectangles_length;
+    }
+    fn get_element_index_and_state_index(&self) -> (usize, usize) {
+        let current_shift_index = self.current_shift_index.unwrap();
+        let placement = &self.placements_per_rectangle_index[current_shift_index][self.current_placement_index_per_shift_index[current_shift_index].unwrap()];
+        let mut state_index = placement.y * self.bounding_width + placement.x;
+        if placement.is_rotated {
+            state_index += self.rotated_states_offset;
+        }
+        return (current_shift_index, state_index);
+    }
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.states;
+    }
+    fn randomize(&mut self) {
+        for placements in self.placements_per_rectangle_index.iter_mut() {
+            fastrand::shuffle(placements);
+        }
+    }
+    fn is_rotation_possible(&self) -> bool {
+        return self.is_rotation_possible;
+    }
+}
+
+#[cfg(test)]
+mod rectangle_packing_shifter_tests {
+    use std::collections::BTreeSet;
+
+    use crate::{CellGroup, incrementer::{Incrementer, shifter_incrementer::ShifterIncrementer, shifting_cell_group_dependency_incrementer::{CellGroupDependency, ShiftingCellGroupDependencyIncrementer}}};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    // returns each collection of cells covered per rectangle index, found by checking every combination of locations and rotations
+    fn get_packed_arrangements(rectangles: &Vec<(usize, usize)>, bounding_size: (usize, usize), padding: usize, is_rotation_permitted: bool) -> BTreeSet<Vec<(usize, usize, usize, usize)>> {
+        let mut arrangements: BTreeSet<Vec<(usize, usize, usize, usize)>> = BTreeSet::new();
+        let mut pending_arrangements: Vec<Vec<(usize, usize, usize, usize)>> = vec![Vec::new()];
+        while let Some(arrangement) = pending_arrangements.pop() {
+            if arrangement.len() == rectangles.len() {
+                arrangements.insert(arrangement);
+                continue;
+            }
+            let (width, height) = rectangles[arrangement.len()];
+            let mut sizes: Vec<(usize, usize)> = vec![(width, height)];
+            if is_rotation_permitted && width != height {
+                sizes.push((height, width));
+            }
+            for (width, height) in sizes.into_iter() {
+                for y in 0..bounding_size.1 {
+                    for x in 0..bounding_size.0 {
+                        if x + width > bounding_size.0 || y + height > bounding_size.1 {
+                            continue;
+                        }
+                        let is_separated = arrangement.iter().all(|(other_x, other_y, other_width, other_height)| {
+                            x >= other_x + other_width + padding || *other_x >= x + width + padding || y >= other_y + other_height + padding || *other_y >= y + height + padding
+                        });
+                        if is_separated {
+                            let mut next_arrangement = arrangement.clone();
+                            next_arrangement.push((x, y, width, height));
+                            pending_arrangements.push(next_arrangement);
+                        }
+                    }
+                }
+            }
+        }
+        return arrangements;
+    }
+
+    #[rstest]
+    #[case(vec![(2, 1), (1, 1)], (3, 2), 0, false)]
+    #[case(vec![(2, 1), (1, 1)], (3, 3), 0, true)]
+    #[case(vec![(2, 1), (1, 2), (1, 1)], (4, 3), 0, true)]
+    #[case(vec![(1, 1), (1, 1)], (3, 3), 1, false)]
+    #[case(vec![(3, 1), (1, 2)], (4, 4), 1, true)]
+    fn each_arrangement_without_overlap_once(#[case] rectangles: Vec<(usize, usize)>, #[case] bounding_size: (usize, usize), #[case] padding: usize, #[case] is_rotation_permitted: bool) {
+        init();
+
+        let expected_arrangements = get_packed_arrangements(&rectangles, bounding_size, padding, is_rotation_permitted);
+        assert!(expected_arrangements.len() > 1);
+        let origin: (u8, u8) = (10, 20);
+        // the rotation of each placement is only known from its state index, so a separate shifter is kept for checking the state indexes
+        let rotation_shifter = RectanglePackingShifter::new(rectangles.clone(), origin, bounding_size, padding, is_rotation_permitted);
+        for is_randomized in [false, true] {
+            let mut rectangle_packing_shifter = RectanglePackingShifter::new(rectangles.clone(), origin, bounding_size, padding, is_rotation_permitted);
+            if is_randomized {
+                rectangle_packing_shifter.randomize();
+            }
+            let mut shifter_incrementer = ShifterIncrementer::new(Box::new(rectangle_packing_shifter), (0..rectangles.len()).collect());
+            let mut arrangements: BTreeSet<Vec<(usize, usize, usize, usize)>> = BTreeSet::new();
+            for _ in 0..2 {
+                arrangements.clear();
+                while shifter_incrementer.try_increment() {
+                    let arrangement: Vec<(usize, usize, usize, usize)> = shifter_incrementer.get_element_index_and_state_index_pairs().unwrap().iter()
+                        .map(|(element_index, state_index)| {
+                            assert!(*element_index < rectangles.len());
+                            let (mut width, mut height) = rectangles[*element_index];
+                            if rotation_shifter.is_rotated_state_index(*state_index) {
+                                (width, height) = (height, width);
+                            }
+                            let location = shifter_incrementer.get_state(*state_index).unwrap();
+                            ((location.0 - origin.0) as usize, (location.1 - origin.1) as usize, width, height)
+                        })
+                        .collect();
+                    assert_eq!(rectangles.len(), shifter_incrementer.get().len());
+                    assert!(arrangements.insert(arrangement.clone()), "arrangement {arrangement:?} returned more than once when randomized {is_randomized}");
+                }
+                shifter_incrementer.reset();
+            }
+            assert_eq!(expected_arrangements, arrangements, "randomized {is_randomized}");
+        }
+    }
+
+    #[rstest]
+    fn element_indexes_stay_within_length() {
+        init();
+
+        let rectangles: Vec<(usize, usize)> = vec![(2, 1), (1, 3)];
+        let mut rectangle_packing_shifter = RectanglePackingShifter::new(rectangles.clone(), (0, 0), (3, 3), 0, true);
+        let mut paths_total: usize = 0;
+        for indexed_elements in rectangle_packing_shifter.iter_paths() {
+            assert_eq!(vec![0, 1], indexed_elements.iter().map(|indexed_element| indexed_element.index).collect::<Vec<usize>>());
+            paths_total += 1;
+        }
+        assert_eq!(get_packed_arrangements(&rectangles, (3, 3), 0, true).len(), paths_total);
+    }
+
+    #[rstest]
+    fn unrotated_rectangles_map_to_cell_groups() {
+        init();
+
+        // the horizontal domino and the single cell
+        let cell_groups: Rc<Vec<CellGroup>> = Rc::new(vec![
+            CellGroup {
+                cells: vec![(0, 0), (1, 0)]
+            },
+            CellGroup {
+                cells: vec![(0, 0)]
+            }
+        ]);
+        let rectangles: Vec<(usize, usize)> = vec![(2, 1), (1, 1)];
+        let cell_group_dependencies: Vec<CellGroupDependency> = vec![
+            CellGroupDependency::new(vec![0, 1], Box::new(RectanglePackingShifter::new(rectangles.clone(), (0, 0), (3, 2), 0, false)))
+        ];
+        let shifting_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new(cell_groups, cell_group_dependencies, None, None);
+        let increments_total = shifting_cell_group_dependency_incrementer.into_incrementer_iter().count();
+        assert_eq!(get_packed_arrangements(&rectangles, (3, 2), 0, false).len(), increments_total);
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn rotated_rectangles_are_rejected_by_cell_group_dependency() {
+        init();
+
+        let rectangles: Vec<(usize, usize)> = vec![(2, 1), (1, 1)];
+        CellGroupDependency::new(vec![0, 1], Box::new(RectanglePackingShifter::new(rectangles, (0, 0), (3, 3), 0, true)));
+    }
+
+    #[rstest]
+    fn square_rectangles_with_rotation_are_permitted_by_cell_group_dependency() {
+        init();
+
+        // square rectangles are never rotated, so the cell groups are compared correctly
+        let rectangles: Vec<(usize, usize)> = vec![(1, 1), (1, 1)];
+        let rectangle_packing_shifter = RectanglePackingShifter::new(rectangles, (0, 0), (3, 3), 0, true);
+        assert!(!rectangle_packing_shifter.is_rotation_possible());
+        CellGroupDependency::new(vec![0, 1], Box::new(rectangle_packing_shifter));
+    }
+
+    #[rstest]
+    #[case((250, 0), (6, 1))]
+    #[case((0, 200), (1, 56))]
+    fn bounding_box_ending_at_largest_location_is_permitted(#[case] origin: (u8, u8), #[case] bounding_size: (usize, usize)) {
+        init();
+
+        let mut rectangle_packing_shifter = RectanglePackingShifter::new(vec![(1, 1)], origin, bounding_size, 0, false);
+        let last_location = rectangle_packing_shifter.iter_paths().last().unwrap()[0].element.as_ref().clone();
+        assert_eq!(255, if bounding_size.0 > 1 { last_location.0 } else { last_location.1 });
+    }
+
+    #[rstest]
+    #[should_panic]
+    #[case((250, 0), (7, 1))]
+    #[should_panic]
+    #[case((0, 200), (1, 57))]
+    fn bounding_box_beyond_largest_location_is_rejected(#[case] origin: (u8, u8), #[case] bounding_size: (usize, usize)) {
+        RectanglePackingShifter::new(vec![(1, 1)], origin, bounding_size, 0, false);
+    }
+}
@@ -0,0 +1,197 @@
+use std::rc::Rc;
+use crate::IndexedElement;
+use super::Shifter;
+
+// Purpose:
+//     To shift over the same states as another shifter except that the states of each shift are incremented from last to first
+//     The states of a shift are collected from the shifter being wrapped the first time that the shift is incremented and then returned in reverse
+// Notes:
+//     Collecting the states of a shift leaves the shifter being wrapped past the last state of that shift, so when the later shifts depend on the earlier shifts the shifter being wrapped is moved back to each returned state by restarting the shift and incrementing up to it, which is quadratic in the number of states per shift
+pub struct ReversedShifter<T> {
+    shifter: Box<dyn Shifter<T = T>>,
+    is_each_shift_independent: bool,
+    states: Vec<Rc<T>>,
+    current_shift_index: Option<usize>,
+    remaining_element_index_and_state_index_tuples_per_shift_index: Vec<Option<Vec<(usize, usize)>>>,
+    current_element_index_and_state_index_per_shift_index: Vec<Option<(usize, usize)>>
+}
+
+impl<T> ReversedShifter<T> {
+    pub fn new(shifter: Box<dyn Shifter<T = T>>) -> Self {
+        let is_each_shift_independent = shifter.is_each_shift_independent();
+        let states = shifter.get_states();
+        ReversedShifter {
+            shifter: shifter,
+            is_each_shift_independent: is_each_shift_independent,
+            states: states,
+            current_shift_index: None,
+            remaining_element_index_and_state_index_tuples_per_shift_index: Vec::new(),
+            current_element_index_and_state_index_per_shift_index: Vec::new()
+        }
+    }
+}
+
+impl<T> Shifter for ReversedShifter<T> {
+    type T = T;
+
+    fn try_forward(&mut self) -> bool {
+        let is_forward = self.shifter.try_forward();
+        let next_shift_index = match self.current_shift_index {
+            Some(current_shift_index) => {
+                if current_shift_index == self.shifter.get_length() {
+                    return is_forward;
+                }
+                current_shift_index + 1
+            },
+            None => 0
+        };
+        self.current_shift_index = Some(next_shift_index);
+        if next_shift_index != self.shifter.get_length() {
+            self.remaining_element_index_and_state_index_tuples_per_shift_index.push(None);
+            self.current_element_index_and_state_index_per_shift_index.push(None);
+        }
+        return is_forward;
+    }
+    fn try_backward(&mut self) -> bool {
+        let is_backward = self.shifter.try_backward();
+        if let Some(current_shift_index) = self.current_shift_index {
+            if current_shift_index != self.shifter.get_length() {
+                self.remaining_element_index_and_state_index_tuples_per_shift_index.pop();
+                self.current_element_index_and_state_index_per_shift_index.pop();
+            }
+            if current_shift_index == 0 {
+                self.current_shift_index = None;
+            }
+            else {
+                self.current_shift_index = Some(current_shift_index - 1);
+            }
+        }
+        return is_backward;
+    }
+    fn try_increment(&mut self) -> bool {
+        if let Some(current_shift_index) = self.current_shift_index {
+            if current_shift_index == self.shifter.get_length() {
+                return false;
+            }
+            if self.remaining_element_index_and_state_index_tuples_per_shift_index[current_shift_index].is_none() {
+                let mut element_index_and_state_index_tuples: Vec<(usize, usize)> = Vec::new();
+                while self.shifter.try_increment() {
+                    element_index_and_state_index_tuples.push(self.shifter.get_element_index_and_state_index());
+                }
+                self.remaining_element_index_and_state_index_tuples_per_shift_index[current_shift_index] = Some(element_index_and_state_index_tuples);
+            }
+            let remaining_element_index_and_state_index_tuples = self.remaining_element_index_and_state_index_tuples_per_shift_index[current_shift_index].as_mut().unwrap();
+            let element_index_and_state_index_option = remaining_element_index_and_state_index_tuples.pop();
+            if element_index_and_state_index_option.is_some() && !self.is_each_shift_independent {
+                // restart the shift of the shifter being wrapped and increment up to the returned state so that the later shifts follow from it
+                let increments_total = remaining_element_index_and_state_index_tuples.len() + 1;
+                self.shifter.try_backward();
+                if !self.shifter.try_forward() {
+                    panic!("Unexpectedly failed to move forward when restarting a shift.");
+                }
+                for _ in 0..increments_total {
+                    if !self.shifter.try_increment() {
+                        panic!("Unexpectedly failed to increment to a state that was already found.");
+                    }
+                }
+            }
+            self.current_element_index_and_state_index_per_shift_index[current_shift_index] = element_index_and_state_index_option;
+            return element_index_and_state_index_option.is_some();
+        }
+        return false;
+    }
+    fn get_indexed_element(&self) -> IndexedElement<Self::T> {
+        let (element_index, state_index) = self.get_element_index_and_state_index();
+        return IndexedElement::new(self.states[state_index].clone(), element_index);
+    }
+    fn get_length(&self) -> usize {
+        return self.shifter.get_length();
+    }
+    fn get_element_index_and_state_index(&self) -> (usize, usize) {
+        return self.current_element_index_and_state_index_per_shift_index[self.current_shift_index.unwrap()].unwrap();
+    }
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.states;
+    }
+    fn randomize(&mut self) {
+        self.shifter.randomize();
+    }
+    fn get_state_indexes_per_element_index(&self) -> Option<Vec<Vec<usize>>> {
+        return self.shifter.get_state_indexes_per_element_index()
+            .map(|state_indexes_per_element_index| {
+                state_indexes_per_element_index.into_iter()
+                    .map(|state_indexes| state_indexes.into_iter().rev().collect())
+                    .collect()
+            });
+    }
+    fn is_each_shift_independent(&self) -> bool {
+        return self.is_each_shift_independent;
+    }
+    fn is_rotation_possible(&self) -> bool {
+        return self.shifter.is_rotation_possible();
+    }
+}
+
+#[cfg(test)]
+mod reversed_shifter_tests {
+    use crate::shifter::{index_shifter::IndexShifter, segment_permutation_shifter::{Segment, SegmentPermutationShifter}};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    #[rstest]
+    fn index_shifter_states_are_reversed() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![
+            vec![Rc::new(0), Rc::new(1)],
+            vec![Rc::new(2), Rc::new(3), Rc::new(4)]
+        ];
+        let mut reversed_shifter = ReversedShifter::new(Box::new(IndexShifter::new(&states_per_shift_index)));
+        for _ in 0..2 {
+            let states_per_path: Vec<Vec<u8>> = reversed_shifter.iter_paths()
+                .map(|path| path.iter().map(|indexed_element| *indexed_element.element).collect())
+                .collect();
+            assert_eq!(vec![
+                vec![1, 4],
+                vec![1, 3],
+                vec![1, 2],
+                vec![0, 4],
+                vec![0, 3],
+                vec![0, 2]
+            ], states_per_path);
+        }
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn dependent_shifts_are_reversed(#[case] is_swapping_permitted: bool) {
+        init();
+
+        let segments: Vec<Rc<Segment>> = vec![
+            Rc::new(Segment::new(1)),
+            Rc::new(Segment::new(2)),
+            Rc::new(Segment::new(1))
+        ];
+        let mut segment_permutation_shifter = SegmentPermutationShifter::new(segments.clone(), (0, 0), 8, true, 1, is_swapping_permitted);
+        let paths: Vec<Vec<(usize, (u8, u8))>> = segment_permutation_shifter.iter_paths()
+            .map(|path| path.iter().map(|indexed_element| (indexed_element.index, *indexed_element.element)).collect())
+            .collect();
+        let mut reversed_shifter = ReversedShifter::new(Box::new(SegmentPermutationShifter::new(segments, (0, 0), 8, true, 1, is_swapping_permitted)));
+        for _ in 0..2 {
+            let reversed_paths: Vec<Vec<(usize, (u8, u8))>> = reversed_shifter.iter_paths()
+                .map(|path| path.iter().map(|indexed_element| (indexed_element.index, *indexed_element.element)).collect())
+                .collect();
+            // each shift is reversed, so the full paths are found in the reverse order
+            let mut expected_reversed_paths = paths.clone();
+            expected_reversed_paths.reverse();
+            assert_eq!(expected_reversed_paths, reversed_paths);
+        }
+    }
+}
@@ -15,7 +15,8 @@ pub struct ScalingSquareBreadthFirstSearchShifter {
     current_binary_density_mask: BitVec,
     current_scale_per_index: Vec<Option<usize>>,
     current_index: Option<usize>,
-    possible_states: Vec<Rc<usize>>
+    possible_states: Vec<Rc<usize>>,
+    state_table: Vec<usize>
 }
 
 impl ScalingSquareBreadthFirstSearchShifter {
@@ -32,7 +33,25 @@ impl ScalingSquareBreadthFirstSearchShifter {
             current_binary_density_mask: BitVec::repeat(false, length),
             current_scale_per_index: Vec::new(),
             current_index: None,
-            possible_states: possible_states
+            possible_states: possible_states,
+            state_table: (0..=maximum_scale).collect()
+        }
+    }
+    fn set_current_binary_density_mask(&mut self) {
+        self.current_binary_density_mask = BitVec::repeat(false, self.length);
+        if let Some(mut bits) = self.binary_density_incrementer.get_bits() {
+            // visit only the set bits of the word, lowest index first
+            while bits != 0 {
+                self.current_binary_density_mask.set(bits.trailing_zeros() as usize, true);
+                bits &= bits - 1;
+            }
+        }
+        else {
+            for indexed_element in self.binary_density_incrementer.get() {
+                if *indexed_element.element.as_ref() {
+                    self.current_binary_density_mask.set(indexed_element.index, true);
+                }
+            }
         }
     }
     pub fn get_scaling_index(&self) -> usize {
@@ -62,12 +81,7 @@ impl Shifter for ScalingSquareBreadthFirstSearchShifter {
             self.current_index = Some(0);
             self.current_scale_per_index.push(None);
             // store the next binary density mask
-            self.current_binary_density_mask = BitVec::repeat(false, self.length);
-            for indexed_element in self.binary_density_incrementer.get() {
-                if *indexed_element.element.as_ref() {
-                    self.current_binary_density_mask.set(indexed_element.index, true);
-                }
-            }
+            self.set_current_binary_density_mask();
             return true;
         }
         if self.current_index.unwrap() == self.length {
@@ -141,12 +155,7 @@ impl Shifter for ScalingSquareBreadthFirstSearchShifter {
                     // fall through and collect the updated mask and set the 0th self.current_scale_per_index
                 }
                 // reset self.current_binary_density_mask
-                self.current_binary_density_mask = BitVec::repeat(false, self.length);
-                for indexed_element in self.binary_density_incrementer.get() {
-                    if *indexed_element.element.as_ref() {
-                        self.current_binary_density_mask.set(indexed_element.index, true);
-                    }
-                }
+                self.set_current_binary_density_mask();
                 // initialize the 0th element
                 if self.current_binary_density_mask[0] {
                     self.current_scale_per_index.push(Some(self.current_scale));
@@ -176,8 +185,11 @@ impl Shifter for ScalingSquareBreadthFirstSearchShifter {
         let current_index = self.current_index.unwrap();
         return (current_index, self.current_scale_per_index[current_index].unwrap());
     }
-    fn get_states(&self) -> Vec<Rc<Self::T>> {
-        return self.possible_states.clone();
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.possible_states;
+    }
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return Some(&self.state_table);
     }
     fn randomize(&mut self) {
         todo!();
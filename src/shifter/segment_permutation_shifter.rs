@@ -1,21 +1,65 @@
-use std::{rc::Rc, collections::VecDeque};
+use std::{rc::Rc, collections::{VecDeque, HashMap}};
 use bitvec::vec::BitVec;
-use crate::{IndexedElement, get_n_choose_k};
+use crate::{IndexedElement, combinatorics::{Count, get_binomial, get_factorial}};
 
 use super::{Shifter};
 
+// the weighted arrangement totals are memoized per set of used segments and minimum position offset, which grows exponentially with the number of swappable segments, so weighted sampling is abandoned for the unweighted randomization once this many totals are memoized
+const WEIGHTED_ARRANGEMENTS_TOTALS_CAPACITY: usize = 1 << 12;
+// the unweighted randomization does not account for the maximum spacing, so arrangements exceeding it are rejected and sampled again, up to this many times before a random walk that always stays within the maximum spacing is used instead
+const MAXIMUM_SPACING_REJECTIONS_TOTAL: usize = 1 << 8;
+
 /// This struct is an unfixed line segment.
 #[derive(Clone, Debug)]
 pub struct Segment {
-    length: usize
+    length: usize,
+    is_optional: bool
 }
 
 impl Segment {
     pub fn new(length: usize) -> Self {
         Segment {
-            length: length
+            length: length,
+            is_optional: false
+        }
+    }
+    /// An optional segment may be omitted by the OptionalSegmentPermutationIncrementer, but the SegmentPermutationShifter always places every segment it is provided and so panics if provided an optional segment.
+    pub fn new_optional(length: usize) -> Self {
+        Segment {
+            length: length,
+            is_optional: true
         }
     }
+    pub fn get_length(&self) -> usize {
+        return self.length;
+    }
+    pub fn is_optional(&self) -> bool {
+        return self.is_optional;
+    }
+}
+
+/// This struct is the permitted number of empty positions between two neighboring segments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spacing {
+    minimum: usize,
+    maximum: Option<usize>
+}
+
+impl Spacing {
+    pub fn new(minimum: usize, maximum: Option<usize>) -> Self {
+        if let Some(maximum) = maximum {
+            if maximum < minimum {
+                panic!("Unexpected maximum spacing less than the minimum spacing.");
+            }
+        }
+        Spacing {
+            minimum: minimum,
+            maximum: maximum
+        }
+    }
+    pub fn get_minimum(&self) -> usize {
+        return self.minimum;
+    }
 }
 
 /// This struct is a fixed line segment.
@@ -34,15 +78,35 @@ impl LocatedSegment {
     }
 }
 
+// the memoized totals used while counting the arrangements
+struct ArrangementsTotals<T> {
+    orderings_total_per_state: HashMap<Vec<bool>, Option<T>>,
+    position_offsets_total_per_state: HashMap<(usize, usize), Option<T>>,
+    arrangements_total_per_state: HashMap<(Vec<bool>, Option<(usize, usize)>), Option<T>>
+}
+
+impl<T> ArrangementsTotals<T> {
+    fn new() -> Self {
+        ArrangementsTotals {
+            orderings_total_per_state: HashMap::new(),
+            position_offsets_total_per_state: HashMap::new(),
+            arrangements_total_per_state: HashMap::new()
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SegmentPermutationShifter {
     segments: Vec<Rc<Segment>>,
-    origin: (u8, u8),
+    path: Vec<Rc<(u8, u8)>>,
     bounding_length: usize,
-    is_horizontal: bool,
-    padding: usize,
+    spacing_per_segment_index: Vec<Spacing>,
+    is_spacing_uniform: bool,
+    is_maximum_spacing_used: bool,
     is_swapping_permitted: bool,
+    preceding_segment_indexes_per_segment_index: Vec<Vec<usize>>,
     possible_locations: Vec<Rc<(u8, u8)>>,
+    possible_location_table: Vec<(u8, u8)>,
     current_mask: BitVec,
     current_segment_index_per_shift_index: Vec<usize>,
     current_initial_position_offset_per_shift_index: Vec<usize>,
@@ -61,26 +125,76 @@ pub struct SegmentPermutationShifter {
     is_starting: bool,  // true if "starting" states are still being pulled from
     is_looped: bool,  // true if one cycle has been performed on the mask
     is_starting_equal_to_ending: bool,  // true if the starting and ending positions are the same
-    is_starting_at_beginning: bool  // true if the segments starting in sequential order and flush to the left
+    is_starting_at_beginning: bool,  // true if the segments starting in sequential order and flush to the left
+    position_weights_per_segment_index: Option<Vec<Vec<f32>>>
 }
 
 impl SegmentPermutationShifter {
     pub fn new(segments: Vec<Rc<Segment>>, origin: (u8, u8), bounding_length: usize, is_horizontal: bool, padding: usize, is_swapping_permitted: bool) -> Self {
+        let spacing_per_segment_index: Vec<Spacing> = vec![Spacing::new(padding, None); segments.len()];
+        return Self::new_with_spacing(segments, origin, bounding_length, is_horizontal, spacing_per_segment_index, is_swapping_permitted);
+    }
+    /// The spacing is per segment index, where the spacing of a segment is the gap after it whenever another segment follows it, so that the gap between two neighboring segments stays with them when they are swapped elsewhere along the line. The spacing of whichever segment is placed last is unused.
+    pub fn new_with_spacing(segments: Vec<Rc<Segment>>, origin: (u8, u8), bounding_length: usize, is_horizontal: bool, spacing_per_segment_index: Vec<Spacing>, is_swapping_permitted: bool) -> Self {
+        let preceding_segment_indexes_per_segment_index: Vec<Vec<usize>> = vec![Vec::new(); segments.len()];
+        return Self::new_with_spacing_and_preceding_segment_indexes(segments, Self::get_straight_path(origin, bounding_length, is_horizontal), spacing_per_segment_index, is_swapping_permitted, preceding_segment_indexes_per_segment_index);
+    }
+    /// Each precedence pair is a segment index that must always be placed somewhere before the other segment index, while every segment not constrained by a precedence pair may be swapped freely.
+    pub fn new_with_precedence(segments: Vec<Rc<Segment>>, origin: (u8, u8), bounding_length: usize, is_horizontal: bool, spacing_per_segment_index: Vec<Spacing>, precedence_pairs: Vec<(usize, usize)>) -> Self {
+        let mut preceding_segment_indexes_per_segment_index: Vec<Vec<usize>> = vec![Vec::new(); segments.len()];
+        for (preceding_segment_index, following_segment_index) in precedence_pairs.into_iter() {
+            if preceding_segment_index >= segments.len() || following_segment_index >= segments.len() {
+                panic!("Unexpected segment index outside of the segments in precedence pair.");
+            }
+            if preceding_segment_index == following_segment_index {
+                panic!("Unexpected segment index preceding itself in precedence pair.");
+            }
+            if !preceding_segment_indexes_per_segment_index[following_segment_index].contains(&preceding_segment_index) {
+                preceding_segment_indexes_per_segment_index[following_segment_index].push(preceding_segment_index);
+            }
+        }
+        return Self::new_with_spacing_and_preceding_segment_indexes(segments, Self::get_straight_path(origin, bounding_length, is_horizontal), spacing_per_segment_index, true, preceding_segment_indexes_per_segment_index);
+    }
+    /// The path is the ordered collection of locations that the segments are placed along, where each location is expected to neighbor the next, such that the position offset of a segment is the index of its first location within the path.
+    pub fn new_along_path(segments: Vec<Rc<Segment>>, path: Vec<(u8, u8)>, padding: usize, is_swapping_permitted: bool) -> Self {
+        let spacing_per_segment_index: Vec<Spacing> = vec![Spacing::new(padding, None); segments.len()];
+        let preceding_segment_indexes_per_segment_index: Vec<Vec<usize>> = vec![Vec::new(); segments.len()];
+        return Self::new_with_spacing_and_preceding_segment_indexes(segments, path, spacing_per_segment_index, is_swapping_permitted, preceding_segment_indexes_per_segment_index);
+    }
+    // returns the locations from the origin along the horizontal or vertical line
+    fn get_straight_path(origin: (u8, u8), bounding_length: usize, is_horizontal: bool) -> Vec<(u8, u8)> {
+        let mut path: Vec<(u8, u8)> = Vec::new();
+        for position_offset in 0..bounding_length {
+            if is_horizontal {
+                path.push((origin.0 + position_offset as u8, origin.1));
+            }
+            else {
+                path.push((origin.0, origin.1 + position_offset as u8));
+            }
+        }
+        return path;
+    }
+    fn new_with_spacing_and_preceding_segment_indexes(segments: Vec<Rc<Segment>>, path: Vec<(u8, u8)>, spacing_per_segment_index: Vec<Spacing>, is_swapping_permitted: bool, preceding_segment_indexes_per_segment_index: Vec<Vec<usize>>) -> Self {
+        if spacing_per_segment_index.len() != segments.len() {
+            panic!("Unexpected mismatch between the number of segments and the number of spacings.");
+        }
+        if segments.iter().any(|segment| segment.is_optional) {
+            panic!("Unexpected optional segment since every segment is always placed.");
+        }
         let segments_length = segments.len();
+        let bounding_length = path.len();
+        let path: Vec<Rc<(u8, u8)>> = path.into_iter().map(Rc::new).collect();
+        let is_spacing_uniform = spacing_per_segment_index.windows(2).all(|spacings| spacings[0] == spacings[1]);
+        let is_maximum_spacing_used = spacing_per_segment_index.iter().any(|spacing| spacing.maximum.is_some());
 
         let mut current_mask: BitVec = BitVec::with_capacity(segments_length);
         current_mask.resize(segments.len(), false);
 
         let mut smallest_segment_length_option: Option<usize> = None;
-        let mut smallest_bounding_length: usize = 0;
-        for (segment_index, segment) in segments.iter().enumerate() {
+        for segment in segments.iter() {
             if smallest_segment_length_option.is_none() || smallest_segment_length_option.unwrap() > segment.length {
                 smallest_segment_length_option = Some(segment.length);
             }
-            if segment_index != 0 {
-                smallest_bounding_length += padding;
-            }
-            smallest_bounding_length += segment.length;
         }
 
         let reduced_bounding_length_offset: usize;
@@ -96,97 +210,22 @@ impl SegmentPermutationShifter {
             reduced_bounding_length_offset = 0;
         }
 
-        let mut possible_locations: Vec<Rc<(u8, u8)>> = Vec::new();
-        let mut current_possible_location = origin.clone();
-        if is_horizontal {
-            for _ in 0..(bounding_length - reduced_bounding_length_offset) {
-                possible_locations.push(Rc::new(current_possible_location));
-                current_possible_location.0 += 1;
-            }
-        }
-        else {
-            for _ in 0..(bounding_length - reduced_bounding_length_offset) {
-                possible_locations.push(Rc::new(current_possible_location));
-                current_possible_location.1 += 1;
-            }
-        }
-
-        let mut starting_segment_index_per_shift_index: Vec<usize> = Vec::new();  // the "ending" state is always going to occur when the segment indexes are sequential with the shift indexes, but it may randomly be at the very end
-        let mut starting_minimum_position_offset_per_shift_index: Vec<usize> = Vec::new();
-        let mut starting_maximum_position_offset_per_shift_index: Vec<usize> = Vec::new();
-        let starting_initial_position_offset_per_shift_index: Vec<usize>;
-        let mut ending_segment_index_per_shift_index: Vec<usize> = Vec::new();
-        let mut ending_position_offset_per_shift_index: Vec<usize> = Vec::new();
-
-        // initialize "ending" objects
-        {
-            for shift_index in 0..segments_length {
-                if is_swapping_permitted {
-                    ending_segment_index_per_shift_index.push(segments_length - shift_index - 1);
-                }
-                else {
-                    ending_segment_index_per_shift_index.push(shift_index);
-                }
-            }
-            if is_swapping_permitted {
-                ending_position_offset_per_shift_index.push(bounding_length - smallest_bounding_length);
-                for shift_index in (1..segments_length).rev() {
-                    let previous_segment_length = segments[shift_index].length + padding;
-                    let previous_ending_position_offset = ending_position_offset_per_shift_index[segments_length - shift_index - 1];
-                    ending_position_offset_per_shift_index.push(previous_ending_position_offset + previous_segment_length);
-                }
-            }
-            else {
-                ending_position_offset_per_shift_index.push(bounding_length - smallest_bounding_length);
-                for shift_index in 1..segments_length {
-                    let previous_segment_length = segments[shift_index - 1].length + padding;
-                    let previous_ending_position_offset = ending_position_offset_per_shift_index[shift_index - 1];
-                    ending_position_offset_per_shift_index.push(previous_ending_position_offset + previous_segment_length);
-                }
-            }
-        }
-
-        // initialize "starting" objects
-        {
-            for shift_index in 0..segments_length {
-                starting_segment_index_per_shift_index.push(shift_index);
-            }
-            let next_minimum_position_offset = 0;
-            let next_maximum_position_offset = bounding_length - smallest_bounding_length;
-            starting_minimum_position_offset_per_shift_index.push(next_minimum_position_offset);
-            starting_maximum_position_offset_per_shift_index.push(next_maximum_position_offset);
-            for shift_index in 1..segments_length {
-                let previous_segment_length = segments[shift_index - 1].length + padding;
-                let previous_minimum_position_offset = starting_minimum_position_offset_per_shift_index[shift_index - 1];
-                let previous_maximum_position_offset = starting_maximum_position_offset_per_shift_index[shift_index - 1];
-                starting_minimum_position_offset_per_shift_index.push(previous_minimum_position_offset + previous_segment_length);
-                starting_maximum_position_offset_per_shift_index.push(previous_maximum_position_offset + previous_segment_length);
-            }
-            starting_initial_position_offset_per_shift_index = starting_minimum_position_offset_per_shift_index.clone();
-        }
-
-        let mut is_starting_equal_to_ending: bool = true;  // TODO save this for when incrementing early while is_starting
-        for shift_index in 0..segments_length {
-            if starting_initial_position_offset_per_shift_index[shift_index] != ending_position_offset_per_shift_index[shift_index] {
-                is_starting_equal_to_ending = false;
-                break;
-            }
-            if starting_segment_index_per_shift_index[shift_index] != ending_segment_index_per_shift_index[shift_index] {
-                is_starting_equal_to_ending = false;
-                break;
-            }
-        }
+        let possible_locations: Vec<Rc<(u8, u8)>> = path[..(bounding_length - reduced_bounding_length_offset)].to_vec();
+        let possible_location_table: Vec<(u8, u8)> = possible_locations.iter().map(|possible_location| *possible_location.as_ref()).collect();
 
         let is_starting_at_beginning = true;
 
         let mut segment_permutation_shifter = SegmentPermutationShifter {
             segments: segments,
-            origin: origin,
+            path: path,
             bounding_length: bounding_length,
-            is_horizontal: is_horizontal,
-            padding: padding,
+            spacing_per_segment_index: spacing_per_segment_index,
+            is_spacing_uniform: is_spacing_uniform,
+            is_maximum_spacing_used: is_maximum_spacing_used,
             is_swapping_permitted: is_swapping_permitted,
+            preceding_segment_indexes_per_segment_index: preceding_segment_indexes_per_segment_index,
             possible_locations: possible_locations,
+            possible_location_table: possible_location_table,
             current_mask: current_mask,
             current_segment_index_per_shift_index: Vec::new(),
             current_initial_position_offset_per_shift_index: Vec::new(),
@@ -196,24 +235,200 @@ impl SegmentPermutationShifter {
             current_is_parent_ending: BitVec::new(),
             is_shifted_outside: false,
             segments_length: segments_length,
-            starting_segment_index_per_shift_index: starting_segment_index_per_shift_index,
-            starting_initial_position_offset_per_shift_index: starting_initial_position_offset_per_shift_index,
-            starting_minimum_position_offset_per_shift_index: starting_minimum_position_offset_per_shift_index,
-            starting_maximum_position_offset_per_shift_index: starting_maximum_position_offset_per_shift_index,
-            ending_segment_index_per_shift_index: ending_segment_index_per_shift_index,
-            ending_position_offset_per_shift_index: ending_position_offset_per_shift_index,
+            starting_segment_index_per_shift_index: Vec::new(),
+            starting_initial_position_offset_per_shift_index: Vec::new(),
+            starting_minimum_position_offset_per_shift_index: Vec::new(),
+            starting_maximum_position_offset_per_shift_index: Vec::new(),
+            ending_segment_index_per_shift_index: Vec::new(),
+            ending_position_offset_per_shift_index: Vec::new(),
             is_starting: true,
             is_looped: false,
-            is_starting_equal_to_ending: is_starting_equal_to_ending,
-            is_starting_at_beginning: is_starting_at_beginning
+            is_starting_equal_to_ending: true,
+            is_starting_at_beginning: is_starting_at_beginning,
+            position_weights_per_segment_index: None
         };
 
+        // the "ending" state is the last arrangement and the "starting" state is the first arrangement
+        {
+            let mut ending_segment_index_per_shift_index: Vec<usize> = Vec::new();
+            let mut ending_position_offset_per_shift_index: Vec<usize> = Vec::new();
+            segment_permutation_shifter.set_bounding_arrangement_from(&mut ending_segment_index_per_shift_index, &mut ending_position_offset_per_shift_index, 0, false);
+            segment_permutation_shifter.ending_segment_index_per_shift_index = ending_segment_index_per_shift_index;
+            segment_permutation_shifter.ending_position_offset_per_shift_index = ending_position_offset_per_shift_index;
+        }
+        {
+            let mut starting_segment_index_per_shift_index: Vec<usize> = Vec::new();
+            let mut starting_initial_position_offset_per_shift_index: Vec<usize> = Vec::new();
+            segment_permutation_shifter.set_bounding_arrangement_from(&mut starting_segment_index_per_shift_index, &mut starting_initial_position_offset_per_shift_index, 0, true);
+            segment_permutation_shifter.starting_segment_index_per_shift_index = starting_segment_index_per_shift_index;
+            segment_permutation_shifter.starting_initial_position_offset_per_shift_index = starting_initial_position_offset_per_shift_index;
+            segment_permutation_shifter.set_starting_position_offset_ranges();
+        }
+
+        segment_permutation_shifter.is_starting_equal_to_ending = segment_permutation_shifter.starting_segment_index_per_shift_index == segment_permutation_shifter.ending_segment_index_per_shift_index && segment_permutation_shifter.starting_initial_position_offset_per_shift_index == segment_permutation_shifter.ending_position_offset_per_shift_index;  // TODO save this for when incrementing early while is_starting
+
         if segment_permutation_shifter.is_initially_looped() {
             segment_permutation_shifter.is_looped = true;
         }
 
         return segment_permutation_shifter;
     }
+    /// The position weights are per segment index and per position offset within the bounding length such that randomizing will sample the first arrangement proportional to the product of the weights of each segment at its position offset. Arrangements with a weight of zero are still iterated over.
+    /// Only the first arrangement after randomizing is weighted, since the later arrangements follow the usual iteration order from it, so randomize again for each weighted sample. If there are too many swappable segments to total the weights, randomizing falls back to the unweighted randomization.
+    pub fn new_with_position_weights(segments: Vec<Rc<Segment>>, origin: (u8, u8), bounding_length: usize, is_horizontal: bool, padding: usize, is_swapping_permitted: bool, position_weights_per_segment_index: Vec<Vec<f32>>) -> Self {
+        if position_weights_per_segment_index.len() != segments.len() {
+            panic!("Unexpected mismatch between the number of segments and the number of position weight collections.");
+        }
+        for position_weights in position_weights_per_segment_index.iter() {
+            if position_weights.len() != bounding_length {
+                panic!("Unexpected mismatch between the bounding length and the number of position weights.");
+            }
+            if position_weights.iter().any(|weight| !(*weight >= 0.0)) {
+                panic!("Unexpected negative or undefined weight.");
+            }
+        }
+        let mut segment_permutation_shifter = Self::new(segments, origin, bounding_length, is_horizontal, padding, is_swapping_permitted);
+        segment_permutation_shifter.position_weights_per_segment_index = Some(position_weights_per_segment_index);
+        return segment_permutation_shifter;
+    }
+    // returns true if every preceding segment index of the segment index is already placed
+    fn is_segment_placeable(&self, segment_index: usize, is_placed: impl Fn(usize) -> bool) -> bool {
+        return self.preceding_segment_indexes_per_segment_index[segment_index].iter().all(|preceding_segment_index| is_placed(*preceding_segment_index));
+    }
+    // returns true if the unused segment index may be placed after the used segments, where the segments must remain in order if swapping is not permitted
+    fn is_segment_next(&self, segment_index: usize, is_used: impl Fn(usize) -> bool) -> bool {
+        if is_used(segment_index) {
+            return false;
+        }
+        if self.is_swapping_permitted {
+            return self.is_segment_placeable(segment_index, is_used);
+        }
+        return (0..segment_index).all(is_used);
+    }
+    // returns the minimum padding after the segment at the shift index, where there is no padding after the last segment
+    fn get_minimum_padding(&self, shift_index: usize, segment_index: usize) -> usize {
+        if shift_index + 1 >= self.segments_length {
+            return 0;
+        }
+        return self.spacing_per_segment_index[segment_index].minimum;
+    }
+    // returns the smallest position offset of the next segment after the previous placement and the largest position offset permitted by the maximum spacing of the previous segment, if it has a maximum spacing
+    fn get_next_position_offset_bounds(&self, previous_placement_option: Option<(usize, usize)>) -> (usize, Option<usize>) {
+        match previous_placement_option {
+            Some((previous_segment_index, previous_position_offset)) => {
+                let previous_segment_end = previous_position_offset + self.segments[previous_segment_index].length;
+                let spacing = &self.spacing_per_segment_index[previous_segment_index];
+                return (previous_segment_end + spacing.minimum, spacing.maximum.map(|maximum| previous_segment_end + maximum));
+            },
+            None => {
+                return (0, None);
+            }
+        }
+    }
+    // returns the placement at the shift index before the provided shift index, if there is one
+    fn get_previous_placement(segment_index_per_shift_index: &[usize], position_offset_per_shift_index: &[usize], shift_index: usize) -> Option<(usize, usize)> {
+        if shift_index == 0 {
+            return None;
+        }
+        return Some((segment_index_per_shift_index[shift_index - 1], position_offset_per_shift_index[shift_index - 1]));
+    }
+    // returns the smallest length that the unused segments may fit within, where the unused segments are placed after the used segments and the segment placed last needs no spacing after it
+    fn get_remaining_minimum_bounding_length(&self, is_used: impl Fn(usize) -> bool) -> usize {
+        let mut remaining_minimum_bounding_length = 0;
+        let mut largest_last_minimum_spacing_option: Option<usize> = None;
+        for segment_index in 0..self.segments_length {
+            if is_used(segment_index) {
+                continue;
+            }
+            let minimum_spacing = self.spacing_per_segment_index[segment_index].minimum;
+            remaining_minimum_bounding_length += self.segments[segment_index].length + minimum_spacing;
+            // only a segment that no other unused segment must follow may be placed last
+            let is_possibly_last = if self.is_swapping_permitted {
+                (0..self.segments_length).all(|other_segment_index| is_used(other_segment_index) || !self.preceding_segment_indexes_per_segment_index[other_segment_index].contains(&segment_index))
+            }
+            else {
+                ((segment_index + 1)..self.segments_length).all(&is_used)
+            };
+            if is_possibly_last && (largest_last_minimum_spacing_option.is_none() || largest_last_minimum_spacing_option.unwrap() < minimum_spacing) {
+                largest_last_minimum_spacing_option = Some(minimum_spacing);
+            }
+        }
+        if let Some(largest_last_minimum_spacing) = largest_last_minimum_spacing_option {
+            remaining_minimum_bounding_length -= largest_last_minimum_spacing;
+        }
+        return remaining_minimum_bounding_length;
+    }
+    // returns the largest position offset of the segment index placed next after the used segments such that the other unused segments still fit after it, or None if the segment does not fit at the minimum position offset
+    fn get_maximum_position_offset(&self, is_used: impl Fn(usize) -> bool, segment_index: usize, minimum_position_offset: usize, maximum_spacing_position_offset_option: Option<usize>) -> Option<usize> {
+        let is_next_used = |other_segment_index: usize| other_segment_index == segment_index || is_used(other_segment_index);
+        let mut required_bounding_length = minimum_position_offset + self.segments[segment_index].length;
+        if !(0..self.segments_length).all(is_next_used) {
+            required_bounding_length += self.spacing_per_segment_index[segment_index].minimum + self.get_remaining_minimum_bounding_length(is_next_used);
+        }
+        if required_bounding_length > self.bounding_length {
+            return None;
+        }
+        let mut maximum_position_offset = minimum_position_offset + (self.bounding_length - required_bounding_length);
+        if let Some(maximum_spacing_position_offset) = maximum_spacing_position_offset_option {
+            maximum_position_offset = maximum_position_offset.min(maximum_spacing_position_offset);
+        }
+        return Some(maximum_position_offset);
+    }
+    // replaces the placements from the shift index onward with the first or last arrangement after the earlier placements, where the first arrangement places the smallest segment index that fits at its smallest position offset per shift index and the last arrangement places the largest segment index that fits at its largest position offset
+    fn set_bounding_arrangement_from(&self, segment_index_per_shift_index: &mut Vec<usize>, position_offset_per_shift_index: &mut Vec<usize>, from_shift_index: usize, is_first: bool) {
+        segment_index_per_shift_index.truncate(from_shift_index);
+        position_offset_per_shift_index.truncate(from_shift_index);
+        let mut is_used_per_segment_index: Vec<bool> = vec![false; self.segments_length];
+        for segment_index in segment_index_per_shift_index.iter() {
+            is_used_per_segment_index[*segment_index] = true;
+        }
+        for shift_index in from_shift_index..self.segments_length {
+            let (minimum_position_offset, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(Self::get_previous_placement(segment_index_per_shift_index, position_offset_per_shift_index, shift_index));
+            let mut is_any_segment_next = false;
+            let mut placement_option: Option<(usize, usize)> = None;
+            for segment_index in 0..self.segments_length {
+                if self.is_segment_next(segment_index, |other_segment_index| is_used_per_segment_index[other_segment_index]) {
+                    is_any_segment_next = true;
+                    if let Some(maximum_position_offset) = self.get_maximum_position_offset(|other_segment_index| is_used_per_segment_index[other_segment_index], segment_index, minimum_position_offset, maximum_spacing_position_offset_option) {
+                        if is_first {
+                            placement_option = Some((segment_index, minimum_position_offset));
+                            break;
+                        }
+                        placement_option = Some((segment_index, maximum_position_offset));
+                    }
+                }
+            }
+            if !is_any_segment_next {
+                panic!("Unexpected cycle within the precedence pairs.");
+            }
+            match placement_option {
+                Some((segment_index, position_offset)) => {
+                    is_used_per_segment_index[segment_index] = true;
+                    segment_index_per_shift_index.push(segment_index);
+                    position_offset_per_shift_index.push(position_offset);
+                },
+                None => {
+                    panic!("Unexpected segments that do not fit within the bounding length.");
+                }
+            }
+        }
+    }
+    // sets the smallest and largest position offsets per shift index of the "starting" arrangement
+    fn set_starting_position_offset_ranges(&mut self) {
+        let mut starting_minimum_position_offset_per_shift_index: Vec<usize> = Vec::new();
+        let mut starting_maximum_position_offset_per_shift_index: Vec<usize> = Vec::new();
+        let mut is_used_per_segment_index: Vec<bool> = vec![false; self.segments_length];
+        for shift_index in 0..self.segments_length {
+            let (minimum_position_offset, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(Self::get_previous_placement(&self.starting_segment_index_per_shift_index, &self.starting_initial_position_offset_per_shift_index, shift_index));
+            let segment_index = self.starting_segment_index_per_shift_index[shift_index];
+            let maximum_position_offset = self.get_maximum_position_offset(|other_segment_index| is_used_per_segment_index[other_segment_index], segment_index, minimum_position_offset, maximum_spacing_position_offset_option).unwrap();
+            starting_minimum_position_offset_per_shift_index.push(minimum_position_offset);
+            starting_maximum_position_offset_per_shift_index.push(maximum_position_offset);
+            is_used_per_segment_index[segment_index] = true;
+        }
+        self.starting_minimum_position_offset_per_shift_index = starting_minimum_position_offset_per_shift_index;
+        self.starting_maximum_position_offset_per_shift_index = starting_maximum_position_offset_per_shift_index;
+    }
     fn is_initially_looped(&self) -> bool {
         return self.is_starting_at_beginning;
 
@@ -236,6 +451,515 @@ impl SegmentPermutationShifter {
         }
         return is_looped;
     }
+    fn set_uniformly_random_ending(&mut self) {
+        if self.is_swapping_permitted {
+            if self.is_spacing_uniform && self.preceding_segment_indexes_per_segment_index.iter().all(|preceding_segment_indexes| preceding_segment_indexes.is_empty()) {
+                fastrand::shuffle(&mut self.ending_segment_index_per_shift_index);
+            }
+            else {
+                // place a random available segment at each shift index so that the precedence pairs are kept and the remaining segments still fit after it
+                let mut is_placed_per_segment_index: Vec<bool> = vec![false; self.segments_length];
+                let mut minimum_position_offset = 0;
+                for shift_index in 0..self.segments_length {
+                    let available_segment_indexes: Vec<usize> = (0..self.segments_length)
+                        .filter(|segment_index| self.is_segment_next(*segment_index, |other_segment_index| is_placed_per_segment_index[other_segment_index]) && self.get_maximum_position_offset(|other_segment_index| is_placed_per_segment_index[other_segment_index], *segment_index, minimum_position_offset, None).is_some())
+                        .collect();
+                    let segment_index = available_segment_indexes[fastrand::usize(..available_segment_indexes.len())];
+                    is_placed_per_segment_index[segment_index] = true;
+                    self.ending_segment_index_per_shift_index[shift_index] = segment_index;
+                    minimum_position_offset += self.segments[segment_index].length + self.get_minimum_padding(shift_index, segment_index);
+                }
+            }
+        }
+        // TODO start the "current" properties in a randomized state
+        //
+        //  1   1   1   1   1   1   1   1
+        //      1   2   3   4   5   6   7
+        //          1   3   6   10  15  21
+        //              1   4   10  20  35
+        //                  1   5   15  35
+        //                      1   6   21
+        //                          1   7
+        //                              1         
+        // f(x) = n! / ((n - k)! * k!)
+        //  n   k   f(x)
+        //  1   1   1
+        //  2   1   2
+        //  3   1   3
+        //  .   .   .
+        //  2   2   1
+        //  3   2   3
+        //  4   2   6   
+        //
+        //  S   B   P   n   k   f(x)
+        //  1   1   1   
+        //  1   2   2   
+        //  1   3   3
+        //  .   .   .
+        //  2   2   1
+        //  2   3   3
+        //  2   4   6
+        //  2   5   10
+        //  .   .   .
+        //  3   3   1
+        //  3   4   4
+        //  3   5   10
+        //  3   6   20           
+
+        // algorith for starting at random state
+        //      consider each segment as having length of one (consider as "original" segment), ignore the padding, and each remaining bounding length as a segment of length one (consider as "empty" segment)
+        //      sort segments randomly within vector
+        //      initialize position to zero
+        //      increment through vector
+        //          if current segment is an "original" segment
+        //              if not first "original" segment found
+        //                  increment position by padding
+        //              record segment position
+        //              increment position by segment length
+        //          else
+        //              increment position by one
+
+        let mut is_original_segment_list: BitVec = BitVec::repeat(true, self.segments_length);
+        let mut remaining_bounding_length = self.bounding_length;
+        for segment_index in 0..self.segments_length {
+            let mapped_segment_index = self.ending_segment_index_per_shift_index[segment_index];
+            remaining_bounding_length -= self.segments[mapped_segment_index].length + self.get_minimum_padding(segment_index, mapped_segment_index);
+        }
+        is_original_segment_list.resize(self.segments_length + remaining_bounding_length, false);
+        debug!("randomize: before shuffle: {:?}", is_original_segment_list);
+        {
+            // implementation from shuffle in https://github.com/smol-rs/fastrand/blob/master/src/lib.rs
+            for bit_index in 1..is_original_segment_list.len() {
+                let other_bit_index = fastrand::usize(..=bit_index);
+                if bit_index != other_bit_index {
+                    is_original_segment_list.swap(bit_index, other_bit_index);
+                }
+            }
+        }
+        debug!("randomize: after shuffle: {:?}", is_original_segment_list);
+        let mut current_position_index = 0;
+        let mut current_segment_index = 0;
+        self.ending_position_offset_per_shift_index.clear();
+        for segment_list_index in 0..(self.segments_length + remaining_bounding_length) {
+            if is_original_segment_list[segment_list_index] {
+                self.ending_position_offset_per_shift_index.push(current_position_index);
+                let mapped_current_segment_index = self.ending_segment_index_per_shift_index[current_segment_index];
+                current_position_index += self.segments[mapped_current_segment_index].length + self.get_minimum_padding(current_segment_index, mapped_current_segment_index);
+                current_segment_index += 1;
+            }
+            else {
+                current_position_index += 1;
+            }
+        }
+    }
+    // samples the "ending" arrangement uniformly while ignoring the maximum spacing, rejecting any arrangement that exceeds it
+    fn set_uniformly_random_ending_within_maximum_spacing(&mut self) {
+        for _ in 0..MAXIMUM_SPACING_REJECTIONS_TOTAL {
+            self.set_uniformly_random_ending();
+            if !self.is_maximum_spacing_used || self.is_ending_within_maximum_spacing() {
+                return;
+            }
+        }
+        debug!("randomize: every uniformly random arrangement exceeded the maximum spacing, so walking randomly instead");
+        self.set_randomly_walked_ending();
+    }
+    // returns true if no segment of the "ending" arrangement is further from the previous segment than the maximum spacing of the previous segment
+    fn is_ending_within_maximum_spacing(&self) -> bool {
+        for shift_index in 1..self.segments_length {
+            let (_, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(Self::get_previous_placement(&self.ending_segment_index_per_shift_index, &self.ending_position_offset_per_shift_index, shift_index));
+            if let Some(maximum_spacing_position_offset) = maximum_spacing_position_offset_option {
+                if self.ending_position_offset_per_shift_index[shift_index] > maximum_spacing_position_offset {
+                    return false;
+                }
+            }
+        }
+        return true;
+    }
+    // sets the "ending" arrangement by choosing uniformly from every possible next placement per shift index, which always keeps within the maximum spacing since the remaining segments still fit at their smallest position offsets, although the arrangements are not equally likely
+    fn set_randomly_walked_ending(&mut self) {
+        let mut segment_index_per_shift_index: Vec<usize> = Vec::new();
+        let mut position_offset_per_shift_index: Vec<usize> = Vec::new();
+        let mut is_used_per_segment_index: Vec<bool> = vec![false; self.segments_length];
+        for shift_index in 0..self.segments_length {
+            let (minimum_position_offset, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(Self::get_previous_placement(&segment_index_per_shift_index, &position_offset_per_shift_index, shift_index));
+            let mut next_placements: Vec<(usize, usize)> = Vec::new();
+            for segment_index in 0..self.segments_length {
+                if self.is_segment_next(segment_index, |other_segment_index| is_used_per_segment_index[other_segment_index]) {
+                    if let Some(maximum_position_offset) = self.get_maximum_position_offset(|other_segment_index| is_used_per_segment_index[other_segment_index], segment_index, minimum_position_offset, maximum_spacing_position_offset_option) {
+                        for position_offset in minimum_position_offset..=maximum_position_offset {
+                            next_placements.push((segment_index, position_offset));
+                        }
+                    }
+                }
+            }
+            if next_placements.is_empty() {
+                panic!("Unexpected segments that do not fit within the bounding length.");
+            }
+            let (segment_index, position_offset) = next_placements[fastrand::usize(..next_placements.len())];
+            is_used_per_segment_index[segment_index] = true;
+            segment_index_per_shift_index.push(segment_index);
+            position_offset_per_shift_index.push(position_offset);
+        }
+        self.ending_segment_index_per_shift_index = segment_index_per_shift_index;
+        self.ending_position_offset_per_shift_index = position_offset_per_shift_index;
+    }
+    // returns each possible next segment index and position offset paired with the total weight of every arrangement that starts with that placement
+    fn get_weighted_next_placements(&self, position_weights_per_segment_index: &Vec<Vec<f32>>, is_used_per_segment_index: &Vec<bool>, minimum_position_offset: usize, maximum_spacing_position_offset_option: Option<usize>, total_weight_per_state: &mut HashMap<(BitVec, usize, Option<usize>), f64>) -> Option<Vec<(usize, usize, f64)>> {
+        let mut weighted_next_placements: Vec<(usize, usize, f64)> = Vec::new();
+        for segment_index in 0..self.segments_length {
+            if !self.is_segment_next(segment_index, |other_segment_index| is_used_per_segment_index[other_segment_index]) {
+                continue;
+            }
+            if let Some(maximum_position_offset) = self.get_maximum_position_offset(|other_segment_index| is_used_per_segment_index[other_segment_index], segment_index, minimum_position_offset, maximum_spacing_position_offset_option) {
+                let mut next_is_used_per_segment_index = is_used_per_segment_index.clone();
+                next_is_used_per_segment_index[segment_index] = true;
+                for position_offset in minimum_position_offset..=maximum_position_offset {
+                    let weight = position_weights_per_segment_index[segment_index][position_offset] as f64;
+                    if weight > 0.0 {
+                        let (next_minimum_position_offset, next_maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(Some((segment_index, position_offset)));
+                        let remaining_total_weight = self.get_weighted_arrangements_total(position_weights_per_segment_index, &next_is_used_per_segment_index, next_minimum_position_offset, next_maximum_spacing_position_offset_option, total_weight_per_state)?;
+                        if remaining_total_weight > 0.0 {
+                            weighted_next_placements.push((segment_index, position_offset, weight * remaining_total_weight));
+                        }
+                    }
+                }
+            }
+        }
+        return Some(weighted_next_placements);
+    }
+    // returns None if there are too many memoized totals to continue
+    fn get_weighted_arrangements_total(&self, position_weights_per_segment_index: &Vec<Vec<f32>>, is_used_per_segment_index: &Vec<bool>, minimum_position_offset: usize, maximum_spacing_position_offset_option: Option<usize>, total_weight_per_state: &mut HashMap<(BitVec, usize, Option<usize>), f64>) -> Option<f64> {
+        if is_used_per_segment_index.iter().all(|is_used| *is_used) {
+            return Some(1.0);
+        }
+        let key = (is_used_per_segment_index.iter().collect::<BitVec>(), minimum_position_offset, maximum_spacing_position_offset_option);
+        if let Some(total_weight) = total_weight_per_state.get(&key) {
+            return Some(*total_weight);
+        }
+        if total_weight_per_state.len() >= WEIGHTED_ARRANGEMENTS_TOTALS_CAPACITY {
+            return None;
+        }
+        let total_weight: f64 = self.get_weighted_next_placements(position_weights_per_segment_index, is_used_per_segment_index, minimum_position_offset, maximum_spacing_position_offset_option, total_weight_per_state)?.iter()
+            .map(|weighted_next_placement| weighted_next_placement.2)
+            .sum();
+        total_weight_per_state.insert(key, total_weight);
+        return Some(total_weight);
+    }
+    // samples the arrangement of segments proportional to the product of the position weights and then sets the "ending" arrangement to the arrangement just before it so that the sampled arrangement is the first one returned
+    fn try_set_weighted_random_ending(&mut self) -> bool {
+        // the uniform sampling only approximates the maximum spacing by rejection and does not account for orders of segments that leave differing slack, so every arrangement is given the same weight instead
+        let uniform_position_weights_per_segment_index: Vec<Vec<f32>> = vec![vec![1.0; self.bounding_length]; self.segments_length];
+        let is_uniform_sampling_exact = self.is_spacing_uniform && !self.is_maximum_spacing_used;
+        let mut position_weights_per_segment_index = match &self.position_weights_per_segment_index {
+            Some(position_weights_per_segment_index) => position_weights_per_segment_index.clone(),
+            None => {
+                if is_uniform_sampling_exact {
+                    return false;
+                }
+                uniform_position_weights_per_segment_index.clone()
+            }
+        };
+        let mut total_weight_per_state: HashMap<(BitVec, usize, Option<usize>), f64> = HashMap::new();
+        let mut is_used_per_segment_index: Vec<bool> = vec![false; self.segments_length];
+        let mut total_weight = match self.get_weighted_arrangements_total(&position_weights_per_segment_index, &is_used_per_segment_index, 0, None, &mut total_weight_per_state) {
+            Some(total_weight) => total_weight,
+            None => {
+                return false;
+            }
+        };
+        if !(total_weight > 0.0 && total_weight.is_finite()) {
+            // every arrangement has zero weight, so they are all equally likely
+            if is_uniform_sampling_exact {
+                return false;
+            }
+            position_weights_per_segment_index = uniform_position_weights_per_segment_index;
+            total_weight_per_state.clear();
+            total_weight = match self.get_weighted_arrangements_total(&position_weights_per_segment_index, &is_used_per_segment_index, 0, None, &mut total_weight_per_state) {
+                Some(total_weight) => total_weight,
+                None => {
+                    return false;
+                }
+            };
+            if !(total_weight > 0.0 && total_weight.is_finite()) {
+                return false;
+            }
+        }
+
+        let mut segment_index_per_shift_index: Vec<usize> = Vec::new();
+        let mut position_offset_per_shift_index: Vec<usize> = Vec::new();
+        let mut minimum_position_offset = 0;
+        let mut maximum_spacing_position_offset_option: Option<usize> = None;
+        for _ in 0..self.segments_length {
+            // every total needed here was memoized while calculating the total weight of the first placement
+            let weighted_next_placements = self.get_weighted_next_placements(&position_weights_per_segment_index, &is_used_per_segment_index, minimum_position_offset, maximum_spacing_position_offset_option, &mut total_weight_per_state).unwrap();
+            let placements_total_weight: f64 = weighted_next_placements.iter().map(|weighted_next_placement| weighted_next_placement.2).sum();
+            let mut remaining_weight = fastrand::f64() * placements_total_weight;
+            let mut chosen_placement = weighted_next_placements.last().unwrap();
+            for weighted_next_placement in weighted_next_placements.iter() {
+                if remaining_weight < weighted_next_placement.2 {
+                    chosen_placement = weighted_next_placement;
+                    break;
+                }
+                remaining_weight -= weighted_next_placement.2;
+            }
+            let (segment_index, position_offset, _) = *chosen_placement;
+            is_used_per_segment_index[segment_index] = true;
+            segment_index_per_shift_index.push(segment_index);
+            position_offset_per_shift_index.push(position_offset);
+            (minimum_position_offset, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(Some((segment_index, position_offset)));
+        }
+        self.set_ending_before(segment_index_per_shift_index, position_offset_per_shift_index);
+        return true;
+    }
+    // sets the "ending" arrangement to the arrangement just before the provided arrangement so that the provided arrangement is the first one returned
+    fn set_ending_before(&mut self, mut segment_index_per_shift_index: Vec<usize>, mut position_offset_per_shift_index: Vec<usize>) {
+        // find the arrangement just before the provided arrangement, where each shift index iterates over segment indexes in order and then position offsets in order
+        let mut previous_shift_index_option: Option<usize> = None;
+        for shift_index in (0..self.segments_length).rev() {
+            let (minimum_position_offset, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(Self::get_previous_placement(&segment_index_per_shift_index, &position_offset_per_shift_index, shift_index));
+            if position_offset_per_shift_index[shift_index] > minimum_position_offset {
+                position_offset_per_shift_index[shift_index] -= 1;
+                previous_shift_index_option = Some(shift_index);
+                break;
+            }
+            if self.is_swapping_permitted {
+                let earlier_segment_indexes = &segment_index_per_shift_index[..shift_index];
+                let previous_placement_option = segment_index_per_shift_index[shift_index..].iter()
+                    .filter(|segment_index| **segment_index < segment_index_per_shift_index[shift_index] && self.is_segment_next(**segment_index, |other_segment_index| earlier_segment_indexes.contains(&other_segment_index)))
+                    .filter_map(|segment_index| {
+                        self.get_maximum_position_offset(|other_segment_index| earlier_segment_indexes.contains(&other_segment_index), *segment_index, minimum_position_offset, maximum_spacing_position_offset_option)
+                            .map(|maximum_position_offset| (*segment_index, maximum_position_offset))
+                    })
+                    .max();
+                if let Some((previous_segment_index, previous_position_offset)) = previous_placement_option {
+                    segment_index_per_shift_index[shift_index] = previous_segment_index;
+                    position_offset_per_shift_index[shift_index] = previous_position_offset;
+                    previous_shift_index_option = Some(shift_index);
+                    break;
+                }
+            }
+        }
+        // every later shift index is at its final arrangement, or else the sampled arrangement is the very first arrangement, so the arrangement before it is the very last arrangement
+        let last_shift_index = previous_shift_index_option.map(|previous_shift_index| previous_shift_index + 1).unwrap_or(0);
+        self.set_bounding_arrangement_from(&mut segment_index_per_shift_index, &mut position_offset_per_shift_index, last_shift_index, false);
+        self.ending_segment_index_per_shift_index = segment_index_per_shift_index;
+        self.ending_position_offset_per_shift_index = position_offset_per_shift_index;
+    }
+    // sets the "starting" arrangement to the arrangement just after the "ending" arrangement
+    fn set_starting_after_ending(&mut self) {
+        let mut segment_index_per_shift_index = self.ending_segment_index_per_shift_index.clone();
+        let mut position_offset_per_shift_index = self.ending_position_offset_per_shift_index.clone();
+
+        // try to move the starting state forward one iteration or swap masks or fully reset, where the later shift indexes restart from their first arrangement
+        let mut first_shift_index = 0;
+        for shift_index in (0..self.segments_length).rev() {
+            let (minimum_position_offset, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(Self::get_previous_placement(&segment_index_per_shift_index, &position_offset_per_shift_index, shift_index));
+            let earlier_segment_indexes = &segment_index_per_shift_index[..shift_index];
+            let segment_index = segment_index_per_shift_index[shift_index];
+            let maximum_position_offset = self.get_maximum_position_offset(|other_segment_index| earlier_segment_indexes.contains(&other_segment_index), segment_index, minimum_position_offset, maximum_spacing_position_offset_option).unwrap();
+            if position_offset_per_shift_index[shift_index] != maximum_position_offset {
+                // this shift index can move forward
+                position_offset_per_shift_index[shift_index] += 1;
+                first_shift_index = shift_index + 1;
+                break;
+            }
+            if self.is_swapping_permitted {
+                let next_segment_index_option = ((segment_index + 1)..self.segments_length)
+                    .find(|next_segment_index| self.is_segment_next(*next_segment_index, |other_segment_index| earlier_segment_indexes.contains(&other_segment_index)) && self.get_maximum_position_offset(|other_segment_index| earlier_segment_indexes.contains(&other_segment_index), *next_segment_index, minimum_position_offset, maximum_spacing_position_offset_option).is_some());
+                if let Some(next_segment_index) = next_segment_index_option {
+                    // found a valid next mask for this shift index
+                    segment_index_per_shift_index[shift_index] = next_segment_index;
+                    position_offset_per_shift_index[shift_index] = minimum_position_offset;
+                    first_shift_index = shift_index + 1;
+                    break;
+                }
+            }
+            // none of the positions can move where they are right now if this is the first shift index
+        }
+        self.set_bounding_arrangement_from(&mut segment_index_per_shift_index, &mut position_offset_per_shift_index, first_shift_index, true);
+        self.starting_segment_index_per_shift_index = segment_index_per_shift_index;
+        self.starting_initial_position_offset_per_shift_index = position_offset_per_shift_index;
+        self.set_starting_position_offset_ranges();
+
+        // determine if we happened to end up with the starting positions and segment order at the very beginning
+        {
+            let mut first_segment_index_per_shift_index: Vec<usize> = Vec::new();
+            let mut first_position_offset_per_shift_index: Vec<usize> = Vec::new();
+            self.set_bounding_arrangement_from(&mut first_segment_index_per_shift_index, &mut first_position_offset_per_shift_index, 0, true);
+            self.is_starting_at_beginning = self.starting_segment_index_per_shift_index == first_segment_index_per_shift_index && self.starting_initial_position_offset_per_shift_index == first_position_offset_per_shift_index;
+        }
+        // set is_looped based on loop criteria
+        self.is_looped = self.is_initially_looped();
+    }
+    // returns the number of orders that the unused segments may be placed in after the used segments, or None if the count does not fit
+    fn get_orderings_total<T: Count>(&self, is_used_per_segment_index: &Vec<bool>, orderings_total_per_state: &mut HashMap<Vec<bool>, Option<T>>) -> Option<T> {
+        if !self.is_swapping_permitted {
+            return Some(T::from_u64(1));
+        }
+        let unused_segments_total = is_used_per_segment_index.iter().filter(|is_used| !**is_used).count() as u64;
+        if self.preceding_segment_indexes_per_segment_index.iter().all(|preceding_segment_indexes| preceding_segment_indexes.is_empty()) {
+            // every unused segment may be swapped with every other unused segment
+            return get_factorial(unused_segments_total);
+        }
+        if unused_segments_total == 0 {
+            return Some(T::from_u64(1));
+        }
+        if let Some(orderings_total) = orderings_total_per_state.get(is_used_per_segment_index) {
+            return orderings_total.clone();
+        }
+        let mut orderings_total_option = Some(T::from_u64(0));
+        for segment_index in 0..self.segments_length {
+            if !is_used_per_segment_index[segment_index] && self.is_segment_placeable(segment_index, |preceding_segment_index| is_used_per_segment_index[preceding_segment_index]) {
+                let mut next_is_used_per_segment_index = is_used_per_segment_index.clone();
+                next_is_used_per_segment_index[segment_index] = true;
+                let next_orderings_total_option = self.get_orderings_total(&next_is_used_per_segment_index, orderings_total_per_state);
+                orderings_total_option = orderings_total_option.zip(next_orderings_total_option)
+                    .and_then(|(orderings_total, next_orderings_total)| orderings_total.checked_add(&next_orderings_total));
+            }
+        }
+        orderings_total_per_state.insert(is_used_per_segment_index.clone(), orderings_total_option.clone());
+        return orderings_total_option;
+    }
+    // returns the number of ways that the segments from the shift index onward may be positioned, or None if the count does not fit, where the remaining slack is how far the segment at the shift index may move beyond its minimum position offset while the later segments still fit
+    // the position offsets do not depend on which segment is at which shift index since every segment has the same spacing, so non-uniform spacing must be counted per arrangement by get_arrangements_total instead
+    fn get_position_offsets_total<T: Count>(&self, shift_index: usize, remaining_slack: usize, position_offsets_total_per_state: &mut HashMap<(usize, usize), Option<T>>) -> Option<T> {
+        debug_assert!(self.is_spacing_uniform);
+        let remaining_segments_total = self.segments_length - shift_index;
+        if !self.is_maximum_spacing_used {
+            // each unit of slack is either placed before one of the remaining segments or after the last segment
+            return get_binomial((remaining_slack + remaining_segments_total) as u64, remaining_segments_total as u64);
+        }
+        if remaining_segments_total == 0 {
+            return Some(T::from_u64(1));
+        }
+        if let Some(position_offsets_total) = position_offsets_total_per_state.get(&(shift_index, remaining_slack)) {
+            return position_offsets_total.clone();
+        }
+        let mut maximum_extra_spacing = remaining_slack;
+        if shift_index != 0 {
+            let spacing = &self.spacing_per_segment_index[0];
+            if let Some(maximum) = spacing.maximum {
+                maximum_extra_spacing = maximum_extra_spacing.min(maximum - spacing.minimum);
+            }
+        }
+        let mut position_offsets_total_option = Some(T::from_u64(0));
+        for extra_spacing in 0..=maximum_extra_spacing {
+            let next_position_offsets_total_option = self.get_position_offsets_total(shift_index + 1, remaining_slack - extra_spacing, position_offsets_total_per_state);
+            position_offsets_total_option = position_offsets_total_option.zip(next_position_offsets_total_option)
+                .and_then(|(position_offsets_total, next_position_offsets_total)| position_offsets_total.checked_add(&next_position_offsets_total));
+        }
+        position_offsets_total_per_state.insert((shift_index, remaining_slack), position_offsets_total_option.clone());
+        return position_offsets_total_option;
+    }
+    // returns the number of arrangements of the unused segments after the previous placement, or None if the count does not fit
+    fn get_arrangements_total<T: Count>(&self, is_used_per_segment_index: &Vec<bool>, previous_placement_option: Option<(usize, usize)>, arrangements_totals: &mut ArrangementsTotals<T>) -> Option<T> {
+        if is_used_per_segment_index.iter().all(|is_used| *is_used) {
+            return Some(T::from_u64(1));
+        }
+        let (minimum_position_offset, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(previous_placement_option);
+        if self.is_spacing_uniform {
+            // every order of the unused segments leaves the same slack, and every gap has the same spacing
+            let remaining_slack = self.bounding_length - minimum_position_offset - self.get_remaining_minimum_bounding_length(|segment_index| is_used_per_segment_index[segment_index]);
+            let shift_index = is_used_per_segment_index.iter().filter(|is_used| **is_used).count();
+            let orderings_total: T = self.get_orderings_total(is_used_per_segment_index, &mut arrangements_totals.orderings_total_per_state)?;
+            return orderings_total.checked_mul(&self.get_position_offsets_total(shift_index, remaining_slack, &mut arrangements_totals.position_offsets_total_per_state)?);
+        }
+        // each gap depends on the spacing of the segment before it, so every order of the unused segments is counted separately
+        let key = (is_used_per_segment_index.clone(), previous_placement_option);
+        if let Some(arrangements_total) = arrangements_totals.arrangements_total_per_state.get(&key) {
+            return arrangements_total.clone();
+        }
+        let mut arrangements_total_option = Some(T::from_u64(0));
+        for segment_index in 0..self.segments_length {
+            if !self.is_segment_next(segment_index, |other_segment_index| is_used_per_segment_index[other_segment_index]) {
+                continue;
+            }
+            if let Some(maximum_position_offset) = self.get_maximum_position_offset(|other_segment_index| is_used_per_segment_index[other_segment_index], segment_index, minimum_position_offset, maximum_spacing_position_offset_option) {
+                let mut next_is_used_per_segment_index = is_used_per_segment_index.clone();
+                next_is_used_per_segment_index[segment_index] = true;
+                for position_offset in minimum_position_offset..=maximum_position_offset {
+                    let next_arrangements_total_option = self.get_arrangements_total(&next_is_used_per_segment_index, Some((segment_index, position_offset)), arrangements_totals);
+                    arrangements_total_option = arrangements_total_option.zip(next_arrangements_total_option)
+                        .and_then(|(arrangements_total, next_arrangements_total)| arrangements_total.checked_add(&next_arrangements_total));
+                }
+            }
+        }
+        arrangements_totals.arrangements_total_per_state.insert(key, arrangements_total_option.clone());
+        return arrangements_total_option;
+    }
+    /// Returns the number of arrangements that the shifter iterates over, accounting for the spacing between the segments and whether or not the segments may be swapped, or None if the count does not fit within a u64. Use get_permutations_total with a larger count type, such as a u128, when there are many segments.
+    pub fn permutations_total(&self) -> Option<u64> {
+        return self.get_permutations_total::<u64>();
+    }
+    /// Returns the same number of arrangements as permutations_total, or None if the count does not fit within the count type.
+    pub fn get_permutations_total<T: Count>(&self) -> Option<T> {
+        return self.get_arrangements_total(&vec![false; self.segments_length], None, &mut ArrangementsTotals::new());
+    }
+    /// Returns the arrangement at the arrangement index as the located segment per shift index, in the order that the shifter iterates over its arrangements when it is not randomized. The arrangement index may be any count type, such as a u128 or a BigUint when there are more arrangements than fit within a u64.
+    pub fn get_nth_arrangement<T: Count>(&self, arrangement_index: T) -> Vec<LocatedSegment> {
+        // a count that does not fit within the count type is always beyond the arrangement index
+        if let Some(permutations_total) = self.get_permutations_total::<T>() {
+            if arrangement_index >= permutations_total {
+                panic!("Unexpected arrangement index beyond the permutations total.");
+            }
+        }
+        let mut arrangements_totals: ArrangementsTotals<T> = ArrangementsTotals::new();
+        let mut remaining_arrangement_index = arrangement_index;
+        let mut is_used_per_segment_index: Vec<bool> = vec![false; self.segments_length];
+        let mut located_segments: Vec<LocatedSegment> = Vec::new();
+        let mut previous_placement_option: Option<(usize, usize)> = None;
+        for _ in 0..self.segments_length {
+            let (minimum_position_offset, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(previous_placement_option);
+            // skip over every arrangement that starts with an earlier segment index or an earlier position offset at this shift index
+            let located_segment = 'finding_located_segment: {
+                for segment_index in 0..self.segments_length {
+                    if !self.is_segment_next(segment_index, |other_segment_index| is_used_per_segment_index[other_segment_index]) {
+                        continue;
+                    }
+                    let maximum_position_offset = match self.get_maximum_position_offset(|other_segment_index| is_used_per_segment_index[other_segment_index], segment_index, minimum_position_offset, maximum_spacing_position_offset_option) {
+                        Some(maximum_position_offset) => maximum_position_offset,
+                        None => {
+                            continue;
+                        }
+                    };
+                    let mut next_is_used_per_segment_index = is_used_per_segment_index.clone();
+                    next_is_used_per_segment_index[segment_index] = true;
+                    for position_offset in minimum_position_offset..=maximum_position_offset {
+                        match self.get_arrangements_total(&next_is_used_per_segment_index, Some((segment_index, position_offset)), &mut arrangements_totals) {
+                            Some(arrangements_total) => {
+                                if remaining_arrangement_index < arrangements_total {
+                                    break 'finding_located_segment LocatedSegment::new(segment_index, position_offset);
+                                }
+                                remaining_arrangement_index = remaining_arrangement_index.checked_sub(&arrangements_total).unwrap();
+                            },
+                            None => {
+                                // the arrangements starting with this placement are more than the count type can hold, so the remaining arrangement index is among them
+                                break 'finding_located_segment LocatedSegment::new(segment_index, position_offset);
+                            }
+                        }
+                    }
+                }
+                panic!("Unexpected arrangement index not found within the arrangements.");
+            };
+            is_used_per_segment_index[located_segment.segment_index] = true;
+            previous_placement_option = Some((located_segment.segment_index, located_segment.position));
+            located_segments.push(located_segment);
+        }
+        return located_segments;
+    }
+    /// Sets the arrangement at the arrangement index as the first arrangement returned, after which every other arrangement is returned in order, wrapping around to the first arrangement. This is expected to be called before shifting, the same as randomizing.
+    /// To split the arrangements across workers, each worker sets its starting arrangement index to the start of its range and then wraps its ShifterIncrementer in a LimitedIncrementer with the length of its range, so that it stops before the range of the next worker.
+    pub fn set_starting_arrangement_index<T: Count>(&mut self, arrangement_index: T) {
+        let located_segments = self.get_nth_arrangement(arrangement_index);
+        if self.is_starting_equal_to_ending {
+            // there is only one arrangement
+            return;
+        }
+        let segment_index_per_shift_index: Vec<usize> = located_segments.iter().map(|located_segment| located_segment.segment_index).collect();
+        let position_offset_per_shift_index: Vec<usize> = located_segments.iter().map(|located_segment| located_segment.position).collect();
+        self.set_ending_before(segment_index_per_shift_index, position_offset_per_shift_index);
+        self.set_starting_after_ending();
+    }
 }
 
 impl Shifter for SegmentPermutationShifter {
@@ -268,26 +992,24 @@ impl Shifter for SegmentPermutationShifter {
             self.current_initial_position_offset_per_shift_index.push(self.starting_initial_position_offset_per_shift_index[shift_index]);
         }
         else {
+            let previous_placement_option = if shift_index == 0 {
+                None
+            }
+            else {
+                Some((self.current_segment_index_per_shift_index[shift_index - 1], self.current_position_offset_per_shift_index[shift_index - 1].unwrap()))
+            };
+            let (minimum_position_offset, maximum_spacing_position_offset_option) = self.get_next_position_offset_bounds(previous_placement_option);
             for mask_index in 0..self.segments_length {
-                if !self.current_mask[mask_index] {
-                    self.current_mask.set(mask_index, true);
-                    self.current_segment_index_per_shift_index.push(mask_index);
-                    let minimum_position_offset;
-                    let maximum_position_offset;
-                    if shift_index == 0 {
-                        minimum_position_offset = 0;
-                        maximum_position_offset = self.starting_maximum_position_offset_per_shift_index[0];
+                if self.is_segment_next(mask_index, |other_mask_index| self.current_mask[other_mask_index]) {
+                    // the segment is skipped if the remaining segments would not fit after it
+                    if let Some(maximum_position_offset) = self.get_maximum_position_offset(|other_mask_index| self.current_mask[other_mask_index], mask_index, minimum_position_offset, maximum_spacing_position_offset_option) {
+                        self.current_mask.set(mask_index, true);
+                        self.current_segment_index_per_shift_index.push(mask_index);
+                        self.current_minimum_position_offset_per_shift_index.push(minimum_position_offset);
+                        self.current_maximum_position_offset_per_shift_index.push(maximum_position_offset);
+                        self.current_initial_position_offset_per_shift_index.push(minimum_position_offset);
+                        break;
                     }
-                    else {
-                        let previous_segment_index = self.current_segment_index_per_shift_index[shift_index - 1];
-                        let previous_segment_length = self.segments[previous_segment_index].length + self.padding;
-                        minimum_position_offset = self.current_position_offset_per_shift_index[shift_index - 1].unwrap() + previous_segment_length;
-                        maximum_position_offset = self.current_maximum_position_offset_per_shift_index[shift_index - 1] + previous_segment_length;
-                    }
-                    self.current_minimum_position_offset_per_shift_index.push(minimum_position_offset);
-                    self.current_maximum_position_offset_per_shift_index.push(maximum_position_offset);
-                    self.current_initial_position_offset_per_shift_index.push(minimum_position_offset);
-                    break;
                 }
             }
         }
@@ -363,363 +1085,148 @@ impl Shifter for SegmentPermutationShifter {
             if self.is_looped {
                 if shift_index == 0 || self.current_is_parent_ending[shift_index - 1] {
                     if self.ending_position_offset_per_shift_index[shift_index] == current_position_offset && self.ending_segment_index_per_shift_index[shift_index] == self.current_segment_index_per_shift_index[shift_index] {
-                        self.current_is_parent_ending.set(shift_index, true);
-                    }
-                }
-            }
-            return true;
-        }
-        self.is_starting = false;
-        if self.current_is_parent_ending[shift_index] {
-            return false;
-        }
-        if self.current_position_offset_per_shift_index[shift_index].unwrap() == self.current_maximum_position_offset_per_shift_index[shift_index] {
-            if !self.is_swapping_permitted {
-                if shift_index == 0 {
-                    // TODO check to see if we need to loop back to the first state because this shifter was randomized
-
-                    // loop back to the first state
-                    self.current_initial_position_offset_per_shift_index[0] = 0;
-                    self.current_position_offset_per_shift_index[0] = Some(0);
-
-                    if self.ending_position_offset_per_shift_index[0] == 0 {
-                        self.current_is_parent_ending.set(0, true);
-                    }
-                    else {
-                        self.current_is_parent_ending.set(0, false);
-                    }
-
-                    self.is_looped = true;
-                    debug!("try_increment: looping back to start");
-                    return true;
-                }
-                return false;
-            }
-            let segment_index = self.current_segment_index_per_shift_index[shift_index];
-            for next_segment_index in (segment_index + 1)..self.segments_length {
-                if !self.current_mask[next_segment_index] {
-                    self.current_mask.set(segment_index, false);
-                    self.current_mask.set(next_segment_index, true);
-                    self.current_segment_index_per_shift_index[shift_index] = next_segment_index;
-                    self.current_position_offset_per_shift_index[shift_index] = Some(self.current_minimum_position_offset_per_shift_index[shift_index]);
-                    if self.is_looped {
-                        if shift_index == 0 || self.current_is_parent_ending[shift_index - 1] {
-                            if self.ending_position_offset_per_shift_index[shift_index] == self.current_position_offset_per_shift_index[shift_index].unwrap() && self.ending_segment_index_per_shift_index[shift_index] == self.current_segment_index_per_shift_index[shift_index] {
-                                self.current_is_parent_ending.set(shift_index, true);
-                            }
-                        }
-                    }
-                    return true;
-                }
-            }
-            // at this point the swapped segments are all in reverse order and need to loop once back at 0th shift
-            if shift_index == 0 {
-                // loop back to the first state
-                self.current_mask.set(segment_index, false);
-                self.current_mask.set(0, true);
-                self.current_segment_index_per_shift_index[0] = 0;
-                self.current_initial_position_offset_per_shift_index[0] = 0;
-                self.current_position_offset_per_shift_index[0] = Some(0);
-
-                if self.ending_position_offset_per_shift_index[0] == 0 {
-                    self.current_is_parent_ending.set(0, true);
-                }
-                else {
-                    self.current_is_parent_ending.set(0, false);
-                }
-
-                self.is_looped = true;
-                debug!("try_increment: looping back to start");
-                return true;
-            }
-            return false;
-        }
-        self.current_position_offset_per_shift_index[shift_index] = Some(self.current_position_offset_per_shift_index[shift_index].unwrap() + 1);
-        if self.is_looped {
-            if shift_index == 0 || self.current_is_parent_ending[shift_index - 1] {
-                if self.ending_position_offset_per_shift_index[shift_index] == self.current_position_offset_per_shift_index[shift_index].unwrap() && self.ending_segment_index_per_shift_index[shift_index] == self.current_segment_index_per_shift_index[shift_index] {
-                    self.current_is_parent_ending.set(shift_index, true);
-                }
-            }
-        }
-        return true;
-    }
-    fn get_indexed_element(&self) -> IndexedElement<(u8, u8)> {
-        let (current_segment_index, current_position_offset) = self.get_element_index_and_state_index();
-        let position: Rc<(u8, u8)>;
-        if self.is_horizontal {
-            position = Rc::new((self.origin.0 + current_position_offset as u8, self.origin.1));
-        }
-        else {
-            position = Rc::new((self.origin.0, self.origin.1 + current_position_offset as u8));
-        }
-        return IndexedElement::new(position, current_segment_index);
-    }
-    fn get_element_index_and_state_index(&self) -> (usize, usize) {
-        let current_position_offset = self.current_position_offset_per_shift_index.last().unwrap().unwrap();
-        let current_segment_index = *self.current_segment_index_per_shift_index.last().unwrap();
-        return (current_segment_index, current_position_offset);
-    }
-    fn get_states(&self) -> Vec<Rc<Self::T>> {
-        return self.possible_locations.clone();
-    }
-    fn get_length(&self) -> usize {
-        return self.segments_length;
-    }
-    fn randomize(&mut self) {
-        if self.is_starting_equal_to_ending {
-            // there is no reason to perform any randomization since there is only one state
-            return;
-        }
-        if self.is_swapping_permitted {
-            fastrand::shuffle(&mut self.ending_segment_index_per_shift_index);
-        }
-        // TODO start the "current" properties in a randomized state
-        //
-        //  1   1   1   1   1   1   1   1
-        //      1   2   3   4   5   6   7
-        //          1   3   6   10  15  21
-        //              1   4   10  20  35
-        //                  1   5   15  35
-        //                      1   6   21
-        //                          1   7
-        //                              1         
-        // f(x) = n! / ((n - k)! * k!)
-        //  n   k   f(x)
-        //  1   1   1
-        //  2   1   2
-        //  3   1   3
-        //  .   .   .
-        //  2   2   1
-        //  3   2   3
-        //  4   2   6   
-        //
-        //  S   B   P   n   k   f(x)
-        //  1   1   1   
-        //  1   2   2   
-        //  1   3   3
-        //  .   .   .
-        //  2   2   1
-        //  2   3   3
-        //  2   4   6
-        //  2   5   10
-        //  .   .   .
-        //  3   3   1
-        //  3   4   4
-        //  3   5   10
-        //  3   6   20           
-
-        // algorith for starting at random state
-        //      consider each segment as having length of one (consider as "original" segment), ignore the padding, and each remaining bounding length as a segment of length one (consider as "empty" segment)
-        //      sort segments randomly within vector
-        //      initialize position to zero
-        //      increment through vector
-        //          if current segment is an "original" segment
-        //              if not first "original" segment found
-        //                  increment position by padding
-        //              record segment position
-        //              increment position by segment length
-        //          else
-        //              increment position by one
-
-        let mut is_original_segment_list: BitVec = BitVec::repeat(true, self.segments_length);
-        let mut remaining_bounding_length = self.bounding_length;
-        for segment_index in 0..self.segments_length {
-            if segment_index != 0 {
-                remaining_bounding_length -= self.padding;
-            }
-            let mapped_segment_index = self.ending_segment_index_per_shift_index[segment_index];
-            remaining_bounding_length -= self.segments[mapped_segment_index].length;
-        }
-        is_original_segment_list.resize(self.segments_length + remaining_bounding_length, false);
-        debug!("randomize: before shuffle: {:?}", is_original_segment_list);
-        {
-            // implementation from shuffle in https://github.com/smol-rs/fastrand/blob/master/src/lib.rs
-            for bit_index in 1..is_original_segment_list.len() {
-                let other_bit_index = fastrand::usize(..=bit_index);
-                if bit_index != other_bit_index {
-                    is_original_segment_list.swap(bit_index, other_bit_index);
+                        self.current_is_parent_ending.set(shift_index, true);
+                    }
                 }
             }
+            return true;
         }
-        debug!("randomize: after shuffle: {:?}", is_original_segment_list);
-        let mut current_position_index = 0;
-        let mut current_segment_index = 0;
-        self.ending_position_offset_per_shift_index.clear();
-        for segment_list_index in 0..(self.segments_length + remaining_bounding_length) {
-            if is_original_segment_list[segment_list_index] {
-                if current_segment_index != 0 {
-                    current_position_index += self.padding;
-                }
-                self.ending_position_offset_per_shift_index.push(current_position_index);
-                let mapped_current_segment_index = self.ending_segment_index_per_shift_index[current_segment_index];
-                current_position_index += self.segments[mapped_current_segment_index].length;
-                current_segment_index += 1;
-            }
-            else {
-                current_position_index += 1;
-            }
+        self.is_starting = false;
+        if self.current_is_parent_ending[shift_index] {
+            return false;
         }
+        if self.current_position_offset_per_shift_index[shift_index].unwrap() == self.current_maximum_position_offset_per_shift_index[shift_index] {
+            if !self.is_swapping_permitted {
+                if shift_index == 0 {
+                    // TODO check to see if we need to loop back to the first state because this shifter was randomized
 
-        // at this point all of the ending positions are known
-
-        // TODO fill out the "starting" states
-        self.starting_minimum_position_offset_per_shift_index.clear();
-        self.starting_maximum_position_offset_per_shift_index.clear();
-        self.starting_initial_position_offset_per_shift_index = self.ending_position_offset_per_shift_index.clone();
-        self.starting_segment_index_per_shift_index = self.ending_segment_index_per_shift_index.clone();
+                    // loop back to the first state
+                    self.current_initial_position_offset_per_shift_index[0] = 0;
+                    self.current_position_offset_per_shift_index[0] = Some(0);
 
-        let mut current_minimum_position_offset = 0;
-        let mut current_maximum_position_offset: Option<usize> = None;
-        for segment_index in 0..self.segments_length {
-            self.starting_minimum_position_offset_per_shift_index.push(current_minimum_position_offset);
-            if current_maximum_position_offset.is_none() {
-                let mut minimum_bounding_length = 0;
-                for other_segment_index in (segment_index + 1)..self.segments_length {
-                    if minimum_bounding_length != 0 {
-                        minimum_bounding_length += self.padding;
+                    if self.ending_position_offset_per_shift_index[0] == 0 {
+                        self.current_is_parent_ending.set(0, true);
                     }
-                    minimum_bounding_length += self.segments[self.starting_segment_index_per_shift_index[other_segment_index]].length;
-                }
-                if minimum_bounding_length != 0 {
-                    minimum_bounding_length += self.padding;
-                }
-                minimum_bounding_length += self.segments[self.starting_segment_index_per_shift_index[segment_index]].length;
-                let maximum_position_offset = (self.bounding_length - current_minimum_position_offset) - minimum_bounding_length;
-                debug!("randomize: maximum_position_offset: {:?}", maximum_position_offset);
-                current_maximum_position_offset = Some(maximum_position_offset);
-            }
-            self.starting_maximum_position_offset_per_shift_index.push(current_maximum_position_offset.unwrap());
-
-            let segment_length = self.segments[self.starting_segment_index_per_shift_index[segment_index]].length + self.padding;
-            current_minimum_position_offset = self.starting_initial_position_offset_per_shift_index[segment_index] + segment_length;
-            current_maximum_position_offset = Some(current_maximum_position_offset.unwrap() + segment_length);
-        }
-
-        // try to move the starting state forward one iteration or swap masks or fully reset
-        
-        let mut shift_index = self.segments_length - 1;
-        let mut is_still_trying_earlier_shift_indexes = true;
-        let mut mask: BitVec = BitVec::repeat(true, self.segments_length);
-        let mut recalculate_position_offsets_from_shift_index: Option<usize> = None;
-        'looking_for_position: {
-            while is_still_trying_earlier_shift_indexes {
-                if self.starting_initial_position_offset_per_shift_index[shift_index] != self.starting_maximum_position_offset_per_shift_index[shift_index] {
-                    // this shift index can move forward
-                    self.starting_initial_position_offset_per_shift_index[shift_index] += 1;
-                    if shift_index + 1 < self.segments_length {
-                        recalculate_position_offsets_from_shift_index = Some(shift_index + 1);
+                    else {
+                        self.current_is_parent_ending.set(0, false);
                     }
-                    break 'looking_for_position;
+
+                    self.is_looped = true;
+                    debug!("try_increment: looping back to start");
+                    return true;
                 }
-                if self.is_swapping_permitted {
-                    mask.set(self.starting_segment_index_per_shift_index[shift_index], false);
-                    for next_mask_index in (self.starting_segment_index_per_shift_index[shift_index] + 1)..self.segments_length {
-                        if !mask[next_mask_index] {
-                            // found a valid next mask for this shift index
-                            mask.set(next_mask_index, true);
-                            self.starting_segment_index_per_shift_index[shift_index] = next_mask_index;
-                            recalculate_position_offsets_from_shift_index = Some(shift_index);
-                            shift_index += 1;
-                            for other_mask_index in 0..self.segments_length {
-                                if !mask[other_mask_index] {
-                                    mask.set(other_mask_index, true);
-                                    self.starting_segment_index_per_shift_index[shift_index] = other_mask_index;
-                                    shift_index += 1;
-                                }
-                            }
-                            break 'looking_for_position;
+                return false;
+            }
+            let segment_index = self.current_segment_index_per_shift_index[shift_index];
+            let minimum_position_offset = self.current_minimum_position_offset_per_shift_index[shift_index];
+            let maximum_spacing_position_offset_option = if shift_index == 0 {
+                None
+            }
+            else {
+                self.get_next_position_offset_bounds(Some((self.current_segment_index_per_shift_index[shift_index - 1], self.current_position_offset_per_shift_index[shift_index - 1].unwrap()))).1
+            };
+            let is_used = |other_segment_index: usize| other_segment_index != segment_index && self.current_mask[other_segment_index];
+            let next_placement_option = ((segment_index + 1)..self.segments_length)
+                .filter(|next_segment_index| self.is_segment_next(*next_segment_index, is_used))
+                .find_map(|next_segment_index| {
+                    self.get_maximum_position_offset(is_used, next_segment_index, minimum_position_offset, maximum_spacing_position_offset_option)
+                        .map(|maximum_position_offset| (next_segment_index, maximum_position_offset))
+                });
+            if let Some((next_segment_index, maximum_position_offset)) = next_placement_option {
+                self.current_mask.set(segment_index, false);
+                self.current_mask.set(next_segment_index, true);
+                self.current_segment_index_per_shift_index[shift_index] = next_segment_index;
+                self.current_maximum_position_offset_per_shift_index[shift_index] = maximum_position_offset;
+                self.current_position_offset_per_shift_index[shift_index] = Some(minimum_position_offset);
+                if self.is_looped {
+                    if shift_index == 0 || self.current_is_parent_ending[shift_index - 1] {
+                        if self.ending_position_offset_per_shift_index[shift_index] == self.current_position_offset_per_shift_index[shift_index].unwrap() && self.ending_segment_index_per_shift_index[shift_index] == self.current_segment_index_per_shift_index[shift_index] {
+                            self.current_is_parent_ending.set(shift_index, true);
                         }
                     }
                 }
-                if shift_index == 0 {
-                    // none of the positions can move where they are right now
-                    for mask_index in 0..self.segments_length {
-                        self.starting_segment_index_per_shift_index[mask_index] = mask_index;
-                    }
-                    recalculate_position_offsets_from_shift_index = Some(0);
-                    break 'looking_for_position;
-                }
-                shift_index -= 1;
+                return true;
             }
-        }
-        if recalculate_position_offsets_from_shift_index.is_some() {
-            // recalculate minimum and maximum position offsets and set the initial position offset to the minimum
-            let shift_index = recalculate_position_offsets_from_shift_index.unwrap();
-            let mut next_minimum_position_offset;
-            let mut next_maximum_position_offset;
-            let mut previous_segment_length;
-
+            // at this point the swapped segments are all in reverse order and need to loop once back at 0th shift
             if shift_index == 0 {
-                next_minimum_position_offset = 0;
+                // loop back to the first state
+                let (first_segment_index, first_maximum_position_offset) = (0..self.segments_length)
+                    .filter(|segment_index| self.is_segment_next(*segment_index, |_| false))
+                    .find_map(|segment_index| {
+                        self.get_maximum_position_offset(|_| false, segment_index, 0, None)
+                            .map(|maximum_position_offset| (segment_index, maximum_position_offset))
+                    })
+                    .unwrap();
+                self.current_mask.set(segment_index, false);
+                self.current_mask.set(first_segment_index, true);
+                self.current_segment_index_per_shift_index[0] = first_segment_index;
+                self.current_maximum_position_offset_per_shift_index[0] = first_maximum_position_offset;
+                self.current_initial_position_offset_per_shift_index[0] = 0;
+                self.current_position_offset_per_shift_index[0] = Some(0);
 
-                // determine what the next maximum position offset is
-                next_maximum_position_offset = 0;
-                for shift_index in 0..self.segments_length {
-                    if shift_index != 0 {
-                        next_maximum_position_offset += self.padding;
-                    }
-                    let segment_index = self.starting_segment_index_per_shift_index[shift_index];
-                    next_maximum_position_offset += self.segments[segment_index].length;
+                if self.ending_position_offset_per_shift_index[0] == 0 && self.ending_segment_index_per_shift_index[0] == first_segment_index {
+                    self.current_is_parent_ending.set(0, true);
                 }
-                next_maximum_position_offset = self.bounding_length - next_maximum_position_offset;
-            }
-            else {
-                let previous_shift_index = shift_index - 1;
-                {
-                    let segment_index = self.starting_segment_index_per_shift_index[previous_shift_index];
-                    previous_segment_length = self.segments[segment_index].length + self.padding;
+                else {
+                    self.current_is_parent_ending.set(0, false);
                 }
 
-                next_minimum_position_offset = self.starting_initial_position_offset_per_shift_index[previous_shift_index] + previous_segment_length;
-                next_maximum_position_offset = self.starting_maximum_position_offset_per_shift_index[previous_shift_index] + previous_segment_length;
-            }
-
-            let segment_index = self.starting_segment_index_per_shift_index[shift_index];
-            previous_segment_length = self.segments[segment_index].length + self.padding;
-            
-            for next_shift_index in shift_index..self.segments_length {
-                debug!("randomize: next_minimum_position_offset {:?} for next_shift_index {:?}", next_minimum_position_offset, next_shift_index);
-                debug!("randomize: next_maximum_position_offset {:?} for next_shift_index {:?}", next_maximum_position_offset, next_shift_index);
-                self.starting_minimum_position_offset_per_shift_index[next_shift_index] = next_minimum_position_offset;
-                self.starting_maximum_position_offset_per_shift_index[next_shift_index] = next_maximum_position_offset;
-                self.starting_initial_position_offset_per_shift_index[next_shift_index] = next_minimum_position_offset;
-                next_minimum_position_offset += previous_segment_length;
-                next_maximum_position_offset += previous_segment_length;
-                let segment_index = self.starting_segment_index_per_shift_index[next_shift_index];
-                previous_segment_length = self.segments[segment_index].length + self.padding;
+                self.is_looped = true;
+                debug!("try_increment: looping back to start");
+                return true;
             }
+            return false;
         }
-        // determine if we happened to end up with the starting positions and segment order at the very beginning
-        'check_starting_at_beginning: {
-            for segment_index in 0..self.segments_length {
-                if self.ending_segment_index_per_shift_index[segment_index] != segment_index {
-                    self.is_starting_at_beginning = false;
-                    break 'check_starting_at_beginning;
-                }
-            }
-            let mut previous_position_offset = 0;
-            for shift_index in 0..self.segments_length {
-                if self.starting_initial_position_offset_per_shift_index[shift_index] != previous_position_offset {
-                    self.is_starting_at_beginning = false;
-                    break 'check_starting_at_beginning;
+        self.current_position_offset_per_shift_index[shift_index] = Some(self.current_position_offset_per_shift_index[shift_index].unwrap() + 1);
+        if self.is_looped {
+            if shift_index == 0 || self.current_is_parent_ending[shift_index - 1] {
+                if self.ending_position_offset_per_shift_index[shift_index] == self.current_position_offset_per_shift_index[shift_index].unwrap() && self.ending_segment_index_per_shift_index[shift_index] == self.current_segment_index_per_shift_index[shift_index] {
+                    self.current_is_parent_ending.set(shift_index, true);
                 }
-                previous_position_offset += self.segments[shift_index].length + self.padding;
             }
-            self.is_starting_at_beginning = true;
         }
-        // set is_looped based on loop criteria
-        self.is_looped = self.is_initially_looped();
+        return true;
+    }
+    fn get_indexed_element(&self) -> IndexedElement<(u8, u8)> {
+        let (current_segment_index, current_position_offset) = self.get_element_index_and_state_index();
+        return IndexedElement::new(self.path[current_position_offset].clone(), current_segment_index);
+    }
+    fn get_element_index_and_state_index(&self) -> (usize, usize) {
+        let current_position_offset = self.current_position_offset_per_shift_index.last().unwrap().unwrap();
+        let current_segment_index = *self.current_segment_index_per_shift_index.last().unwrap();
+        return (current_segment_index, current_position_offset);
+    }
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.possible_locations;
+    }
+    fn get_state_table(&self) -> Option<&[Self::T]> {
+        return Some(&self.possible_location_table);
+    }
+    fn get_length(&self) -> usize {
+        return self.segments_length;
+    }
+    fn randomize(&mut self) {
+        if self.is_starting_equal_to_ending {
+            // there is no reason to perform any randomization since there is only one state
+            return;
+        }
+        if !self.try_set_weighted_random_ending() {
+            self.set_uniformly_random_ending_within_maximum_spacing();
+        }
+
+        // at this point all of the ending positions are known
+        self.set_starting_after_ending();
     }
 }
 
 #[cfg(test)]
 mod segment_permutation_shifter_tests {
-    use std::{time::{Duration, Instant}, cell::RefCell, collections::BTreeMap};
+    use std::{time::{Duration, Instant}, cell::RefCell, collections::{BTreeMap, BTreeSet}};
 
-    use crate::incrementer::{shifter_incrementer::ShifterIncrementer, Incrementer};
+    use crate::incrementer::{shifter_incrementer::ShifterIncrementer, limited_incrementer::LimitedIncrementer, Incrementer};
 
     use super::*;
+    use itertools::Itertools;
     use rstest::rstest;
 
     fn init() {
@@ -1627,6 +2134,478 @@ mod segment_permutation_shifter_tests {
         println!("iterations: {iterations}");
     }
 
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn weighted_randomize_starts_at_only_weighted_arrangement(#[case] is_swapping_permitted: bool) {
+        init();
+
+        // only the first segment at position offset 3 and the second segment at position offset 0 have any weight
+        let segments = vec![Rc::new(Segment::new(1)), Rc::new(Segment::new(2))];
+        let position_weights_per_segment_index: Vec<Vec<f32>> = vec![
+            vec![0.0, 0.0, 0.0, 5.0, 0.0],
+            vec![2.0, 0.0, 0.0, 0.0, 0.0]
+        ];
+        let mut expected_arrangements_total: Option<usize> = None;
+        for _ in 0..50 {
+            let mut segment_permutation_shifter = SegmentPermutationShifter::new_with_position_weights(segments.clone(), (0, 0), 5, true, 1, is_swapping_permitted, position_weights_per_segment_index.clone());
+            segment_permutation_shifter.randomize();
+            let mut shifter_incrementer = ShifterIncrementer::new(Box::new(segment_permutation_shifter), vec![0, 1]);
+            assert!(shifter_incrementer.try_increment());
+            let mut indexed_elements = shifter_incrementer.get();
+            indexed_elements.sort_by_key(|indexed_element| indexed_element.index);
+            if is_swapping_permitted {
+                assert_eq!((3, 0), *indexed_elements[0].element);
+                assert_eq!((0, 0), *indexed_elements[1].element);
+            }
+            else {
+                // no arrangement that keeps the segments in order has any weight, so the arrangements are equally likely
+                assert!(*indexed_elements[0].element == (0, 0) || *indexed_elements[0].element == (1, 0));
+            }
+
+            // every arrangement is still visited exactly once
+            let mut arrangements: BTreeSet<Vec<(u8, u8)>> = BTreeSet::new();
+            arrangements.insert(indexed_elements.iter().map(|indexed_element| *indexed_element.element).collect());
+            while shifter_incrementer.try_increment() {
+                let mut indexed_elements = shifter_incrementer.get();
+                indexed_elements.sort_by_key(|indexed_element| indexed_element.index);
+                assert!(arrangements.insert(indexed_elements.iter().map(|indexed_element| *indexed_element.element).collect()));
+            }
+            if let Some(expected_arrangements_total) = expected_arrangements_total {
+                assert_eq!(expected_arrangements_total, arrangements.len());
+            }
+            else {
+                expected_arrangements_total = Some(arrangements.len());
+            }
+        }
+        if is_swapping_permitted {
+            assert_eq!(Some(6), expected_arrangements_total);
+        }
+        else {
+            assert_eq!(Some(3), expected_arrangements_total);
+        }
+    }
+
+    #[rstest]
+    fn weighted_randomize_follows_position_weights() {
+        init();
+
+        let position_weights: Vec<f32> = vec![1.0, 0.0, 2.0, 5.0];
+        let mut count_per_position_offset: Vec<usize> = vec![0; 4];
+        let iterations_total = 4000;
+        for _ in 0..iterations_total {
+            let mut segment_permutation_shifter = SegmentPermutationShifter::new_with_position_weights(vec![Rc::new(Segment::new(1))], (0, 0), 4, true, 1, false, vec![position_weights.clone()]);
+            segment_permutation_shifter.randomize();
+            assert!(segment_permutation_shifter.try_forward());
+            assert!(segment_permutation_shifter.try_increment());
+            count_per_position_offset[segment_permutation_shifter.get_indexed_element().element.0 as usize] += 1;
+        }
+        assert_eq!(0, count_per_position_offset[1]);
+        for (position_offset, weight) in position_weights.iter().enumerate() {
+            let expected = iterations_total as f32 * weight / 8.0;
+            assert!((count_per_position_offset[position_offset] as f32 - expected).abs() < iterations_total as f32 / 20.0);
+        }
+    }
+
+    #[rstest]
+    fn weighted_randomize_falls_back_when_too_many_segments_are_swappable() {
+        init();
+
+        // every subset of twenty swappable segments could be memoized, which is far more than permitted
+        let segments_total = 20;
+        let bounding_length = 24;
+        let segments: Vec<Rc<Segment>> = (0..segments_total).map(|_| Rc::new(Segment::new(1))).collect();
+        let position_weights_per_segment_index: Vec<Vec<f32>> = vec![vec![1.0; bounding_length]; segments_total];
+        let mut segment_permutation_shifter = SegmentPermutationShifter::new_with_position_weights(segments, (0, 0), bounding_length, true, 0, true, position_weights_per_segment_index);
+        segment_permutation_shifter.randomize();
+        let mut segment_indexes: BTreeSet<usize> = BTreeSet::new();
+        let mut previous_position_offset_option: Option<u8> = None;
+        for _ in 0..segments_total {
+            assert!(segment_permutation_shifter.try_forward());
+            assert!(segment_permutation_shifter.try_increment());
+            let indexed_element = segment_permutation_shifter.get_indexed_element();
+            assert!(segment_indexes.insert(indexed_element.index));
+            if let Some(previous_position_offset) = previous_position_offset_option {
+                assert!(previous_position_offset < indexed_element.element.0);
+            }
+            previous_position_offset_option = Some(indexed_element.element.0);
+        }
+        assert!(!segment_permutation_shifter.try_forward());
+    }
+
+    // returns every arrangement of the segments as (segment index, position offset) per shift index, permitting only the gaps within the spacing of the segment before each gap
+    fn get_spaced_arrangements(segment_lengths: &Vec<usize>, bounding_length: usize, spacing_per_segment_index: &Vec<Spacing>, is_swapping_permitted: bool) -> BTreeSet<Vec<(usize, usize)>> {
+        let mut segment_indexes_per_permutation: Vec<Vec<usize>> = Vec::new();
+        if is_swapping_permitted {
+            for segment_indexes in (0..segment_lengths.len()).permutations(segment_lengths.len()) {
+                segment_indexes_per_permutation.push(segment_indexes);
+            }
+        }
+        else {
+            segment_indexes_per_permutation.push((0..segment_lengths.len()).collect());
+        }
+        let mut arrangements: BTreeSet<Vec<(usize, usize)>> = BTreeSet::new();
+        for segment_indexes in segment_indexes_per_permutation.iter() {
+            let mut pending_arrangements: Vec<Vec<(usize, usize)>> = vec![Vec::new()];
+            while let Some(arrangement) = pending_arrangements.pop() {
+                let shift_index = arrangement.len();
+                if shift_index == segment_indexes.len() {
+                    arrangements.insert(arrangement);
+                    continue;
+                }
+                let segment_index = segment_indexes[shift_index];
+                let mut minimum_position_offset = 0;
+                let mut maximum_position_offset = bounding_length;
+                if let Some((previous_segment_index, previous_position_offset)) = arrangement.last() {
+                    let spacing = &spacing_per_segment_index[*previous_segment_index];
+                    minimum_position_offset = previous_position_offset + segment_lengths[*previous_segment_index] + spacing.minimum;
+                    if let Some(maximum) = spacing.maximum {
+                        maximum_position_offset = previous_position_offset + segment_lengths[*previous_segment_index] + maximum;
+                    }
+                }
+                for position_offset in minimum_position_offset..=maximum_position_offset {
+                    if position_offset + segment_lengths[segment_index] <= bounding_length {
+                        let mut next_arrangement = arrangement.clone();
+                        next_arrangement.push((segment_index, position_offset));
+                        pending_arrangements.push(next_arrangement);
+                    }
+                }
+            }
+        }
+        return arrangements;
+    }
+
+    #[rstest]
+    #[case(vec![1, 1, 1], 7, vec![Spacing::new(0, None), Spacing::new(2, None), Spacing::new(0, None)], false)]
+    #[case(vec![1, 1, 1], 7, vec![Spacing::new(0, None), Spacing::new(2, None), Spacing::new(0, None)], true)]
+    #[case(vec![1, 2], 8, vec![Spacing::new(1, Some(2)), Spacing::new(0, None)], false)]
+    #[case(vec![1, 2], 8, vec![Spacing::new(1, Some(2)), Spacing::new(0, None)], true)]
+    #[case(vec![1, 1, 1], 9, vec![Spacing::new(1, Some(1)), Spacing::new(0, Some(3)), Spacing::new(2, None)], false)]
+    #[case(vec![1, 1, 1], 9, vec![Spacing::new(1, Some(1)), Spacing::new(0, Some(3)), Spacing::new(2, None)], true)]
+    #[case(vec![1, 1, 1, 1], 10, vec![Spacing::new(0, Some(4)); 4], false)]
+    #[case(vec![2, 1, 1], 10, vec![Spacing::new(1, Some(2)), Spacing::new(0, Some(4)), Spacing::new(3, None)], true)]
+    #[case(vec![2, 1, 1], 10, vec![Spacing::new(1, None); 3], true)]
+    #[case(vec![3, 1, 2], 9, vec![Spacing::new(2, None), Spacing::new(0, None), Spacing::new(1, Some(1))], true)]
+    #[case(vec![1, 1, 1], 5, vec![Spacing::new(3, None), Spacing::new(0, None), Spacing::new(0, None)], true)]
+    fn spacing_per_segment_permits_only_spaced_arrangements(#[case] segment_lengths: Vec<usize>, #[case] bounding_length: usize, #[case] spacing_per_segment_index: Vec<Spacing>, #[case] is_swapping_permitted: bool) {
+        init();
+
+        let expected_arrangements = get_spaced_arrangements(&segment_lengths, bounding_length, &spacing_per_segment_index, is_swapping_permitted);
+        assert!(expected_arrangements.len() > 1);
+        let segments: Vec<Rc<Segment>> = segment_lengths.iter().map(|segment_length| Rc::new(Segment::new(*segment_length))).collect();
+        for is_randomized in [false, true] {
+            for _ in 0..10 {
+                let mut segment_permutation_shifter = SegmentPermutationShifter::new_with_spacing(segments.clone(), (0, 0), bounding_length, true, spacing_per_segment_index.clone(), is_swapping_permitted);
+                if is_randomized {
+                    segment_permutation_shifter.randomize();
+                }
+                let element_indexes: Vec<usize> = (0..segment_lengths.len()).collect();
+                let shifter_incrementer = ShifterIncrementer::new(Box::new(segment_permutation_shifter), element_indexes);
+                let mut arrangements: BTreeSet<Vec<(usize, usize)>> = BTreeSet::new();
+                for indexed_elements in shifter_incrementer.into_incrementer_iter() {
+                    let arrangement: Vec<(usize, usize)> = indexed_elements.iter()
+                        .map(|indexed_element| (indexed_element.index, indexed_element.element.0 as usize))
+                        .collect();
+                    assert!(arrangements.insert(arrangement.clone()), "arrangement {arrangement:?} returned more than once when randomized {is_randomized}");
+                }
+                assert_eq!(expected_arrangements, arrangements, "randomized {is_randomized}");
+            }
+        }
+    }
+
+    // covers randomizing with too many swappable segments for the weighted sampling to memoize, where the unweighted randomization must still keep within the maximum spacing
+    #[rstest]
+    fn randomize_beyond_weighted_capacity_keeps_within_maximum_spacing() {
+        init();
+
+        let segments_total = 14;
+        let segments: Vec<Rc<Segment>> = (0..segments_total).map(|_| Rc::new(Segment::new(1))).collect();
+        let spacing_per_segment_index: Vec<Spacing> = vec![Spacing::new(1, Some(2)); segments_total];
+        let bounding_length = segments_total * 2 - 1 + 8;
+        for _ in 0..20 {
+            let mut segment_permutation_shifter = SegmentPermutationShifter::new_with_spacing(segments.clone(), (0, 0), bounding_length, true, spacing_per_segment_index.clone(), true);
+            segment_permutation_shifter.randomize();
+            let mut shifter_incrementer = ShifterIncrementer::new(Box::new(segment_permutation_shifter), (0..segments_total).collect());
+            for _ in 0..10 {
+                assert!(shifter_incrementer.try_increment());
+                let mut positions: Vec<usize> = shifter_incrementer.get().iter()
+                    .map(|indexed_element| indexed_element.element.0 as usize)
+                    .collect();
+                positions.sort();
+                for position_index in 1..positions.len() {
+                    let spacing = positions[position_index] - positions[position_index - 1] - 1;
+                    assert!((1..=2).contains(&spacing), "spacing {spacing} between positions {positions:?}");
+                }
+            }
+        }
+    }
+
+    // covers randomizing with uniform padding where the later shift indexes must restart their permutation, segments of differing lengths must each advance the later position offsets by their own length, and the randomized start must only be treated as the beginning when the segments are in their original order
+    #[rstest]
+    #[case(vec![1, 2, 1], 8, 1, false)]
+    #[case(vec![1, 2, 1], 8, 1, true)]
+    #[case(vec![2, 1, 1, 3], 10, 0, true)]
+    #[case(vec![3, 1], 7, 2, true)]
+    #[case(vec![1, 3, 2], 9, 1, true)]
+    fn uniformly_padded_randomize_returns_each_arrangement_once(#[case] segment_lengths: Vec<usize>, #[case] bounding_length: usize, #[case] padding: usize, #[case] is_swapping_permitted: bool) {
+        init();
+
+        let spacing_per_segment_index: Vec<Spacing> = vec![Spacing::new(padding, None); segment_lengths.len()];
+        let expected_arrangements = get_spaced_arrangements(&segment_lengths, bounding_length, &spacing_per_segment_index, is_swapping_permitted);
+        let segments: Vec<Rc<Segment>> = segment_lengths.iter().map(|segment_length| Rc::new(Segment::new(*segment_length))).collect();
+        for _ in 0..30 {
+            let mut segment_permutation_shifter = SegmentPermutationShifter::new(segments.clone(), (0, 0), bounding_length, true, padding, is_swapping_permitted);
+            segment_permutation_shifter.randomize();
+            let shifter_incrementer = ShifterIncrementer::new(Box::new(segment_permutation_shifter), (0..segment_lengths.len()).collect());
+            let mut arrangements: BTreeSet<Vec<(usize, usize)>> = BTreeSet::new();
+            for indexed_elements in shifter_incrementer.into_incrementer_iter() {
+                let arrangement: Vec<(usize, usize)> = indexed_elements.iter()
+                    .map(|indexed_element| (indexed_element.index, indexed_element.element.0 as usize))
+                    .collect();
+                assert!(arrangements.insert(arrangement.clone()), "arrangement {arrangement:?} returned more than once");
+            }
+            assert_eq!(expected_arrangements, arrangements);
+        }
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn spacing_per_segment_requires_spacing_per_segment() {
+        SegmentPermutationShifter::new_with_spacing(vec![Rc::new(Segment::new(1)), Rc::new(Segment::new(1))], (0, 0), 4, true, Vec::new(), false);
+    }
+
+    #[rstest]
+    fn spacing_follows_segments_across_swaps() {
+        init();
+
+        // the first segment needs two empty positions after it while the second segment may touch whatever follows it
+        let segments: Vec<Rc<Segment>> = vec![Rc::new(Segment::new(1)), Rc::new(Segment::new(1))];
+        let segment_permutation_shifter = SegmentPermutationShifter::new_with_spacing(segments, (0, 0), 4, true, vec![Spacing::new(2, None), Spacing::new(0, None)], true);
+        let arrangements: BTreeSet<Vec<(usize, usize)>> = ShifterIncrementer::new(Box::new(segment_permutation_shifter), vec![0, 1]).into_incrementer_iter()
+            .map(|indexed_elements| {
+                indexed_elements.iter()
+                    .map(|indexed_element| (indexed_element.index, indexed_element.element.0 as usize))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(BTreeSet::from([
+            vec![(0, 0), (1, 3)],
+            vec![(1, 0), (0, 1)],
+            vec![(1, 0), (0, 2)],
+            vec![(1, 0), (0, 3)],
+            vec![(1, 1), (0, 2)],
+            vec![(1, 1), (0, 3)],
+            vec![(1, 2), (0, 3)]
+        ]), arrangements);
+    }
+
+    #[rstest]
+    #[case(vec![1, 1, 1], 6, vec![Spacing::new(1, None); 3], vec![(2, 0)])]
+    #[case(vec![1, 1, 1, 1], 9, vec![Spacing::new(1, None); 4], vec![(2, 1), (0, 3)])]
+    #[case(vec![1, 2, 1], 8, vec![Spacing::new(0, Some(2)), Spacing::new(1, None), Spacing::new(0, None)], vec![(1, 0)])]
+    #[case(vec![1, 1, 1, 1], 8, vec![Spacing::new(0, None); 4], vec![(0, 1), (1, 2), (2, 3)])]
+    #[case(vec![2, 1, 1], 8, vec![Spacing::new(1, Some(3)); 3], vec![(2, 1)])]
+    #[case(vec![1, 1, 1], 6, vec![Spacing::new(0, None), Spacing::new(2, None), Spacing::new(0, None)], vec![(0, 2)])]
+    fn precedence_pairs_permit_only_ordered_arrangements(#[case] segment_lengths: Vec<usize>, #[case] bounding_length: usize, #[case] spacing_per_segment_index: Vec<Spacing>, #[case] precedence_pairs: Vec<(usize, usize)>) {
+        init();
+
+        let expected_arrangements: BTreeSet<Vec<(usize, usize)>> = get_spaced_arrangements(&segment_lengths, bounding_length, &spacing_per_segment_index, true).into_iter()
+            .filter(|arrangement| {
+                precedence_pairs.iter().all(|(preceding_segment_index, following_segment_index)| {
+                    let preceding_shift_index = arrangement.iter().position(|(segment_index, _)| segment_index == preceding_segment_index).unwrap();
+                    let following_shift_index = arrangement.iter().position(|(segment_index, _)| segment_index == following_segment_index).unwrap();
+                    preceding_shift_index < following_shift_index
+                })
+            })
+            .collect();
+        assert!(expected_arrangements.len() > 1);
+        let segments: Vec<Rc<Segment>> = segment_lengths.iter().map(|segment_length| Rc::new(Segment::new(*segment_length))).collect();
+        for is_randomized in [false, true] {
+            for _ in 0..10 {
+                let mut segment_permutation_shifter = SegmentPermutationShifter::new_with_precedence(segments.clone(), (0, 0), bounding_length, true, spacing_per_segment_index.clone(), precedence_pairs.clone());
+                if is_randomized {
+                    segment_permutation_shifter.randomize();
+                }
+                let element_indexes: Vec<usize> = (0..segment_lengths.len()).collect();
+                let shifter_incrementer = ShifterIncrementer::new(Box::new(segment_permutation_shifter), element_indexes);
+                let mut arrangements: BTreeSet<Vec<(usize, usize)>> = BTreeSet::new();
+                for indexed_elements in shifter_incrementer.into_incrementer_iter() {
+                    let arrangement: Vec<(usize, usize)> = indexed_elements.iter()
+                        .map(|indexed_element| (indexed_element.index, indexed_element.element.0 as usize))
+                        .collect();
+                    assert!(arrangements.insert(arrangement.clone()), "arrangement {arrangement:?} returned more than once when randomized {is_randomized}");
+                }
+                assert_eq!(expected_arrangements, arrangements, "randomized {is_randomized}");
+            }
+        }
+    }
+
+    #[rstest]
+    fn segments_along_path_turn_corner() {
+        init();
+
+        // an L-shaped path along the top of a room and then down its right side
+        let path: Vec<(u8, u8)> = vec![(3, 5), (4, 5), (5, 5), (5, 6), (5, 7)];
+        let mut segment_permutation_shifter = SegmentPermutationShifter::new_along_path(vec![Rc::new(Segment::new(1)), Rc::new(Segment::new(2))], path, 1, false);
+        let mut located_segments_per_arrangement: Vec<Vec<(usize, (u8, u8))>> = Vec::new();
+        for _ in 0..2 {
+            located_segments_per_arrangement = segment_permutation_shifter.iter_paths()
+                .map(|indexed_elements| {
+                    indexed_elements.iter()
+                        .map(|indexed_element| (indexed_element.index, *indexed_element.element))
+                        .collect()
+                })
+                .collect();
+        }
+        assert_eq!(vec![
+            vec![(0, (3, 5)), (1, (5, 5))],
+            vec![(0, (3, 5)), (1, (5, 6))],
+            vec![(0, (4, 5)), (1, (5, 6))]
+        ], located_segments_per_arrangement);
+    }
+
+    #[rstest]
+    #[case(vec![1], 5, vec![Spacing::new(0, None)], false, Vec::new())]
+    #[case(vec![1, 2, 1], 8, vec![Spacing::new(1, None); 3], false, Vec::new())]
+    #[case(vec![1, 2, 1], 8, vec![Spacing::new(1, None); 3], true, Vec::new())]
+    #[case(vec![1, 1, 1], 9, vec![Spacing::new(1, Some(1)), Spacing::new(0, Some(3)), Spacing::new(0, None)], false, Vec::new())]
+    #[case(vec![2, 1, 1], 10, vec![Spacing::new(1, Some(2)), Spacing::new(0, Some(4)), Spacing::new(1, None)], true, Vec::new())]
+    #[case(vec![1, 2, 1], 7, vec![Spacing::new(2, None), Spacing::new(0, None), Spacing::new(1, None)], true, Vec::new())]
+    #[case(vec![1, 1, 1, 1], 9, vec![Spacing::new(1, None); 4], true, vec![(2, 1), (0, 3)])]
+    #[case(vec![2, 1, 1], 8, vec![Spacing::new(1, Some(3)); 3], true, vec![(2, 1)])]
+    fn nth_arrangement_matches_iteration_order(#[case] segment_lengths: Vec<usize>, #[case] bounding_length: usize, #[case] spacing_per_segment_index: Vec<Spacing>, #[case] is_swapping_permitted: bool, #[case] precedence_pairs: Vec<(usize, usize)>) {
+        init();
+
+        let segments: Vec<Rc<Segment>> = segment_lengths.iter().map(|segment_length| Rc::new(Segment::new(*segment_length))).collect();
+        let get_segment_permutation_shifter = || {
+            if precedence_pairs.is_empty() {
+                return SegmentPermutationShifter::new_with_spacing(segments.clone(), (0, 0), bounding_length, true, spacing_per_segment_index.clone(), is_swapping_permitted);
+            }
+            return SegmentPermutationShifter::new_with_precedence(segments.clone(), (0, 0), bounding_length, true, spacing_per_segment_index.clone(), precedence_pairs.clone());
+        };
+        let get_arrangements = |segment_permutation_shifter: SegmentPermutationShifter| -> Vec<Vec<(usize, usize)>> {
+            let shifter_incrementer = ShifterIncrementer::new(Box::new(segment_permutation_shifter), (0..segment_lengths.len()).collect());
+            return shifter_incrementer.into_incrementer_iter()
+                .map(|indexed_elements| {
+                    indexed_elements.iter()
+                        .map(|indexed_element| (indexed_element.index, indexed_element.element.0 as usize))
+                        .collect()
+                })
+                .collect();
+        };
+        let expected_arrangements = get_arrangements(get_segment_permutation_shifter());
+        assert!(expected_arrangements.len() > 1);
+        let segment_permutation_shifter = get_segment_permutation_shifter();
+        assert_eq!(Some(expected_arrangements.len() as u64), segment_permutation_shifter.permutations_total());
+        assert_eq!(Some(expected_arrangements.len() as u128), segment_permutation_shifter.get_permutations_total::<u128>());
+        for (arrangement_index, expected_arrangement) in expected_arrangements.iter().enumerate() {
+            let arrangement: Vec<(usize, usize)> = segment_permutation_shifter.get_nth_arrangement(arrangement_index as u64).iter()
+                .map(|located_segment| (located_segment.segment_index, located_segment.position))
+                .collect();
+            assert_eq!(expected_arrangement, &arrangement);
+
+            // the jumped shifter continues in order and wraps around to the earlier arrangements
+            let mut jumped_segment_permutation_shifter = get_segment_permutation_shifter();
+            jumped_segment_permutation_shifter.set_starting_arrangement_index(arrangement_index as u64);
+            let mut rotated_expected_arrangements = expected_arrangements.clone();
+            rotated_expected_arrangements.rotate_left(arrangement_index);
+            assert_eq!(rotated_expected_arrangements, get_arrangements(jumped_segment_permutation_shifter), "arrangement index {arrangement_index}");
+        }
+    }
+
+    #[rstest]
+    fn workers_split_arrangements_by_starting_arrangement_index() {
+        init();
+
+        let segments: Vec<Rc<Segment>> = vec![Rc::new(Segment::new(1)), Rc::new(Segment::new(2)), Rc::new(Segment::new(1))];
+        let get_arrangements = |incrementer: &mut dyn Incrementer<T = (u8, u8)>| -> Vec<Vec<(usize, usize)>> {
+            return incrementer.into_iter()
+                .map(|indexed_elements| {
+                    indexed_elements.iter()
+                        .map(|indexed_element| (indexed_element.index, indexed_element.element.0 as usize))
+                        .collect()
+                })
+                .collect();
+        };
+        let mut shifter_incrementer = ShifterIncrementer::new(Box::new(SegmentPermutationShifter::new(segments.clone(), (0, 0), 8, true, 1, true)), vec![0, 1, 2]);
+        let expected_arrangements = get_arrangements(&mut shifter_incrementer);
+        let permutations_total = SegmentPermutationShifter::new(segments.clone(), (0, 0), 8, true, 1, true).permutations_total().unwrap();
+        assert_eq!(expected_arrangements.len() as u64, permutations_total);
+
+        // each worker starts at its own range of arrangement indexes and stops before the range of the next worker
+        let workers_total = 4;
+        let mut arrangements: Vec<Vec<(usize, usize)>> = Vec::new();
+        for worker_index in 0..workers_total {
+            let range_start = permutations_total * worker_index / workers_total;
+            let range_end = permutations_total * (worker_index + 1) / workers_total;
+            let mut segment_permutation_shifter = SegmentPermutationShifter::new(segments.clone(), (0, 0), 8, true, 1, true);
+            segment_permutation_shifter.set_starting_arrangement_index(range_start);
+            let shifter_incrementer = ShifterIncrementer::new(Box::new(segment_permutation_shifter), vec![0, 1, 2]);
+            let mut limited_incrementer = LimitedIncrementer::new(Box::new(shifter_incrementer), (range_end - range_start) as usize);
+            arrangements.extend(get_arrangements(&mut limited_incrementer));
+        }
+        assert_eq!(expected_arrangements, arrangements);
+    }
+
+    #[rstest]
+    fn permutations_total_reports_overflow() {
+        init();
+
+        // every order of the segments fits flush within the bounding length
+        let segments: Vec<Rc<Segment>> = (0..25).map(|_| Rc::new(Segment::new(1))).collect();
+        let segment_permutation_shifter = SegmentPermutationShifter::new(segments, (0, 0), 25, true, 0, true);
+        assert_eq!(None, segment_permutation_shifter.permutations_total());
+        assert_eq!(Some(15511210043330985984000000), segment_permutation_shifter.get_permutations_total::<u128>());
+        let segment_indexes: Vec<usize> = segment_permutation_shifter.get_nth_arrangement(u64::MAX).iter()
+            .map(|located_segment| located_segment.segment_index)
+            .collect();
+        assert_eq!(25, segment_indexes.len());
+
+        // the arrangement index may be beyond a u64, where the last arrangement has every segment swapped into reverse order
+        let last_segment_indexes: Vec<usize> = segment_permutation_shifter.get_nth_arrangement(15511210043330985984000000u128 - 1).iter()
+            .map(|located_segment| located_segment.segment_index)
+            .collect();
+        assert_eq!((0..25).rev().collect::<Vec<usize>>(), last_segment_indexes);
+        let second_to_last_segment_indexes: Vec<usize> = segment_permutation_shifter.get_nth_arrangement(15511210043330985984000000u128 - 2).iter()
+            .map(|located_segment| located_segment.segment_index)
+            .collect();
+        assert_eq!((2..25).rev().chain([0, 1]).collect::<Vec<usize>>(), second_to_last_segment_indexes);
+    }
+
+    #[cfg(feature = "big-integer")]
+    #[rstest]
+    fn nth_arrangement_beyond_u128() {
+        init();
+
+        let segments: Vec<Rc<Segment>> = (0..40).map(|_| Rc::new(Segment::new(1))).collect();
+        let segment_permutation_shifter = SegmentPermutationShifter::new(segments, (0, 0), 40, true, 0, true);
+        assert_eq!(None, segment_permutation_shifter.get_permutations_total::<u128>());
+        let permutations_total: num_bigint::BigUint = segment_permutation_shifter.get_permutations_total().unwrap();
+        let last_segment_indexes: Vec<usize> = segment_permutation_shifter.get_nth_arrangement(permutations_total - 1u32).iter()
+            .map(|located_segment| located_segment.segment_index)
+            .collect();
+        assert_eq!((0..40).rev().collect::<Vec<usize>>(), last_segment_indexes);
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn nth_arrangement_must_be_within_permutations_total() {
+        let segment_permutation_shifter = SegmentPermutationShifter::new(vec![Rc::new(Segment::new(1)), Rc::new(Segment::new(1))], (0, 0), 4, true, 1, false);
+        segment_permutation_shifter.get_nth_arrangement(segment_permutation_shifter.permutations_total().unwrap());
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn optional_segments_are_rejected() {
+        SegmentPermutationShifter::new(vec![Rc::new(Segment::new(1)), Rc::new(Segment::new_optional(1))], (0, 0), 4, true, 1, true);
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn precedence_pairs_must_not_contain_cycle() {
+        SegmentPermutationShifter::new_with_precedence(vec![Rc::new(Segment::new(1)), Rc::new(Segment::new(1)), Rc::new(Segment::new(1))], (0, 0), 5, true, vec![Spacing::new(0, None); 3], vec![(0, 1), (1, 2), (2, 0)]);
+    }
+
     fn decrement_incrementer() {
         todo!();
     }
@@ -0,0 +1,100 @@
+use crate::{IndexedElement, incrementer::{Incrementer, shifter_incrementer::ShifterIncrementer}};
+use super::Shifter;
+
+// Purpose:
+//     To iterate over each full path of a shifter, from the first shift to the last shift, so that any shifter can be used alongside the standard iterator adapters
+//     The shifter is reset before the first path is searched for and each path is found by the ShifterIncrementer over the borrowed shifter
+// Notes:
+//     the element indexes are those of the shifter itself since the index mapping is the identity
+pub struct ShifterPathIterator<'a, TShifter: Shifter + ?Sized> {
+    shifter_incrementer: ShifterIncrementer<TShifter::T, &'a mut TShifter>
+}
+
+impl<'a, TShifter: Shifter + ?Sized> ShifterPathIterator<'a, TShifter> {
+    pub fn new(shifter: &'a mut TShifter) -> Self {
+        shifter.reset();
+        let index_mapping: Vec<usize> = (0..shifter.get_length()).collect();
+        ShifterPathIterator {
+            shifter_incrementer: ShifterIncrementer::new_with_shifter_pointer(shifter, index_mapping)
+        }
+    }
+}
+
+impl<'a, TShifter: Shifter + ?Sized> Iterator for ShifterPathIterator<'a, TShifter> {
+    type Item = Vec<IndexedElement<TShifter::T>>;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if !self.shifter_incrementer.try_increment() {
+            return None;
+        }
+        let states = self.shifter_incrementer.get_states_ref();
+        return Some(self.shifter_incrementer.get_element_index_and_state_index_pairs().unwrap()
+            .iter()
+            .map(|(element_index, state_index)| IndexedElement::new(states[*state_index].clone(), *element_index))
+            .collect());
+    }
+}
+
+#[cfg(test)]
+mod shifter_path_iterator_tests {
+    use std::rc::Rc;
+
+    use crate::{incrementer::shifter_incrementer::ShifterIncrementer, shifter::{index_shifter::IndexShifter, segment_permutation_shifter::{Segment, SegmentPermutationShifter}}};
+
+    use super::*;
+    use rstest::rstest;
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    #[rstest]
+    fn no_shifts() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = Vec::new();
+        let mut index_shifter = IndexShifter::new(&states_per_shift_index);
+        assert_eq!(0, index_shifter.iter_paths().count());
+    }
+
+    #[rstest]
+    fn index_shifter_paths_match_shifter_incrementer() {
+        init();
+
+        let states_per_shift_index: Vec<Vec<Rc<u8>>> = vec![
+            vec![Rc::new(0), Rc::new(1)],
+            vec![Rc::new(2)],
+            vec![Rc::new(3), Rc::new(4), Rc::new(5)]
+        ];
+        let mut index_shifter = IndexShifter::new(&states_per_shift_index);
+        let shifter_incrementer = ShifterIncrementer::new(Box::new(index_shifter.clone()), vec![0, 1, 2]);
+        let expected_paths: Vec<Vec<IndexedElement<u8>>> = shifter_incrementer.into_incrementer_iter().collect();
+        assert_eq!(6, expected_paths.len());
+        for _ in 0..2 {
+            // the shifter is reset each time that its paths are iterated over
+            let paths: Vec<Vec<IndexedElement<u8>>> = index_shifter.iter_paths().collect();
+            assert_eq!(expected_paths, paths);
+        }
+    }
+
+    #[rstest]
+    fn boxed_segment_permutation_shifter_paths() {
+        init();
+
+        let segments: Vec<Rc<Segment>> = vec![
+            Rc::new(Segment::new(1)),
+            Rc::new(Segment::new(1))
+        ];
+        let mut shifter: Box<dyn Shifter<T = (u8, u8)>> = Box::new(SegmentPermutationShifter::new(segments, (0, 0), 4, true, 1, false));
+        let locations_per_path: Vec<Vec<(u8, u8)>> = ShifterPathIterator::new(shifter.as_mut())
+            .map(|path| path.iter().map(|indexed_element| *indexed_element.element).collect())
+            .collect();
+        // each segment is followed by at least one empty position, so only the first position of the first segment and the last position of the second segment permit shifting
+        assert_eq!(vec![
+            vec![(0, 0), (2, 0)],
+            vec![(0, 0), (3, 0)],
+            vec![(1, 0), (3, 0)]
+        ], locations_per_path);
+    }
+}
@@ -1,6 +1,6 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, rc::Rc, collections::{HashMap, hash_map::RandomState}, hash::{Hash, BuildHasher}};
 use crate::IndexedElement;
-use super::{Shifter, scaling_square_breadth_first_search_shifter::ScalingSquareBreadthFirstSearchShifter};
+use super::{Shifter, scaling_square_breadth_first_search_shifter::ScalingSquareBreadthFirstSearchShifter, get_or_push_state_index, get_or_push_hashed_state_index};
 
 // Purpose:
 //     To increment each shifter forward, resetting as need be, to ensure that the earliest states of each shifter are attempted before the last states
@@ -76,8 +76,8 @@ pub struct ShiftingSquareBreadthFirstSearchShifter<T> {
     is_shifter_order_preserved_on_randomize: bool
 }
 
-impl<T: PartialEq> ShiftingSquareBreadthFirstSearchShifter<T> {
-    pub fn new(shifters: Vec<Box<dyn Shifter<T = T>>>, is_shifter_order_preserved_on_randomize: bool) -> Self {
+impl<T> ShiftingSquareBreadthFirstSearchShifter<T> {
+    fn new_with_state_index_getter(shifters: Vec<Box<dyn Shifter<T = T>>>, is_shifter_order_preserved_on_randomize: bool, get_or_push_state_index: &mut dyn FnMut(&mut Vec<Rc<T>>, &Rc<T>) -> usize) -> Self {
         let mut element_index_offset_per_shifter_index: Vec<usize> = Vec::new();
         let mut length = 0;
         let mut highest_shifter_state_length: usize = 0;
@@ -96,17 +96,9 @@ impl<T: PartialEq> ShiftingSquareBreadthFirstSearchShifter<T> {
             // determine the possible states mapping
             let mut state_index_mapping: Vec<usize> = Vec::new();
             let current_shifter_possible_states = borrowed_shifter.get_states();
-            for current_shifter_possible_state in current_shifter_possible_states {
-                'found_possible_state_index: {
-                    for (possible_state_index, possible_state) in possible_states.iter().enumerate() {
-                        if possible_state.as_ref() == current_shifter_possible_state.as_ref() {
-                            state_index_mapping.push(possible_state_index);
-                            break 'found_possible_state_index;
-                        }
-                    }
-                    state_index_mapping.push(possible_states.len());
-                    possible_states.push(current_shifter_possible_state);
-                }
+            for current_shifter_possible_state in current_shifter_possible_states.iter() {
+                let possible_state_index = get_or_push_state_index(&mut possible_states, current_shifter_possible_state);
+                state_index_mapping.push(possible_state_index);
             }
             state_index_mapping_per_shifter_index.push(state_index_mapping);
         }
@@ -131,6 +123,23 @@ impl<T: PartialEq> ShiftingSquareBreadthFirstSearchShifter<T> {
     }
 }
 
+impl<T: PartialEq> ShiftingSquareBreadthFirstSearchShifter<T> {
+    pub fn new(shifters: Vec<Box<dyn Shifter<T = T>>>, is_shifter_order_preserved_on_randomize: bool) -> Self {
+        return Self::new_with_state_index_getter(shifters, is_shifter_order_preserved_on_randomize, &mut get_or_push_state_index);
+    }
+}
+
+impl<T: Eq + Hash> ShiftingSquareBreadthFirstSearchShifter<T> {
+    /// Produces the same shifter as new, but finds duplicate states across the shifters by hashing instead of comparing every pair of states
+    pub fn new_hashed(shifters: Vec<Box<dyn Shifter<T = T>>>, is_shifter_order_preserved_on_randomize: bool) -> Self {
+        return Self::new_hashed_with_hasher::<RandomState>(shifters, is_shifter_order_preserved_on_randomize);
+    }
+    pub fn new_hashed_with_hasher<S: BuildHasher + Default>(shifters: Vec<Box<dyn Shifter<T = T>>>, is_shifter_order_preserved_on_randomize: bool) -> Self {
+        let mut state_index_per_state: HashMap<Rc<T>, usize, S> = HashMap::default();
+        return Self::new_with_state_index_getter(shifters, is_shifter_order_preserved_on_randomize, &mut |possible_states, state| get_or_push_hashed_state_index(possible_states, &mut state_index_per_state, state));
+    }
+}
+
 impl<T> Shifter for ShiftingSquareBreadthFirstSearchShifter<T> {
     type T = T;
 
@@ -319,8 +328,24 @@ impl<T> Shifter for ShiftingSquareBreadthFirstSearchShifter<T> {
         state_index = self.state_index_mapping_per_shifter_index[current_shifter_index][state_index];
         return (element_index, state_index);
     }
-    fn get_states(&self) -> Vec<Rc<Self::T>> {
-        return self.possible_states.clone();
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.possible_states;
+    }
+    fn get_state_indexes_per_element_index(&self) -> Option<Vec<Vec<usize>>> {
+        let mut state_indexes_per_element_index: Vec<Vec<usize>> = Vec::new();
+        for (shifter_index, shifter) in self.shifters.iter().enumerate() {
+            match shifter.get_state_indexes_per_element_index() {
+                Some(shifter_state_indexes_per_element_index) => {
+                    for shifter_state_indexes in shifter_state_indexes_per_element_index.iter() {
+                        state_indexes_per_element_index.push(shifter_state_indexes.iter().map(|state_index| self.state_index_mapping_per_shifter_index[shifter_index][*state_index]).collect());
+                    }
+                },
+                None => {
+                    return None;
+                }
+            }
+        }
+        return Some(state_indexes_per_element_index);
     }
     fn randomize(&mut self) {
         // TODO determine if this misorders indexes - should a mapper be used and randomized instead?
@@ -331,6 +356,9 @@ impl<T> Shifter for ShiftingSquareBreadthFirstSearchShifter<T> {
             fastrand::shuffle(&mut self.shifters);
         }
     }
+    fn is_rotation_possible(&self) -> bool {
+        return self.shifters.iter().any(|shifter| shifter.is_rotation_possible());
+    }
 }
 
 #[cfg(test)]
@@ -1697,4 +1725,30 @@ mod shifting_square_breadth_first_search_shifter_tests {
         }
     }
 
-}
\ No newline at end of file
+    #[rstest]
+    fn hashed_matches_unhashed() {
+        init();
+
+        let get_shifters = || -> Vec<Box<dyn Shifter<T = (u8, u8)>>> {
+            return vec![
+                Box::new(SegmentPermutationShifter::new(vec![
+                    Rc::new(Segment::new(1)),
+                    Rc::new(Segment::new(1))
+                ], (10, 100), 4, true, 1, false)),
+                Box::new(SegmentPermutationShifter::new(vec![
+                    Rc::new(Segment::new(1))
+                ], (10, 100), 3, false, 1, false))
+            ];
+        };
+        let mut unhashed_shifter_incrementer = ShifterIncrementer::new(Box::new(ShiftingSquareBreadthFirstSearchShifter::new(get_shifters(), true)), vec![0, 1, 2]);
+        let mut hashed_shifter_incrementer = ShifterIncrementer::new(Box::new(ShiftingSquareBreadthFirstSearchShifter::new_hashed(get_shifters(), true)), vec![0, 1, 2]);
+        let mut permutations_total = 0;
+        while unhashed_shifter_incrementer.try_increment() {
+            assert!(hashed_shifter_incrementer.try_increment());
+            assert_eq!(unhashed_shifter_incrementer.get(), hashed_shifter_incrementer.get());
+            permutations_total += 1;
+        }
+        assert!(!hashed_shifter_incrementer.try_increment());
+        assert_eq!(9, permutations_total);
+    }
+}
@@ -0,0 +1,60 @@
+use std::{rc::Rc, cell::RefCell};
+pub mod voxel_board_randomizer;
+
+pub trait Voxel {
+    fn get_invalid_location_offsets_for_other_voxel(&self, other_voxel: &Self) -> Vec<(i16, i16, i16)>;
+}
+
+pub struct VoxelBoard<T: Voxel> {
+    width: usize,
+    height: usize,
+    depth: usize,
+    voxels: Vec<Option<Rc<RefCell<T>>>>
+}
+
+impl<T: Voxel> Clone for VoxelBoard<T> {
+    fn clone(&self) -> Self {
+        Self {
+            width: self.width.clone(),
+            height: self.height.clone(),
+            depth: self.depth.clone(),
+            voxels: self.voxels.clone()
+        }
+    }
+}
+
+impl<T: Voxel> VoxelBoard<T> {
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        let mut voxels = Vec::new();
+        for _ in 0..(width * height * depth) {
+            voxels.push(None);
+        }
+        VoxelBoard {
+            width: width,
+            height: height,
+            depth: depth,
+            voxels: voxels
+        }
+    }
+    pub fn set(&mut self, x: usize, y: usize, z: usize, voxel: Rc<RefCell<T>>) {
+        let index = (z * self.height + y) * self.width + x;
+        let _ = self.voxels[index].insert(voxel);
+    }
+    pub fn exists(&self, x: usize, y: usize, z: usize) -> bool {
+        let index = (z * self.height + y) * self.width + x;
+        self.voxels[index].is_some()
+    }
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<Rc<RefCell<T>>> {
+        let index = (z * self.height + y) * self.width + x;
+        self.voxels[index].clone()
+    }
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+}
@@ -0,0 +1,525 @@
+use std::{rc::Rc, collections::{BTreeSet, BTreeMap}};
+use bitvec::vec::BitVec;
+use itertools::Itertools;
+use crate::{IndexedElement, VoxelCellGroup, incrementer::{Incrementer, shifting_cell_group_dependency_incrementer::{CellGroupDependency, Neighborhood, ShiftingCellGroupDependencyIncrementer, VoxelNeighborhood}}, shifter::{Shifter, index_shifter::IndexShifter, segment_permutation_shifter::{Segment, SegmentPermutationShifter}, shifting_square_breadth_first_search_shifter::ShiftingSquareBreadthFirstSearchShifter}};
+use super::{Voxel, VoxelBoard};
+
+// the six face neighbors of a voxel
+const VOXEL_NEIGHBOR_OFFSETS: [(isize, isize, isize); 6] = [(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)];
+
+fn get_axis_value(cell: &(u8, u8, u8), axis_index: usize) -> u8 {
+    match axis_index {
+        0 => cell.0,
+        1 => cell.1,
+        _ => cell.2
+    }
+}
+
+fn set_axis_value(cell: &mut (u8, u8, u8), axis_index: usize, value: u8) {
+    match axis_index {
+        0 => cell.0 = value,
+        1 => cell.1 = value,
+        _ => cell.2 = value
+    }
+}
+
+fn get_minimum_cell(cells: &Vec<(u8, u8, u8)>) -> (u8, u8, u8) {
+    (cells.iter().map(|cell| cell.0).min().unwrap(), cells.iter().map(|cell| cell.1).min().unwrap(), cells.iter().map(|cell| cell.2).min().unwrap())
+}
+
+// Purpose:
+//      To permit a SegmentPermutationShifter to shift wall segments along a row of one face of the voxel board
+//      The SegmentPermutationShifter shifts along a horizontal line from (0, 0) and each position offset is mapped onto the row
+#[derive(Clone)]
+struct WallRowShifter {
+    segment_permutation_shifter: SegmentPermutationShifter,
+    possible_locations: Vec<Rc<(u8, u8, u8)>>
+}
+
+impl WallRowShifter {
+    fn new(segments: Vec<Rc<Segment>>, origin: (u8, u8, u8), axis_index: usize, bounding_length: usize) -> Self {
+        let segment_permutation_shifter = SegmentPermutationShifter::new(segments, (0, 0), bounding_length, true, 1, false);
+        let possible_locations: Vec<Rc<(u8, u8, u8)>> = segment_permutation_shifter.get_states().iter()
+            .map(|state| {
+                let mut possible_location = origin;
+                set_axis_value(&mut possible_location, axis_index, get_axis_value(&origin, axis_index) + state.0);
+                Rc::new(possible_location)
+            })
+            .collect();
+        WallRowShifter {
+            segment_permutation_shifter: segment_permutation_shifter,
+            possible_locations: possible_locations
+        }
+    }
+}
+
+impl Shifter for WallRowShifter {
+    type T = (u8, u8, u8);
+
+    fn try_forward(&mut self) -> bool {
+        return self.segment_permutation_shifter.try_forward();
+    }
+    fn try_backward(&mut self) -> bool {
+        return self.segment_permutation_shifter.try_backward();
+    }
+    fn try_increment(&mut self) -> bool {
+        return self.segment_permutation_shifter.try_increment();
+    }
+    fn get_indexed_element(&self) -> IndexedElement<Self::T> {
+        let (element_index, state_index) = self.get_element_index_and_state_index();
+        return IndexedElement::new(self.possible_locations[state_index].clone(), element_index);
+    }
+    fn get_length(&self) -> usize {
+        return self.segment_permutation_shifter.get_length();
+    }
+    fn get_element_index_and_state_index(&self) -> (usize, usize) {
+        return self.segment_permutation_shifter.get_element_index_and_state_index();
+    }
+    fn get_states_ref(&self) -> &[Rc<Self::T>] {
+        return &self.possible_locations;
+    }
+    fn randomize(&mut self) {
+        self.segment_permutation_shifter.randomize();
+    }
+}
+
+// Purpose:
+//      To randomize the cell groups of a VoxelBoard, where the six faces of the box are the walls
+//      Wall cell groups that touch an edge or corner of the box, or that spread across a face in two dimensions, remain where they are
+//      Wall cell groups that form a straight row within a face shuffle along that row between the edges of the face
+//      Inner cell groups shuffle anywhere inside of the walls while keeping the same adjacency to the other cell groups that they started with
+pub struct VoxelBoardRandomizer<TVoxel: Voxel> {
+    voxel_board: VoxelBoard<TVoxel>,
+    cell_groups: Rc<Vec<VoxelCellGroup>>,
+    voxel_board_coordinate_per_cell_group_index: Vec<(u8, u8, u8)>,
+    index_shifters: Vec<IndexShifter<(u8, u8, u8)>>,
+    cell_group_index_per_index_shifter: Vec<usize>,
+    wall_row_shifters: Vec<WallRowShifter>,
+    cell_group_indexes_per_wall_row_shifter: Vec<Vec<usize>>,
+    detection_offsets_per_cell_group_index_per_cell_group_index: Rc<Vec<Vec<Vec<(i16, i16, i16)>>>>,
+    is_adjacent_cell_group_index_per_cell_group_index: Rc<Vec<BitVec>>
+}
+
+impl<TVoxel: Voxel> VoxelBoardRandomizer<TVoxel> {
+    pub fn new(voxel_board: VoxelBoard<TVoxel>) -> Self {
+        let dimensions: [usize; 3] = [voxel_board.get_width(), voxel_board.get_height(), voxel_board.get_depth()];
+        let get_boundary_axes_total = |cell: &(u8, u8, u8)| -> usize {
+            (0..3).filter(|axis_index| {
+                let value = get_axis_value(cell, *axis_index) as usize;
+                value == 0 || value + 1 == dimensions[*axis_index]
+            }).count()
+        };
+        let get_neighbors = |cell: &(u8, u8, u8)| -> Vec<(u8, u8, u8)> {
+            let mut neighbors: Vec<(u8, u8, u8)> = Vec::new();
+            for offset in VOXEL_NEIGHBOR_OFFSETS.iter() {
+                let neighbor = (cell.0 as isize + offset.0, cell.1 as isize + offset.1, cell.2 as isize + offset.2);
+                if neighbor.0 >= 0 && (neighbor.0 as usize) < dimensions[0] &&
+                    neighbor.1 >= 0 && (neighbor.1 as usize) < dimensions[1] &&
+                    neighbor.2 >= 0 && (neighbor.2 as usize) < dimensions[2] {
+
+                    neighbors.push((neighbor.0 as u8, neighbor.1 as u8, neighbor.2 as u8));
+                }
+            }
+            return neighbors;
+        };
+        let is_voxel = |cell: &(u8, u8, u8)| -> bool {
+            voxel_board.exists(cell.0 as usize, cell.1 as usize, cell.2 as usize)
+        };
+
+        // collect each connected collection of wall voxels and of inner voxels separately
+        let mut wall_raw_cell_groups: Vec<Vec<(u8, u8, u8)>> = Vec::new();
+        let mut inner_raw_cell_groups: Vec<Vec<(u8, u8, u8)>> = Vec::new();
+        {
+            let mut visited_cells: BTreeSet<(u8, u8, u8)> = BTreeSet::new();
+            for z in 0..dimensions[2] {
+                for y in 0..dimensions[1] {
+                    for x in 0..dimensions[0] {
+                        let cell = (x as u8, y as u8, z as u8);
+                        if is_voxel(&cell) && !visited_cells.contains(&cell) {
+                            let is_wall = get_boundary_axes_total(&cell) != 0;
+                            let mut cells: Vec<(u8, u8, u8)> = Vec::new();
+                            let mut possible_cells: Vec<(u8, u8, u8)> = vec![cell];
+                            visited_cells.insert(cell);
+                            while let Some(possible_cell) = possible_cells.pop() {
+                                cells.push(possible_cell);
+                                for neighbor in get_neighbors(&possible_cell) {
+                                    if is_voxel(&neighbor) && !visited_cells.contains(&neighbor) && (get_boundary_axes_total(&neighbor) != 0) == is_wall {
+                                        visited_cells.insert(neighbor);
+                                        possible_cells.push(neighbor);
+                                    }
+                                }
+                            }
+                            cells.sort();
+                            if is_wall {
+                                wall_raw_cell_groups.push(cells);
+                            }
+                            else {
+                                inner_raw_cell_groups.push(cells);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut raw_cell_groups: Vec<Vec<(u8, u8, u8)>> = Vec::new();
+        let mut index_shifters: Vec<IndexShifter<(u8, u8, u8)>> = Vec::new();
+        let mut cell_group_index_per_index_shifter: Vec<usize> = Vec::new();
+        let mut wall_row_shifters: Vec<WallRowShifter> = Vec::new();
+        let mut cell_group_indexes_per_wall_row_shifter: Vec<Vec<usize>> = Vec::new();
+
+        // determine which wall cell groups are rows within a face, keyed by the face axis, the slide axis, and the fixed location of the row
+        {
+            let mut row_cell_group_indexes_per_row_key: BTreeMap<(usize, usize, (u8, u8, u8)), Vec<usize>> = BTreeMap::new();
+            let get_face_axis_index = |cell: &(u8, u8, u8)| -> usize {
+                (0..3).find(|axis_index| {
+                    let value = get_axis_value(cell, *axis_index) as usize;
+                    value == 0 || value + 1 == dimensions[*axis_index]
+                }).unwrap()
+            };
+            let get_row_key = |cell: &(u8, u8, u8), face_axis_index: usize, slide_axis_index: usize| -> (usize, usize, (u8, u8, u8)) {
+                let mut row_origin = *cell;
+                set_axis_value(&mut row_origin, slide_axis_index, 1);
+                (face_axis_index, slide_axis_index, row_origin)
+            };
+            // single voxels could slide along either axis of their face, so they are considered after the longer rows are known
+            let mut single_voxel_cell_group_indexes: Vec<usize> = Vec::new();
+            for wall_raw_cell_group in wall_raw_cell_groups.into_iter() {
+                let cell_group_index = raw_cell_groups.len();
+                let is_within_face = wall_raw_cell_group.iter().all(|cell| get_boundary_axes_total(cell) == 1);
+                let mut is_row = false;
+                if is_within_face {
+                    if wall_raw_cell_group.len() == 1 {
+                        single_voxel_cell_group_indexes.push(cell_group_index);
+                        is_row = true;
+                    }
+                    else {
+                        let first_cell = wall_raw_cell_group[0];
+                        let face_axis_index = get_face_axis_index(&first_cell);
+                        let slide_axis_index_option = (0..3).filter(|axis_index| *axis_index != face_axis_index).find(|axis_index| {
+                            wall_raw_cell_group.iter().all(|cell| {
+                                (0..3).all(|other_axis_index| other_axis_index == *axis_index || get_axis_value(cell, other_axis_index) == get_axis_value(&first_cell, other_axis_index))
+                            })
+                        });
+                        if let Some(slide_axis_index) = slide_axis_index_option {
+                            row_cell_group_indexes_per_row_key.entry(get_row_key(&first_cell, face_axis_index, slide_axis_index)).or_insert(Vec::new()).push(cell_group_index);
+                            is_row = true;
+                        }
+                    }
+                }
+                if !is_row {
+                    // the wall cell group remains fixed
+                    index_shifters.push(IndexShifter::new(&vec![vec![Rc::new(get_minimum_cell(&wall_raw_cell_group))]]));
+                    cell_group_index_per_index_shifter.push(cell_group_index);
+                }
+                raw_cell_groups.push(wall_raw_cell_group);
+            }
+            for cell_group_index in single_voxel_cell_group_indexes.into_iter() {
+                // join an existing row if one exists along either axis of the face, otherwise slide along the first axis of the face
+                let cell = raw_cell_groups[cell_group_index][0];
+                let face_axis_index = get_face_axis_index(&cell);
+                let in_face_axis_indexes: Vec<usize> = (0..3).filter(|axis_index| *axis_index != face_axis_index).collect();
+                let row_key = in_face_axis_indexes.iter()
+                    .map(|slide_axis_index| get_row_key(&cell, face_axis_index, *slide_axis_index))
+                    .find(|row_key| row_cell_group_indexes_per_row_key.contains_key(row_key))
+                    .unwrap_or(get_row_key(&cell, face_axis_index, in_face_axis_indexes[0]));
+                row_cell_group_indexes_per_row_key.entry(row_key).or_insert(Vec::new()).push(cell_group_index);
+            }
+
+            for ((_, slide_axis_index, row_origin), mut row_cell_group_indexes) in row_cell_group_indexes_per_row_key.into_iter() {
+                row_cell_group_indexes.sort_by_key(|cell_group_index| get_axis_value(&raw_cell_groups[*cell_group_index][0], slide_axis_index));
+                let segments: Vec<Rc<Segment>> = row_cell_group_indexes.iter()
+                    .map(|cell_group_index| Rc::new(Segment::new(raw_cell_groups[*cell_group_index].len())))
+                    .collect();
+                wall_row_shifters.push(WallRowShifter::new(segments, row_origin, slide_axis_index, dimensions[slide_axis_index] - 2));
+                cell_group_indexes_per_wall_row_shifter.push(row_cell_group_indexes);
+            }
+        }
+
+        // the inner cell groups may exist anywhere that all of their cells remain inside of the walls
+        for inner_raw_cell_group in inner_raw_cell_groups.into_iter() {
+            let minimum_cell = get_minimum_cell(&inner_raw_cell_group);
+            let relative_cells: Vec<(u8, u8, u8)> = inner_raw_cell_group.iter()
+                .map(|cell| (cell.0 - minimum_cell.0, cell.1 - minimum_cell.1, cell.2 - minimum_cell.2))
+                .collect();
+            let mut states: Vec<Rc<(u8, u8, u8)>> = Vec::new();
+            for z in 1..dimensions[2] {
+                for y in 1..dimensions[1] {
+                    for x in 1..dimensions[0] {
+                        let is_inside_walls = relative_cells.iter().all(|relative_cell| {
+                            relative_cell.0 as usize + x + 1 < dimensions[0] &&
+                                relative_cell.1 as usize + y + 1 < dimensions[1] &&
+                                relative_cell.2 as usize + z + 1 < dimensions[2]
+                        });
+                        if is_inside_walls {
+                            states.push(Rc::new((x as u8, y as u8, z as u8)));
+                        }
+                    }
+                }
+            }
+            index_shifters.push(IndexShifter::new_hashed(&vec![states]));
+            cell_group_index_per_index_shifter.push(raw_cell_groups.len());
+            raw_cell_groups.push(inner_raw_cell_group);
+        }
+
+        // at this point all cell groups are known
+
+        let mut voxel_board_coordinate_per_cell_group_index: Vec<(u8, u8, u8)> = Vec::new();
+        let mut transformed_cell_groups: Vec<VoxelCellGroup> = Vec::new();
+        for raw_cell_group in raw_cell_groups.iter() {
+            let minimum_cell = get_minimum_cell(raw_cell_group);
+            voxel_board_coordinate_per_cell_group_index.push(minimum_cell);
+            transformed_cell_groups.push(VoxelCellGroup {
+                cells: raw_cell_group.iter()
+                    .map(|cell| (cell.0 - minimum_cell.0, cell.1 - minimum_cell.1, cell.2 - minimum_cell.2))
+                    .collect()
+            });
+        }
+
+        // cell groups that start adjacent to each other must remain adjacent and all others must never become adjacent
+        let mut is_adjacent_cell_group_index_per_cell_group_index: Vec<BitVec> = Vec::new();
+        for (cell_group_index, raw_cell_group) in raw_cell_groups.iter().enumerate() {
+            let mut is_adjacent_per_cell_group_index: BitVec = BitVec::repeat(false, raw_cell_groups.len());
+            for (other_cell_group_index, other_raw_cell_group) in raw_cell_groups.iter().enumerate() {
+                if other_cell_group_index > cell_group_index {
+                    let is_adjacent = raw_cell_group.iter()
+                        .cartesian_product(other_raw_cell_group.iter())
+                        .any(|(cell, other_cell)| VoxelNeighborhood::Face.is_adjacent(cell, other_cell));
+                    is_adjacent_per_cell_group_index.set(other_cell_group_index, is_adjacent);
+                }
+            }
+            is_adjacent_cell_group_index_per_cell_group_index.push(is_adjacent_per_cell_group_index);
+        }
+
+        let mut detection_offsets_per_cell_group_index_per_cell_group_index: Vec<Vec<Vec<(i16, i16, i16)>>> = Vec::new();
+        for (cell_group_index, raw_cell_group) in raw_cell_groups.iter().enumerate() {
+            let voxel_board_coordinate = voxel_board_coordinate_per_cell_group_index[cell_group_index];
+            let mut detection_offsets_per_cell_group_index: Vec<Vec<(i16, i16, i16)>> = Vec::new();
+            for (other_cell_group_index, other_raw_cell_group) in raw_cell_groups.iter().enumerate() {
+                let mut raw_detection_offsets: Vec<(i16, i16, i16)> = Vec::new();
+                if other_cell_group_index != cell_group_index {
+                    for cell in raw_cell_group.iter() {
+                        let voxel = voxel_board.get(cell.0 as usize, cell.1 as usize, cell.2 as usize).unwrap();
+                        let borrowed_voxel: &TVoxel = &voxel.borrow();
+                        for other_cell in other_raw_cell_group.iter() {
+                            let other_voxel = voxel_board.get(other_cell.0 as usize, other_cell.1 as usize, other_cell.2 as usize).unwrap();
+                            let borrowed_other_voxel: &TVoxel = &other_voxel.borrow();
+                            for invalid_location_offset in borrowed_voxel.get_invalid_location_offsets_for_other_voxel(borrowed_other_voxel).iter() {
+                                let x = (cell.0 - voxel_board_coordinate.0) as i16 + invalid_location_offset.0;
+                                let y = (cell.1 - voxel_board_coordinate.1) as i16 + invalid_location_offset.1;
+                                let z = (cell.2 - voxel_board_coordinate.2) as i16 + invalid_location_offset.2;
+                                raw_detection_offsets.push((x, y, z));
+                            }
+                        }
+                    }
+                }
+                detection_offsets_per_cell_group_index.push(raw_detection_offsets.into_iter().unique().collect());
+            }
+            detection_offsets_per_cell_group_index_per_cell_group_index.push(detection_offsets_per_cell_group_index);
+        }
+
+        VoxelBoardRandomizer {
+            voxel_board: voxel_board,
+            cell_groups: Rc::new(transformed_cell_groups),
+            voxel_board_coordinate_per_cell_group_index: voxel_board_coordinate_per_cell_group_index,
+            index_shifters: index_shifters,
+            cell_group_index_per_index_shifter: cell_group_index_per_index_shifter,
+            wall_row_shifters: wall_row_shifters,
+            cell_group_indexes_per_wall_row_shifter: cell_group_indexes_per_wall_row_shifter,
+            detection_offsets_per_cell_group_index_per_cell_group_index: Rc::new(detection_offsets_per_cell_group_index_per_cell_group_index),
+            is_adjacent_cell_group_index_per_cell_group_index: Rc::new(is_adjacent_cell_group_index_per_cell_group_index)
+        }
+    }
+    pub fn get_random_voxel_board(&self) -> VoxelBoard<TVoxel> {
+        if self.cell_groups.is_empty() {
+            // nothing to randomize
+            return self.voxel_board.clone();
+        }
+
+        let mut shifters: Vec<Box<dyn Shifter<T = (u8, u8, u8)>>> = Vec::new();
+        let mut cell_group_index_mapping: Vec<usize> = Vec::new();
+        for (wall_row_shifter, cell_group_indexes) in self.wall_row_shifters.iter().zip(self.cell_group_indexes_per_wall_row_shifter.iter()) {
+            let mut shifter = wall_row_shifter.clone();
+            shifter.randomize();
+            shifters.push(Box::new(shifter));
+            cell_group_index_mapping.extend(cell_group_indexes.iter());
+        }
+        for (index_shifter, cell_group_index) in self.index_shifters.iter().zip(self.cell_group_index_per_index_shifter.iter()) {
+            let mut shifter = index_shifter.clone();
+            shifter.randomize();
+            shifters.push(Box::new(shifter));
+            cell_group_index_mapping.push(*cell_group_index);
+        }
+        let cell_group_dependency = CellGroupDependency::new(cell_group_index_mapping, Box::new(ShiftingSquareBreadthFirstSearchShifter::new(shifters, true)));
+        let mut shifting_voxel_cell_group_dependency_incrementer = ShiftingCellGroupDependencyIncrementer::new_with_neighborhood(
+            self.cell_groups.clone(),
+            vec![cell_group_dependency],
+            Some(self.detection_offsets_per_cell_group_index_per_cell_group_index.clone()),
+            Some(self.is_adjacent_cell_group_index_per_cell_group_index.clone()),
+            VoxelNeighborhood::Face
+        );
+        if !shifting_voxel_cell_group_dependency_incrementer.try_increment() {
+            panic!("Unexpected failure to find the original placement, let alone a new random one.");
+        }
+
+        let mut random_voxel_board: VoxelBoard<TVoxel> = VoxelBoard::new(self.voxel_board.get_width(), self.voxel_board.get_height(), self.voxel_board.get_depth());
+        for indexed_element in shifting_voxel_cell_group_dependency_incrementer.get() {
+            let location = *indexed_element.element.as_ref();
+            let voxel_board_coordinate = self.voxel_board_coordinate_per_cell_group_index[indexed_element.index];
+            for cell in self.cell_groups[indexed_element.index].cells.iter() {
+                let voxel = self.voxel_board.get((voxel_board_coordinate.0 + cell.0) as usize, (voxel_board_coordinate.1 + cell.1) as usize, (voxel_board_coordinate.2 + cell.2) as usize).unwrap();
+                random_voxel_board.set((location.0 + cell.0) as usize, (location.1 + cell.1) as usize, (location.2 + cell.2) as usize, voxel);
+            }
+        }
+        return random_voxel_board;
+    }
+}
+
+#[cfg(test)]
+mod voxel_board_randomizer_tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use rstest::rstest;
+    use uuid::Uuid;
+
+    struct Tile {
+        image_id: String
+    }
+
+    enum ExampleVoxel {
+        Tile(Tile)
+    }
+
+    impl Voxel for ExampleVoxel {
+        fn get_invalid_location_offsets_for_other_voxel(&self, _other_voxel: &ExampleVoxel) -> Vec<(i16, i16, i16)> {
+            return Vec::new();
+        }
+    }
+
+    fn init() {
+        std::env::set_var("RUST_LOG", "trace");
+        //pretty_env_logger::try_init();
+    }
+
+    fn get_tile_voxel() -> Rc<RefCell<ExampleVoxel>> {
+        Rc::new(RefCell::new(ExampleVoxel::Tile(Tile {
+            image_id: Uuid::new_v4().to_string()
+        })))
+    }
+
+    fn get_image_id_per_cell(voxel_board: &VoxelBoard<ExampleVoxel>) -> Vec<((usize, usize, usize), String)> {
+        let mut image_id_per_cell = Vec::new();
+        for z in 0..voxel_board.get_depth() {
+            for y in 0..voxel_board.get_height() {
+                for x in 0..voxel_board.get_width() {
+                    if let Some(voxel) = voxel_board.get(x, y, z) {
+                        let ExampleVoxel::Tile(tile) = &*voxel.borrow();
+                        image_id_per_cell.push(((x, y, z), tile.image_id.clone()));
+                    }
+                }
+            }
+        }
+        return image_id_per_cell;
+    }
+
+    #[rstest]
+    fn empty_board_remains_empty() {
+        init();
+
+        let voxel_board: VoxelBoard<ExampleVoxel> = VoxelBoard::new(4, 5, 6);
+        let voxel_board_randomizer = VoxelBoardRandomizer::new(voxel_board);
+        for _ in 0..10 {
+            let random_voxel_board = voxel_board_randomizer.get_random_voxel_board();
+            assert!(get_image_id_per_cell(&random_voxel_board).is_empty());
+        }
+    }
+
+    #[rstest]
+    fn edge_wall_remains_fixed() {
+        init();
+
+        let mut voxel_board: VoxelBoard<ExampleVoxel> = VoxelBoard::new(5, 5, 5);
+        voxel_board.set(0, 0, 2, get_tile_voxel());
+        voxel_board.set(4, 2, 4, get_tile_voxel());
+        let expected_image_id_per_cell = get_image_id_per_cell(&voxel_board);
+        let voxel_board_randomizer = VoxelBoardRandomizer::new(voxel_board);
+        for _ in 0..10 {
+            let random_voxel_board = voxel_board_randomizer.get_random_voxel_board();
+            assert_eq!(expected_image_id_per_cell, get_image_id_per_cell(&random_voxel_board));
+        }
+    }
+
+    #[rstest]
+    fn single_face_voxel_slides_along_row() {
+        init();
+
+        let mut voxel_board: VoxelBoard<ExampleVoxel> = VoxelBoard::new(5, 5, 5);
+        voxel_board.set(0, 2, 2, get_tile_voxel());
+        let voxel_board_randomizer = VoxelBoardRandomizer::new(voxel_board);
+        let mut found_cells: BTreeSet<(usize, usize, usize)> = BTreeSet::new();
+        for _ in 0..50 {
+            let random_voxel_board = voxel_board_randomizer.get_random_voxel_board();
+            let image_id_per_cell = get_image_id_per_cell(&random_voxel_board);
+            assert_eq!(1, image_id_per_cell.len());
+            found_cells.insert(image_id_per_cell[0].0);
+        }
+        assert_eq!(BTreeSet::from([(0, 1, 2), (0, 2, 2), (0, 3, 2)]), found_cells);
+    }
+
+    #[rstest]
+    fn face_row_segments_remain_in_order() {
+        init();
+
+        let mut voxel_board: VoxelBoard<ExampleVoxel> = VoxelBoard::new(5, 5, 7);
+        let first_voxel = get_tile_voxel();
+        let second_voxel = get_tile_voxel();
+        voxel_board.set(2, 0, 1, first_voxel.clone());
+        voxel_board.set(2, 0, 2, first_voxel.clone());
+        voxel_board.set(2, 0, 4, second_voxel.clone());
+        let voxel_board_randomizer = VoxelBoardRandomizer::new(voxel_board);
+        let mut found_z_pairs: BTreeSet<(usize, usize)> = BTreeSet::new();
+        for _ in 0..100 {
+            let random_voxel_board = voxel_board_randomizer.get_random_voxel_board();
+            let image_id_per_cell = get_image_id_per_cell(&random_voxel_board);
+            assert_eq!(3, image_id_per_cell.len());
+            for (cell, _) in image_id_per_cell.iter() {
+                assert_eq!(2, cell.0);
+                assert_eq!(0, cell.1);
+            }
+            // the two-voxel segment always precedes the one-voxel segment with a gap between them
+            assert_eq!(image_id_per_cell[0].1, image_id_per_cell[1].1);
+            assert_eq!(image_id_per_cell[0].0.2 + 1, image_id_per_cell[1].0.2);
+            assert!(image_id_per_cell[1].0.2 + 1 < image_id_per_cell[2].0.2);
+            found_z_pairs.insert((image_id_per_cell[0].0.2, image_id_per_cell[2].0.2));
+        }
+        assert_eq!(BTreeSet::from([(1, 4), (1, 5), (2, 5)]), found_z_pairs);
+    }
+
+    #[rstest]
+    fn inner_voxel_remains_adjacent_to_wall() {
+        init();
+
+        let mut voxel_board: VoxelBoard<ExampleVoxel> = VoxelBoard::new(5, 5, 5);
+        // an L-shaped wall spreads across the face and so remains fixed
+        for cell in [(0, 1, 1), (0, 2, 1), (0, 1, 2)] {
+            voxel_board.set(cell.0, cell.1, cell.2, get_tile_voxel());
+        }
+        voxel_board.set(1, 1, 1, get_tile_voxel());
+        let voxel_board_randomizer = VoxelBoardRandomizer::new(voxel_board);
+        let mut found_inner_cells: BTreeSet<(usize, usize, usize)> = BTreeSet::new();
+        for _ in 0..50 {
+            let random_voxel_board = voxel_board_randomizer.get_random_voxel_board();
+            let image_id_per_cell = get_image_id_per_cell(&random_voxel_board);
+            assert_eq!(4, image_id_per_cell.len());
+            let inner_cells: Vec<(usize, usize, usize)> = image_id_per_cell.iter()
+                .map(|(cell, _)| *cell)
+                .filter(|cell| cell.0 != 0)
+                .collect();
+            assert_eq!(1, inner_cells.len());
+            assert_eq!(1, inner_cells[0].0);
+            found_inner_cells.insert(inner_cells[0]);
+        }
+        assert_eq!(BTreeSet::from([(1, 1, 1), (1, 2, 1), (1, 1, 2)]), found_inner_cells);
+    }
+}